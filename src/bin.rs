@@ -0,0 +1,155 @@
+use crate::reader::DataFrame;
+use crate::stats;
+use serde::Serialize;
+
+/// How bin edges are placed.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BinMethod {
+    /// Equal-width bins spanning the column's range.
+    Width,
+    /// Equal-frequency bins with edges at evenly spaced percentiles.
+    Quantile,
+}
+
+impl BinMethod {
+    /// Parse "width" or "quantile".
+    pub fn parse(value: &str) -> Option<BinMethod> {
+        match value {
+            "width" => Some(BinMethod::Width),
+            "quantile" => Some(BinMethod::Quantile),
+            _ => None,
+        }
+    }
+}
+
+/// A numeric column discretized into bins.
+#[derive(Debug, Clone, Serialize)]
+pub struct Binning {
+    pub column: String,
+    pub method: BinMethod,
+    /// `bins + 1` ascending edges; bin `i` spans `(edges[i], edges[i + 1]]`, and the
+    /// first bin also includes its lower edge.
+    pub edges: Vec<f64>,
+    pub counts: Vec<usize>,
+    /// 0-based bin of each row, `None` for missing or non-numeric cells.
+    #[serde(skip)]
+    pub assignments: Vec<Option<usize>>,
+}
+
+impl Binning {
+    /// 0-based indices of the bins that received no values.
+    pub fn empty_bins(&self) -> Vec<usize> {
+        (0..self.counts.len())
+            .filter(|&i| self.counts[i] == 0)
+            .collect()
+    }
+}
+
+/// Bin edges for sorted, non-empty values.
+fn edges(sorted: &[f64], bins: usize, method: BinMethod) -> Vec<f64> {
+    let (lo, hi) = (sorted[0], sorted[sorted.len() - 1]);
+    (0..=bins)
+        .map(|i| match method {
+            BinMethod::Width => lo + (hi - lo) * i as f64 / bins as f64,
+            BinMethod::Quantile => stats::percentile(sorted, 100.0 * i as f64 / bins as f64),
+        })
+        .collect()
+}
+
+/// The bin holding `value`: the first whose upper edge is at least `value`, so a
+/// value on a boundary goes to the lower bin.
+fn bin_of(edges: &[f64], value: f64) -> usize {
+    let last = edges.len() - 2;
+    edges[1..=last]
+        .iter()
+        .position(|&upper| value <= upper)
+        .unwrap_or(last)
+}
+
+/// Assign every numeric value of a column to one of `bins` bins.
+///
+/// Returns `None` if the column does not exist or `bins` is 0. With no numeric
+/// values all bins are empty and `edges` is empty.
+pub fn bin_column(df: &DataFrame, column: &str, bins: usize, method: BinMethod) -> Option<Binning> {
+    if bins == 0 {
+        return None;
+    }
+    let values = df.numeric_column(column)?;
+    let mut sorted: Vec<f64> = values.iter().flatten().copied().collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut counts = vec![0; bins];
+    if sorted.is_empty() {
+        return Some(Binning {
+            column: column.to_string(),
+            method,
+            edges: Vec::new(),
+            counts,
+            assignments: vec![None; values.len()],
+        });
+    }
+
+    let edges = edges(&sorted, bins, method);
+    let assignments: Vec<Option<usize>> = values
+        .iter()
+        .map(|v| v.map(|x| bin_of(&edges, x)))
+        .collect();
+    for bin in assignments.iter().flatten() {
+        counts[*bin] += 1;
+    }
+
+    Some(Binning {
+        column: column.to_string(),
+        method,
+        edges,
+        counts,
+        assignments,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantile_bins_have_equal_counts() {
+        let df = DataFrame::from_column("x", &["8", "1", "2", "3", "4", "5", "6", "7"]);
+        let b = bin_column(&df, "x", 4, BinMethod::Quantile).unwrap();
+        assert_eq!(b.edges, vec![1.0, 2.75, 4.5, 6.25, 8.0]);
+        assert_eq!(b.counts, vec![2, 2, 2, 2]);
+        assert_eq!(b.assignments[0], Some(3));
+        assert_eq!(b.assignments[1], Some(0));
+    }
+
+    #[test]
+    fn test_boundary_values_go_to_lower_bin() {
+        let df = DataFrame::from_column("x", &["0", "5", "10", "", "abc"]);
+        let b = bin_column(&df, "x", 2, BinMethod::Width).unwrap();
+        assert_eq!(b.edges, vec![0.0, 5.0, 10.0]);
+        assert_eq!(b.assignments, vec![Some(0), Some(0), Some(1), None, None]);
+        assert_eq!(b.counts, vec![2, 1]);
+    }
+
+    #[test]
+    fn test_heavy_ties_leave_empty_bins() {
+        let df = DataFrame::from_column("x", &["1", "1", "1", "1", "1", "1", "2", "9"]);
+        let b = bin_column(&df, "x", 4, BinMethod::Quantile).unwrap();
+        assert_eq!(b.counts.iter().sum::<usize>(), 8);
+        assert_eq!(b.counts[0], 6);
+        assert_eq!(b.empty_bins(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_bin_column_edge_cases() {
+        let df = DataFrame::from_column("x", &["3", "3"]);
+        let b = bin_column(&df, "x", 3, BinMethod::Width).unwrap();
+        assert_eq!(b.counts, vec![2, 0, 0]);
+        assert!(bin_column(&df, "x", 0, BinMethod::Width).is_none());
+        assert!(bin_column(&df, "nope", 3, BinMethod::Width).is_none());
+        let df = DataFrame::from_column("x", &["", "NA"]);
+        let empty = bin_column(&df, "x", 2, BinMethod::Quantile).unwrap();
+        assert!(empty.edges.is_empty());
+        assert_eq!(empty.assignments, vec![None, None]);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::plot::{self, HistogramBins};
+use anyhow::{anyhow, bail, Result};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+/// Pixel size of exported charts.
+const CHART_SIZE: (u32, u32) = (800, 500);
+
+fn image_format(path: &str) -> Option<&'static str> {
+    let extension = std::path::Path::new(path).extension()?.to_str()?;
+    if extension.eq_ignore_ascii_case("png") {
+        Some("png")
+    } else if extension.eq_ignore_ascii_case("svg") {
+        Some("svg")
+    } else {
+        None
+    }
+}
+
+/// Render a histogram to a PNG or SVG file, chosen by the extension of `path`.
+pub fn histogram(data: &HistogramBins, col_name: &str, path: &str) -> Result<()> {
+    match image_format(path) {
+        Some("png") => draw_histogram(
+            BitMapBackend::new(path, CHART_SIZE).into_drawing_area(),
+            data,
+            col_name,
+        ),
+        Some("svg") => draw_histogram(
+            SVGBackend::new(path, CHART_SIZE).into_drawing_area(),
+            data,
+            col_name,
+        ),
+        _ => bail!("Cannot render '{}': use a .png or .svg path", path),
+    }
+}
+
+/// Draw the bars and optional density curve on any plotters backend.
+fn draw_histogram<DB: DrawingBackend>(
+    root: DrawingArea<DB, Shift>,
+    data: &HistogramBins,
+    col_name: &str,
+) -> Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let draw_error = |e: DrawingAreaErrorKind<DB::ErrorType>| anyhow!("Cannot draw chart: {}", e);
+
+    let x_min = data.min;
+    let x_max = data.min + data.bin_width * data.counts.len() as f64;
+    let max_count = data.counts.iter().copied().max().unwrap_or(1) as f64;
+    let curve_max = data
+        .density
+        .iter()
+        .flatten()
+        .fold(0.0_f64, |acc, &v| acc.max(v));
+    let y_max = max_count.max(curve_max) * 1.05;
+
+    root.fill(&WHITE).map_err(draw_error)?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(format!("{} (n={})", col_name, data.n), ("sans-serif", 24))
+        .margin(15)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(x_min..x_max, 0.0..y_max)
+        .map_err(draw_error)?;
+
+    let x_desc = if data.log {
+        format!("{} (log scale)", col_name)
+    } else {
+        col_name.to_string()
+    };
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_desc(x_desc)
+        .x_label_formatter(&|v| plot::format_number_short(plot::to_original_units(*v, data.log)))
+        .y_desc("Count")
+        .y_label_formatter(&|v| format!("{:.0}", v))
+        .draw()
+        .map_err(draw_error)?;
+
+    let bar_style = BLUE.mix(0.6).filled();
+    chart
+        .draw_series(data.counts.iter().enumerate().map(|(i, &count)| {
+            let lower = data.min + i as f64 * data.bin_width;
+            let mut bar = Rectangle::new(
+                [(lower, 0.0), (lower + data.bin_width, count as f64)],
+                bar_style,
+            );
+            bar.set_margin(0, 0, 1, 1);
+            bar
+        }))
+        .map_err(draw_error)?;
+
+    if let Some(curve) = data.density.as_ref().filter(|c| !c.is_empty()) {
+        let points = curve
+            .iter()
+            .enumerate()
+            .map(|(i, &y)| (data.min + (i as f64 + 0.5) * data.bin_width, y));
+        chart
+            .draw_series(LineSeries::new(points, RED.stroke_width(2)))
+            .map_err(draw_error)?
+            .label("Density")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+        chart
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()
+            .map_err(draw_error)?;
+    }
+
+    root.present().map_err(draw_error)?;
+    Ok(())
+}
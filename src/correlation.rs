@@ -1,32 +1,121 @@
 use crate::reader::DataFrame;
 use crate::stats;
 use crate::types;
+use crate::utils::is_missing;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Association measure behind a correlation matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CorrelationMethod {
+    /// Pearson correlation between numeric columns.
+    Pearson,
+    /// Cramér's V between categorical columns.
+    CramersV,
+    /// Phi coefficient between missing-value indicators.
+    Missingness,
+}
+
+impl CorrelationMethod {
+    /// Name shown in titles, e.g. "Correlation Matrix (Pearson)".
+    pub fn label(&self) -> &'static str {
+        match self {
+            CorrelationMethod::Pearson => "Pearson",
+            CorrelationMethod::CramersV => "Cramér's V",
+            CorrelationMethod::Missingness => "missingness φ",
+        }
+    }
+
+    /// Symbol of the coefficient: `r` or `V`.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            CorrelationMethod::Pearson => "r",
+            CorrelationMethod::CramersV => "V",
+            CorrelationMethod::Missingness => "φ",
+        }
+    }
+}
 
 /// A correlation matrix result.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CorrelationMatrix {
+    pub method: CorrelationMethod,
     pub columns: Vec<String>,
     pub matrix: Vec<Vec<f64>>,
+    /// Number of pairwise complete observations behind each cell.
+    pub pairs: Vec<Vec<usize>>,
 }
 
-/// Compute Pearson correlation between two slices.
-/// Both slices must have the same length. Uses pairwise complete observations.
-fn pearson_correlation(x_all: &[Option<f64>], y_all: &[Option<f64>]) -> f64 {
+/// A pair of variables whose coefficient passed the reporting threshold.
+#[derive(Debug, Clone, Serialize)]
+pub struct HighCorrelation {
+    pub a: String,
+    pub b: String,
+    pub r: f64,
+    /// Pairwise complete observations.
+    pub n: usize,
+    /// Two-sided p-value against zero correlation (Pearson only).
+    pub p: Option<f64>,
+}
+
+/// Correlation between two variables after removing the linear effect of a third.
+#[derive(Debug, Clone, Serialize)]
+pub struct PartialCorrelation {
+    pub x: String,
+    pub y: String,
+    pub control: String,
+    /// Zero-order Pearson correlation of `x` and `y`.
+    pub r: f64,
+    /// Correlation of `x` and `y` controlling for `control`.
+    pub partial_r: f64,
+}
+
+/// Linear association of one column with a target column.
+#[derive(Debug, Clone, Serialize)]
+pub struct TargetCorrelation {
+    pub column: String,
+    pub r: f64,
+    /// Coefficient of determination of a simple linear fit, `r * r`.
+    pub r2: f64,
+    /// Pairwise complete observations.
+    pub n: usize,
+}
+
+/// Number of rows where both columns are present, for each pair of numeric columns.
+#[derive(Debug, Clone, Serialize)]
+pub struct OverlapMatrix {
+    pub columns: Vec<String>,
+    /// Pairwise complete observations; the diagonal holds each column's own count.
+    pub counts: Vec<Vec<usize>>,
+    /// Total number of rows in the file.
+    pub nrows: usize,
+}
+
+/// Observations where both x and y are present.
+/// Both slices must have the same length.
+fn complete_pairs(x_all: &[Option<f64>], y_all: &[Option<f64>]) -> Vec<(f64, f64)> {
     assert_eq!(x_all.len(), y_all.len());
 
-    // Use only observations where both x and y are present
-    let pairs: Vec<(f64, f64)> = x_all
+    x_all
         .iter()
         .zip(y_all.iter())
         .filter_map(|(a, b)| match (a, b) {
             (Some(x), Some(y)) => Some((*x, *y)),
             _ => None,
         })
-        .collect();
+        .collect()
+}
+
+/// Compute Pearson correlation between two slices.
+/// Both slices must have the same length. Uses pairwise complete observations,
+/// and returns their count alongside the coefficient.
+fn pearson_correlation(x_all: &[Option<f64>], y_all: &[Option<f64>]) -> (f64, usize) {
+    let pairs = complete_pairs(x_all, y_all);
 
     let n = pairs.len();
     if n < 2 {
-        return f64::NAN;
+        return (f64::NAN, n);
     }
 
     let x_vals: Vec<f64> = pairs.iter().map(|(x, _)| *x).collect();
@@ -48,14 +137,71 @@ fn pearson_correlation(x_all: &[Option<f64>], y_all: &[Option<f64>]) -> f64 {
     }
 
     if var_x == 0.0 || var_y == 0.0 {
-        return f64::NAN;
+        return (f64::NAN, n);
     }
 
-    cov / (var_x.sqrt() * var_y.sqrt())
+    (cov / (var_x.sqrt() * var_y.sqrt()), n)
 }
 
-/// Compute the correlation matrix for all numeric columns.
-pub fn correlation_matrix(df: &DataFrame, columns: Option<&[&str]>) -> CorrelationMatrix {
+/// Coefficient and complete-pair count for one cell `(i, j)` of a matrix.
+type PairResult = (usize, usize, (f64, usize));
+
+/// Evaluate `pair` for every cell above the diagonal of an `n` x `n` matrix.
+#[cfg(feature = "parallel")]
+fn upper_triangle<F>(n: usize, pair: F) -> Vec<PairResult>
+where
+    F: Fn(usize, usize) -> (f64, usize) + Sync,
+{
+    use rayon::prelude::*;
+
+    (0..n)
+        .into_par_iter()
+        .flat_map_iter(|i| ((i + 1)..n).map(move |j| (i, j)))
+        .map(|(i, j)| (i, j, pair(i, j)))
+        .collect()
+}
+
+/// Evaluate `pair` for every cell above the diagonal of an `n` x `n` matrix.
+#[cfg(not(feature = "parallel"))]
+fn upper_triangle<F>(n: usize, pair: F) -> Vec<PairResult>
+where
+    F: Fn(usize, usize) -> (f64, usize),
+{
+    (0..n)
+        .flat_map(|i| ((i + 1)..n).map(move |j| (i, j)))
+        .map(|(i, j)| (i, j, pair(i, j)))
+        .collect()
+}
+
+/// Build the symmetric coefficient and pair-count matrices from upper-triangle results.
+///
+/// The diagonal is 1 and counts each column's own non-missing values (`counts`).
+fn symmetric_matrix(
+    counts: &[usize],
+    results: Vec<PairResult>,
+) -> (Vec<Vec<f64>>, Vec<Vec<usize>>) {
+    let n = counts.len();
+    let mut matrix = vec![vec![0.0f64; n]; n];
+    let mut pairs = vec![vec![0usize; n]; n];
+
+    for (i, &count) in counts.iter().enumerate() {
+        matrix[i][i] = 1.0;
+        pairs[i][i] = count;
+    }
+    for (i, j, (r, count)) in results {
+        matrix[i][j] = r;
+        matrix[j][i] = r;
+        pairs[i][j] = count;
+        pairs[j][i] = count;
+    }
+
+    (matrix, pairs)
+}
+
+/// Names and values of the selected numeric columns, or of all numeric columns.
+///
+/// Selected columns that are missing or not numeric are left out.
+fn numeric_data(df: &DataFrame, columns: Option<&[&str]>) -> (Vec<String>, Vec<Vec<Option<f64>>>) {
     let col_names: Vec<String> = match columns {
         Some(cols) => cols
             .iter()
@@ -68,32 +214,331 @@ pub fn correlation_matrix(df: &DataFrame, columns: Option<&[&str]>) -> Correlati
         None => types::numeric_columns(df),
     };
 
-    let n = col_names.len();
-    let mut matrix = vec![vec![0.0f64; n]; n];
-
     // Pre-compute numeric columns (with Option values for pairwise completeness)
     let data: Vec<Vec<Option<f64>>> = col_names
         .iter()
         .map(|col| df.numeric_column(col).unwrap_or_default())
         .collect();
 
+    (col_names, data)
+}
+
+/// Compute the correlation matrix for all numeric columns.
+pub fn correlation_matrix(df: &DataFrame, columns: Option<&[&str]>) -> CorrelationMatrix {
+    let (col_names, data) = numeric_data(df, columns);
+
+    let counts: Vec<usize> = data
+        .iter()
+        .map(|col| col.iter().flatten().count())
+        .collect();
+    let results = upper_triangle(data.len(), |i, j| pearson_correlation(&data[i], &data[j]));
+    let (matrix, pairs) = symmetric_matrix(&counts, results);
+
+    CorrelationMatrix {
+        method: CorrelationMethod::Pearson,
+        columns: col_names,
+        matrix,
+        pairs,
+    }
+}
+
+/// Count the rows where both columns are present, for every pair of numeric columns.
+///
+/// Uses the same pairwise filtering as the correlation matrix, so each cell is the
+/// `n` behind the matching correlation coefficient.
+pub fn overlap_matrix(df: &DataFrame, columns: Option<&[&str]>) -> OverlapMatrix {
+    let (col_names, data) = numeric_data(df, columns);
+
+    let n = data.len();
+    let mut counts = vec![vec![0usize; n]; n];
     for i in 0..n {
-        matrix[i][i] = 1.0;
-        for j in (i + 1)..n {
-            let r = pearson_correlation(&data[i], &data[j]);
-            matrix[i][j] = r;
-            matrix[j][i] = r;
+        for j in i..n {
+            let count = complete_pairs(&data[i], &data[j]).len();
+            counts[i][j] = count;
+            counts[j][i] = count;
         }
     }
 
+    OverlapMatrix {
+        columns: col_names,
+        counts,
+        nrows: df.nrows(),
+    }
+}
+
+/// Correlate missing-value indicators between the columns that have missing values.
+///
+/// Each column becomes a 0/1 vector (1 where the cell is missing), and the Pearson
+/// correlation of two such vectors is their phi coefficient. Columns without any
+/// missing value are left out, as their indicator is constant.
+pub fn missingness_matrix(df: &DataFrame) -> CorrelationMatrix {
+    let mut col_names = Vec::new();
+    let mut data: Vec<Vec<Option<f64>>> = Vec::new();
+    for name in &df.headers {
+        let Some(values) = df.column(name) else {
+            continue;
+        };
+        let indicator: Vec<Option<f64>> = values
+            .iter()
+            .map(|v| Some(if is_missing(v) { 1.0 } else { 0.0 }))
+            .collect();
+        if indicator.contains(&Some(1.0)) {
+            col_names.push(name.clone());
+            data.push(indicator);
+        }
+    }
+
+    let counts = vec![df.nrows(); data.len()];
+    let results = upper_triangle(data.len(), |i, j| pearson_correlation(&data[i], &data[j]));
+    let (matrix, pairs) = symmetric_matrix(&counts, results);
+
+    CorrelationMatrix {
+        method: CorrelationMethod::Missingness,
+        columns: col_names,
+        matrix,
+        pairs,
+    }
+}
+
+/// Compute Cramér's V between two categorical columns.
+///
+/// Uses the chi-square statistic of their contingency table over rows where both
+/// values are present, and returns their count alongside the coefficient. The
+/// coefficient is `NaN` if either column has fewer than two levels.
+fn cramers_v(x_all: &[&str], y_all: &[&str]) -> (f64, usize) {
+    assert_eq!(x_all.len(), y_all.len());
+
+    let mut x_levels: Vec<&str> = Vec::new();
+    let mut y_levels: Vec<&str> = Vec::new();
+    let mut cells: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut n = 0usize;
+
+    for (x, y) in x_all.iter().zip(y_all) {
+        let (x, y) = (x.trim(), y.trim());
+        if is_missing(x) || is_missing(y) {
+            continue;
+        }
+        let i = level_index(&mut x_levels, x);
+        let j = level_index(&mut y_levels, y);
+        *cells.entry((i, j)).or_insert(0) += 1;
+        n += 1;
+    }
+
+    let k = x_levels.len().min(y_levels.len());
+    if k < 2 {
+        return (f64::NAN, n);
+    }
+
+    let mut row_totals = vec![0usize; x_levels.len()];
+    let mut col_totals = vec![0usize; y_levels.len()];
+    for (&(i, j), &count) in &cells {
+        row_totals[i] += count;
+        col_totals[j] += count;
+    }
+
+    let mut chi2 = 0.0;
+    for (i, &row_total) in row_totals.iter().enumerate() {
+        for (j, &col_total) in col_totals.iter().enumerate() {
+            let expected = row_total as f64 * col_total as f64 / n as f64;
+            let observed = cells.get(&(i, j)).copied().unwrap_or(0) as f64;
+            chi2 += (observed - expected).powi(2) / expected;
+        }
+    }
+
+    ((chi2 / (n as f64 * (k - 1) as f64)).sqrt(), n)
+}
+
+fn level_index<'a>(levels: &mut Vec<&'a str>, value: &'a str) -> usize {
+    match levels.iter().position(|l| *l == value) {
+        Some(i) => i,
+        None => {
+            levels.push(value);
+            levels.len() - 1
+        }
+    }
+}
+
+/// Compute the Cramér's V matrix for all categorical (and boolean) columns.
+pub fn cramers_v_matrix(df: &DataFrame, columns: Option<&[&str]>) -> CorrelationMatrix {
+    let categorical: Vec<String> = types::infer_types(df)
+        .into_iter()
+        .filter(|info| info.col_type != types::ColumnType::Numeric)
+        .map(|info| info.name)
+        .collect();
+    let col_names: Vec<String> = match columns {
+        Some(cols) => cols
+            .iter()
+            .filter(|c| categorical.iter().any(|name| name == *c))
+            .map(|c| c.to_string())
+            .collect(),
+        None => categorical,
+    };
+
+    let data: Vec<Vec<&str>> = col_names
+        .iter()
+        .map(|col| df.column(col).unwrap_or_default())
+        .collect();
+
+    let counts: Vec<usize> = data
+        .iter()
+        .map(|col| col.iter().filter(|v| !is_missing(v)).count())
+        .collect();
+    let results = upper_triangle(data.len(), |i, j| cramers_v(&data[i], &data[j]));
+    let (matrix, pairs) = symmetric_matrix(&counts, results);
+
     CorrelationMatrix {
+        method: CorrelationMethod::CramersV,
         columns: col_names,
         matrix,
+        pairs,
+    }
+}
+
+/// First-order partial correlation of x and y given z from the pairwise Pearson
+/// coefficients. `NaN` when x or y is perfectly correlated with z.
+pub fn partial_correlation(rxy: f64, rxz: f64, ryz: f64) -> f64 {
+    let denom = ((1.0 - rxz * rxz) * (1.0 - ryz * ryz)).sqrt();
+    if denom == 0.0 {
+        f64::NAN
+    } else {
+        (rxy - rxz * ryz) / denom
+    }
+}
+
+/// Partial correlation of `x` and `y` controlling for `control`, using pairwise
+/// complete observations. `None` if any of the three columns is not numeric.
+pub fn partial_correlation_of(
+    df: &DataFrame,
+    x: &str,
+    y: &str,
+    control: &str,
+) -> Option<PartialCorrelation> {
+    let numeric = types::numeric_columns(df);
+    if [x, y, control]
+        .iter()
+        .any(|c| !numeric.iter().any(|n| n == c))
+    {
+        return None;
+    }
+    let m = correlation_matrix(df, Some(&[x, y, control])).matrix;
+    Some(PartialCorrelation {
+        x: x.to_string(),
+        y: y.to_string(),
+        control: control.to_string(),
+        r: m[0][1],
+        partial_r: partial_correlation(m[0][1], m[0][2], m[1][2]),
+    })
+}
+
+/// Pearson r and R² of each numeric column against `target`, best fit first.
+///
+/// `columns` restricts the candidates; the target itself is always left out.
+/// `None` if the target is not a numeric column. Undefined coefficients sort last.
+pub fn target_correlations(
+    df: &DataFrame,
+    target: &str,
+    columns: Option<&[&str]>,
+) -> Option<Vec<TargetCorrelation>> {
+    let numeric = types::numeric_columns(df);
+    if !numeric.iter().any(|c| c == target) {
+        return None;
+    }
+    let y = df.numeric_column(target)?;
+
+    let mut result: Vec<TargetCorrelation> = numeric
+        .iter()
+        .filter(|c| *c != target)
+        .filter(|c| match columns {
+            Some(cols) => cols.contains(&c.as_str()),
+            None => true,
+        })
+        .map(|c| {
+            let x = df.numeric_column(c).unwrap_or_default();
+            let (r, n) = pearson_correlation(&x, &y);
+            TargetCorrelation {
+                column: c.clone(),
+                r,
+                r2: r * r,
+                n,
+            }
+        })
+        .collect();
+
+    result.sort_by(|a, b| match (a.r2.is_nan(), b.r2.is_nan()) {
+        (false, false) => b.r2.partial_cmp(&a.r2).unwrap(),
+        (nan_a, nan_b) => nan_a.cmp(&nan_b),
+    });
+    Some(result)
+}
+
+/// Reorder the matrix so strongly related columns sit next to each other.
+///
+/// Greedy nearest-neighbor ordering by |r|: start from the most strongly
+/// correlated pair, then repeatedly append the unplaced column most correlated
+/// with the last one placed. `NaN` coefficients count as zero and ties keep
+/// header order. Both the coefficients and the pair counts are permuted.
+pub fn cluster_order(cm: &CorrelationMatrix) -> CorrelationMatrix {
+    let n = cm.columns.len();
+    let strength = |i: usize, j: usize| {
+        let r = cm.matrix[i][j].abs();
+        if r.is_nan() {
+            0.0
+        } else {
+            r
+        }
+    };
+
+    let mut order: Vec<usize> = Vec::with_capacity(n);
+    let mut best: Option<(usize, usize)> = None;
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let stronger = match best {
+                Some((a, b)) => strength(i, j) > strength(a, b),
+                None => true,
+            };
+            if stronger {
+                best = Some((i, j));
+            }
+        }
+    }
+    match best {
+        Some((i, j)) => order.extend([i, j]),
+        None => order.extend(0..n),
+    }
+
+    while order.len() < n {
+        let last = order[order.len() - 1];
+        let mut next: Option<usize> = None;
+        for c in (0..n).filter(|c| !order.contains(c)) {
+            let closer = match next {
+                Some(current) => strength(last, c) > strength(last, current),
+                None => true,
+            };
+            if closer {
+                next = Some(c);
+            }
+        }
+        order.extend(next);
+    }
+
+    CorrelationMatrix {
+        method: cm.method,
+        columns: order.iter().map(|&i| cm.columns[i].clone()).collect(),
+        matrix: order
+            .iter()
+            .map(|&i| order.iter().map(|&j| cm.matrix[i][j]).collect())
+            .collect(),
+        pairs: order
+            .iter()
+            .map(|&i| order.iter().map(|&j| cm.pairs[i][j]).collect())
+            .collect(),
     }
 }
 
 /// Find high correlations above a threshold.
-pub fn high_correlations(cm: &CorrelationMatrix, threshold: f64) -> Vec<(String, String, f64)> {
+///
+/// Pearson pairs carry a two-sided p-value from the t-distribution.
+pub fn high_correlations(cm: &CorrelationMatrix, threshold: f64) -> Vec<HighCorrelation> {
     let mut result = Vec::new();
     let n = cm.columns.len();
 
@@ -101,12 +546,23 @@ pub fn high_correlations(cm: &CorrelationMatrix, threshold: f64) -> Vec<(String,
         for j in (i + 1)..n {
             let r = cm.matrix[i][j];
             if r.abs() >= threshold && !r.is_nan() {
-                result.push((cm.columns[i].clone(), cm.columns[j].clone(), r));
+                let pairs = cm.pairs[i][j];
+                let p = match cm.method {
+                    CorrelationMethod::Pearson => Some(stats::correlation_p_value(r, pairs)),
+                    CorrelationMethod::CramersV | CorrelationMethod::Missingness => None,
+                };
+                result.push(HighCorrelation {
+                    a: cm.columns[i].clone(),
+                    b: cm.columns[j].clone(),
+                    r,
+                    n: pairs,
+                    p,
+                });
             }
         }
     }
 
-    result.sort_by(|a, b| b.2.abs().partial_cmp(&a.2.abs()).unwrap_or(std::cmp::Ordering::Equal));
+    result.sort_by(|a, b| b.r.abs().partial_cmp(&a.r.abs()).unwrap_or(std::cmp::Ordering::Equal));
     result
 }
 
@@ -120,7 +576,7 @@ mod tests {
         // x and y are identical => r = 1.0
         let x: Vec<Option<f64>> = vec![Some(1.0), Some(2.0), Some(3.0), Some(4.0), Some(5.0)];
         let y: Vec<Option<f64>> = vec![Some(1.0), Some(2.0), Some(3.0), Some(4.0), Some(5.0)];
-        let r = pearson_correlation(&x, &y);
+        let (r, _) = pearson_correlation(&x, &y);
         assert!((r - 1.0).abs() < 1e-10);
     }
 
@@ -129,7 +585,7 @@ mod tests {
         // x goes up, y goes down => r = -1.0
         let x: Vec<Option<f64>> = vec![Some(1.0), Some(2.0), Some(3.0), Some(4.0), Some(5.0)];
         let y: Vec<Option<f64>> = vec![Some(5.0), Some(4.0), Some(3.0), Some(2.0), Some(1.0)];
-        let r = pearson_correlation(&x, &y);
+        let (r, _) = pearson_correlation(&x, &y);
         assert!((r - (-1.0)).abs() < 1e-10);
     }
 
@@ -138,7 +594,7 @@ mod tests {
         // Orthogonal pattern: no linear relationship
         let x: Vec<Option<f64>> = vec![Some(1.0), Some(0.0), Some(-1.0), Some(0.0)];
         let y: Vec<Option<f64>> = vec![Some(0.0), Some(1.0), Some(0.0), Some(-1.0)];
-        let r = pearson_correlation(&x, &y);
+        let (r, _) = pearson_correlation(&x, &y);
         assert!(r.abs() < 1e-10);
     }
 
@@ -148,8 +604,9 @@ mod tests {
         let x: Vec<Option<f64>> = vec![Some(1.0), None, Some(3.0), Some(4.0), Some(5.0)];
         let y: Vec<Option<f64>> = vec![Some(2.0), Some(4.0), None, Some(8.0), Some(10.0)];
         // Complete pairs: (1,2), (4,8), (5,10) => perfect correlation
-        let r = pearson_correlation(&x, &y);
+        let (r, n) = pearson_correlation(&x, &y);
         assert!((r - 1.0).abs() < 1e-10);
+        assert_eq!(n, 3);
     }
 
     #[test]
@@ -157,7 +614,7 @@ mod tests {
         let x: Vec<Option<f64>> = vec![Some(1.0), None, None];
         let y: Vec<Option<f64>> = vec![None, Some(2.0), None];
         // 0 complete pairs
-        let r = pearson_correlation(&x, &y);
+        let (r, _) = pearson_correlation(&x, &y);
         assert!(r.is_nan());
     }
 
@@ -166,7 +623,7 @@ mod tests {
         // One variable is constant => r is NaN (zero variance)
         let x: Vec<Option<f64>> = vec![Some(1.0), Some(2.0), Some(3.0)];
         let y: Vec<Option<f64>> = vec![Some(5.0), Some(5.0), Some(5.0)];
-        let r = pearson_correlation(&x, &y);
+        let (r, _) = pearson_correlation(&x, &y);
         assert!(r.is_nan());
     }
 
@@ -176,7 +633,7 @@ mod tests {
         // Expected r ~ 0.7746
         let x: Vec<Option<f64>> = vec![Some(1.0), Some(2.0), Some(3.0), Some(4.0), Some(5.0)];
         let y: Vec<Option<f64>> = vec![Some(2.0), Some(4.0), Some(5.0), Some(4.0), Some(5.0)];
-        let r = pearson_correlation(&x, &y);
+        let (r, _) = pearson_correlation(&x, &y);
         assert!((r - 0.7746).abs() < 0.01);
     }
 
@@ -232,10 +689,184 @@ mod tests {
         let high = high_correlations(&cm, 0.0);
         // Verify sorted by descending absolute value
         for i in 1..high.len() {
-            assert!(high[i - 1].2.abs() >= high[i].2.abs());
+            assert!(high[i - 1].r.abs() >= high[i].r.abs());
+        }
+    }
+
+    #[test]
+    fn test_overlap_counts_pairwise_complete_rows() {
+        let df = reader::read_file("tests/data/sample.csv").unwrap();
+        let om = overlap_matrix(&df, Some(&["income", "score"]));
+        assert_eq!(om.nrows, 30);
+        // income has 3 missing values and score 1, on a different row
+        assert_eq!(om.counts, vec![vec![27, 26], vec![26, 29]]);
+
+        // Every cell is the n behind the matching correlation
+        let cm = correlation_matrix(&df, None);
+        assert_eq!(overlap_matrix(&df, None).counts, cm.pairs);
+    }
+
+    #[test]
+    fn test_cramers_v_perfect_and_independent() {
+        let x = ["a", "a", "b", "b"];
+        assert!((cramers_v(&x, &["u", "u", "v", "v"]).0 - 1.0).abs() < 1e-10);
+        assert!(cramers_v(&x, &["u", "v", "u", "v"]).0.abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_cramers_v_single_level_is_nan() {
+        let x = ["a", "b", "a", "NA"];
+        assert!(cramers_v(&x, &["u", "u", "u", "v"]).0.is_nan());
+    }
+
+    #[test]
+    fn test_cramers_v_matrix_categorical_columns() {
+        let df = reader::read_file("tests/data/sample.csv").unwrap();
+        let cm = cramers_v_matrix(&df, None);
+        assert_eq!(cm.method, CorrelationMethod::CramersV);
+        assert_eq!(cm.columns, vec!["name", "gender", "city", "employed"]);
+        let v = cm.matrix[1][2];
+        assert!((0.0..=1.0).contains(&v));
+        assert_eq!(v, cm.matrix[2][1]);
+        // Numeric columns are ignored even when requested
+        assert_eq!(cramers_v_matrix(&df, Some(&["age", "city"])).columns, vec!["city"]);
+    }
+
+    #[test]
+    fn test_missingness_matrix_indicators() {
+        let rows = [
+            ["NA", "", "1", "1"],
+            ["NA", "NA", "2", "1"],
+            ["1", "2", "NA", "1"],
+            ["2", "3", "NA", "1"],
+            ["3", "4", "5", "1"],
+        ];
+        let df = DataFrame::from_rows(&["a", "b", "c", "d"], &rows);
+        let cm = missingness_matrix(&df);
+        assert_eq!(cm.method, CorrelationMethod::Missingness);
+        // d is never missing
+        assert_eq!(cm.columns, vec!["a", "b", "c"]);
+        assert!((cm.matrix[0][1] - 1.0).abs() < 1e-10);
+        assert!(cm.matrix[0][2] < 0.0);
+        assert_eq!(cm.pairs[0][2], 5);
+
+        let high = high_correlations(&cm, 0.5);
+        assert_eq!((high[0].a.as_str(), high[0].b.as_str()), ("a", "b"));
+        assert!(high[0].p.is_none());
+    }
+
+    #[test]
+    fn test_cluster_order_groups_related_columns() {
+        // a-c and b-d are strongly related; a-b, c-d and the rest are not
+        let matrix = vec![
+            vec![1.0, 0.1, 0.9, 0.0],
+            vec![0.1, 1.0, 0.2, -0.8],
+            vec![0.9, 0.2, 1.0, f64::NAN],
+            vec![0.0, -0.8, f64::NAN, 1.0],
+        ];
+        let pairs: Vec<Vec<usize>> = (0..4).map(|i| (0..4).map(|j| 10 * i + j).collect()).collect();
+        let cm = CorrelationMatrix {
+            method: CorrelationMethod::Pearson,
+            columns: ["a", "b", "c", "d"].iter().map(|c| c.to_string()).collect(),
+            matrix,
+            pairs,
+        };
+
+        let ordered = cluster_order(&cm);
+        assert_eq!(ordered.columns, ["a", "c", "b", "d"]);
+
+        let mut columns = ordered.columns.clone();
+        columns.sort();
+        assert_eq!(columns, cm.columns);
+        for i in 0..4 {
+            assert_eq!(ordered.matrix[i][i], 1.0);
+            for j in 0..4 {
+                let (x, y) = (ordered.matrix[i][j], ordered.matrix[j][i]);
+                assert!(x == y || (x.is_nan() && y.is_nan()));
+                let original = |name: &str| cm.columns.iter().position(|c| c == name).unwrap();
+                let (oi, oj) = (original(&ordered.columns[i]), original(&ordered.columns[j]));
+                assert_eq!(ordered.pairs[i][j], cm.pairs[oi][oj]);
+            }
         }
     }
 
+    #[test]
+    fn test_high_correlations_carry_n_and_p() {
+        let df = reader::read_file("tests/data/sample.csv").unwrap();
+        let cm = correlation_matrix(&df, Some(&["age", "income"]));
+        let high = high_correlations(&cm, 0.0);
+        assert_eq!(high.len(), 1);
+        // income has 3 missing values
+        assert_eq!(high[0].n, 27);
+        let p = high[0].p.unwrap();
+        assert!((0.0..0.001).contains(&p));
+
+        let cv = cramers_v_matrix(&df, Some(&["gender", "city"]));
+        assert!(high_correlations(&cv, 0.0)[0].p.is_none());
+    }
+
+    #[test]
+    fn test_matrix_matches_serial_pairwise_loop() {
+        let df = reader::read_file("tests/data/sample.csv").unwrap();
+        let cm = correlation_matrix(&df, None);
+        let data: Vec<Vec<Option<f64>>> = cm
+            .columns
+            .iter()
+            .map(|c| df.numeric_column(c).unwrap())
+            .collect();
+        for i in 0..data.len() {
+            for j in 0..data.len() {
+                if i == j {
+                    continue;
+                }
+                let (r, n) = pearson_correlation(&data[i], &data[j]);
+                assert_eq!(cm.matrix[i][j].to_bits(), r.to_bits());
+                assert_eq!(cm.pairs[i][j], n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_partial_correlation_formula() {
+        // (0.5 - 0.5 * 0.5) / sqrt(0.75 * 0.75) = 1/3
+        assert!((partial_correlation(0.5, 0.5, 0.5) - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(partial_correlation(0.3, 0.0, 0.0), 0.3);
+    }
+
+    #[test]
+    fn test_partial_correlation_zero_denominator_is_nan() {
+        assert!(partial_correlation(0.4, 1.0, 0.2).is_nan());
+        assert!(partial_correlation(0.4, 0.2, -1.0).is_nan());
+    }
+
+    #[test]
+    fn test_partial_correlation_of_columns() {
+        let df = reader::read_file("tests/data/sample.csv").unwrap();
+        let pc = partial_correlation_of(&df, "age", "income", "score").unwrap();
+        let cm = correlation_matrix(&df, Some(&["age", "income"]));
+        assert_eq!(pc.r, cm.matrix[0][1]);
+        assert!(pc.partial_r.abs() <= 1.0);
+        assert!(partial_correlation_of(&df, "age", "income", "city").is_none());
+    }
+
+    #[test]
+    fn test_target_correlations_sorted_by_r2() {
+        let df = reader::read_file("tests/data/sample.csv").unwrap();
+        let result = target_correlations(&df, "income", None).unwrap();
+        let names: Vec<&str> = result.iter().map(|t| t.column.as_str()).collect();
+        assert_eq!(names.len(), 3);
+        assert!(!names.contains(&"income"));
+        assert!(result.windows(2).all(|w| w[0].r2 >= w[1].r2));
+        assert_eq!(result[0].column, "age");
+        assert_eq!(result[0].n, 27);
+        assert!((result[0].r2 - result[0].r * result[0].r).abs() < 1e-12);
+
+        let only = target_correlations(&df, "income", Some(&["score"])).unwrap();
+        assert_eq!(only.len(), 1);
+        assert!(target_correlations(&df, "nope", None).is_none());
+        assert!(target_correlations(&df, "city", None).is_none());
+    }
+
     #[test]
     fn test_nonexistent_column_filtered() {
         let df = reader::read_file("tests/data/sample.csv").unwrap();
@@ -0,0 +1,179 @@
+use crate::reader::DataFrame;
+use crate::utils::is_missing;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Maximum number of levels kept on each side of a contingency table.
+pub const MAX_LEVELS: usize = 30;
+
+/// How counts are converted to proportions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Normalize {
+    /// Each row sums to 1.
+    Row,
+    /// Each column sums to 1.
+    Col,
+    /// The whole table sums to 1.
+    All,
+}
+
+impl Normalize {
+    /// Parse "row", "col" or "all".
+    pub fn parse(value: &str) -> Option<Normalize> {
+        match value {
+            "row" | "rows" => Some(Normalize::Row),
+            "col" | "cols" | "column" | "columns" => Some(Normalize::Col),
+            "all" => Some(Normalize::All),
+            _ => None,
+        }
+    }
+}
+
+/// A two-way frequency table of two categorical columns.
+#[derive(Debug, Clone, Serialize)]
+pub struct Crosstab {
+    pub row_var: String,
+    pub col_var: String,
+    pub row_levels: Vec<String>,
+    pub col_levels: Vec<String>,
+    /// `counts[i][j]` is the number of rows with `row_levels[i]` and `col_levels[j]`.
+    pub counts: Vec<Vec<usize>>,
+    /// Number of row levels dropped to stay within `MAX_LEVELS`.
+    pub dropped_rows: usize,
+    /// Number of column levels dropped to stay within `MAX_LEVELS`.
+    pub dropped_cols: usize,
+}
+
+impl Crosstab {
+    /// Sum of each row.
+    pub fn row_totals(&self) -> Vec<usize> {
+        self.counts.iter().map(|row| row.iter().sum()).collect()
+    }
+
+    /// Sum of each column.
+    pub fn col_totals(&self) -> Vec<usize> {
+        (0..self.col_levels.len())
+            .map(|j| self.counts.iter().map(|row| row[j]).sum())
+            .collect()
+    }
+
+    /// Sum of all cells.
+    pub fn total(&self) -> usize {
+        self.row_totals().iter().sum()
+    }
+}
+
+/// Build a contingency table of `row_var` by `col_var`.
+///
+/// Rows where either value is missing are skipped. Levels are sorted by name; when a
+/// variable has more than `MAX_LEVELS` levels only the most frequent ones are kept.
+pub fn crosstab(df: &DataFrame, row_var: &str, col_var: &str) -> Option<Crosstab> {
+    let rows = df.column(row_var)?;
+    let cols = df.column(col_var)?;
+
+    let pairs: Vec<(&str, &str)> = rows
+        .iter()
+        .zip(&cols)
+        .map(|(r, c)| (r.trim(), c.trim()))
+        .filter(|(r, c)| !is_missing(r) && !is_missing(c))
+        .collect();
+
+    let (row_levels, dropped_rows) = top_levels(pairs.iter().map(|p| p.0));
+    let (col_levels, dropped_cols) = top_levels(pairs.iter().map(|p| p.1));
+
+    let row_index: HashMap<&str, usize> = row_levels
+        .iter()
+        .enumerate()
+        .map(|(i, l)| (l.as_str(), i))
+        .collect();
+    let col_index: HashMap<&str, usize> = col_levels
+        .iter()
+        .enumerate()
+        .map(|(j, l)| (l.as_str(), j))
+        .collect();
+
+    let mut counts = vec![vec![0usize; col_levels.len()]; row_levels.len()];
+    for (r, c) in &pairs {
+        if let (Some(&i), Some(&j)) = (row_index.get(r), col_index.get(c)) {
+            counts[i][j] += 1;
+        }
+    }
+
+    Some(Crosstab {
+        row_var: row_var.to_string(),
+        col_var: col_var.to_string(),
+        row_levels,
+        col_levels,
+        counts,
+        dropped_rows,
+        dropped_cols,
+    })
+}
+
+/// Distinct values sorted by name, limited to the `MAX_LEVELS` most frequent.
+/// Returns the kept levels and how many were dropped.
+fn top_levels<'a>(values: impl Iterator<Item = &'a str>) -> (Vec<String>, usize) {
+    let mut freq: HashMap<&str, usize> = HashMap::new();
+    for v in values {
+        *freq.entry(v).or_insert(0) += 1;
+    }
+
+    let mut levels: Vec<(&str, usize)> = freq.into_iter().collect();
+    levels.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    let dropped = levels.len().saturating_sub(MAX_LEVELS);
+    levels.truncate(MAX_LEVELS);
+
+    let mut names: Vec<String> = levels.into_iter().map(|(l, _)| l.to_string()).collect();
+    names.sort();
+    (names, dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader;
+
+    #[test]
+    fn test_crosstab_sample_counts_and_totals() {
+        let df = reader::read_file("tests/data/sample.csv").unwrap();
+        let ct = crosstab(&df, "gender", "employed").unwrap();
+        assert_eq!(ct.row_levels, ["F", "M", "Other"]);
+        assert_eq!(ct.col_levels, ["false", "true"]);
+        assert_eq!(ct.row_totals(), [14, 15, 1]);
+        assert_eq!(ct.col_totals().iter().sum::<usize>(), 30);
+        assert_eq!(ct.total(), 30);
+        assert_eq!(ct.dropped_rows, 0);
+    }
+
+    #[test]
+    fn test_crosstab_skips_missing_pairs() {
+        let df = reader::read_file("tests/data/sample.csv").unwrap();
+        let ct = crosstab(&df, "gender", "city").unwrap();
+        assert_eq!(ct.total(), 28);
+        assert!(crosstab(&df, "gender", "nope").is_none());
+    }
+
+    #[test]
+    fn test_crosstab_truncates_levels() {
+        let rows: Vec<[String; 2]> = (0..40)
+            .flat_map(|i| {
+                let copies = if i < 5 { 3 } else { 1 };
+                (0..copies).map(move |_| [format!("L{:02}", i), "x".to_string()])
+            })
+            .collect();
+        let df = DataFrame::from_rows(&["a", "b"], &rows);
+        let ct = crosstab(&df, "a", "b").unwrap();
+        assert_eq!(ct.row_levels.len(), MAX_LEVELS);
+        assert_eq!(ct.dropped_rows, 10);
+        assert!(ct.row_levels.contains(&"L00".to_string()));
+        assert_eq!(ct.total(), 5 * 3 + 25);
+    }
+
+    #[test]
+    fn test_normalize_parse() {
+        assert_eq!(Normalize::parse("row"), Some(Normalize::Row));
+        assert_eq!(Normalize::parse("col"), Some(Normalize::Col));
+        assert_eq!(Normalize::parse("all"), Some(Normalize::All));
+        assert_eq!(Normalize::parse("cell"), None);
+    }
+}
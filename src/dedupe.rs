@@ -0,0 +1,54 @@
+use crate::reader::DataFrame;
+use anyhow::{bail, Result};
+use std::collections::HashSet;
+
+/// Joins key fields; a control character that does not occur in normal data.
+pub(crate) const KEY_SEPARATOR: &str = "\u{1f}";
+
+/// Indices of the first occurrence of each distinct row, in their original order.
+///
+/// With `subset`, rows are compared on those columns only; otherwise on all of them.
+pub fn unique_rows(df: &DataFrame, subset: Option<&[String]>) -> Result<Vec<usize>> {
+    let key_cols: Vec<usize> = match subset {
+        Some(cols) => {
+            let mut indices = Vec::with_capacity(cols.len());
+            for col in cols {
+                let Some(idx) = df.col_index(col) else {
+                    bail!("Column '{}' not found", col);
+                };
+                indices.push(idx);
+            }
+            indices
+        }
+        None => (0..df.ncols()).collect(),
+    };
+
+    let mut seen: HashSet<String> = HashSet::new();
+    Ok((0..df.nrows())
+        .filter(|&i| {
+            let key: Vec<&str> = key_cols.iter().map(|&c| df.rows[i][c].as_str()).collect();
+            seen.insert(key.join(KEY_SEPARATOR))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::read_file;
+
+    #[test]
+    fn test_unique_rows_keeps_first_occurrences() {
+        let df = read_file("tests/data/duplicates.csv").unwrap();
+        assert_eq!(df.nrows(), 6);
+        assert_eq!(unique_rows(&df, None).unwrap(), vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn test_unique_rows_subset() {
+        let df = read_file("tests/data/duplicates.csv").unwrap();
+        let city = ["city".to_string()];
+        assert_eq!(unique_rows(&df, Some(&city)).unwrap(), vec![0, 1]);
+        assert!(unique_rows(&df, Some(&["nope".to_string()])).is_err());
+    }
+}
@@ -1,39 +1,367 @@
-use crate::correlation::CorrelationMatrix;
+use crate::bin::{BinMethod, Binning};
+use crate::correlation::{
+    CorrelationMatrix, CorrelationMethod, HighCorrelation, OverlapMatrix, PartialCorrelation,
+    TargetCorrelation,
+};
+use crate::crosstab::{Crosstab, Normalize};
+use crate::keys::KeyCandidate;
 use crate::missing::{MissingInfo, MissingPatternReport};
-use crate::stats::{CategoricalSummary, DescriptiveStats};
+use crate::profile::ColumnProfile;
+use crate::reader::DataFrame;
+use crate::report::{Report, Table, TableStyle};
+use crate::sketch;
+use crate::stats::{CategoricalSummary, DescriptiveStats, ValueCounts};
+use crate::tests::{Anova, KsTest, JARQUE_BERA_MIN_N};
 use crate::types::ColumnTypeInfo;
-use colored::Colorize;
-use tabled::{builder::Builder, settings::Style};
+use crate::validate::ColumnValidation;
+use colored::{ColoredString, Colorize};
+use std::collections::HashMap;
+use tabled::builder::Builder;
+
+/// Display settings shared by the formatters.
+#[derive(Debug, Clone, Default)]
+pub struct DisplayOptions {
+    /// Fixed number of decimal places; `None` keeps the magnitude-based defaults.
+    pub precision: Option<usize>,
+    /// Per-column decimal places that override `precision` in the summary tables.
+    pub precisions: HashMap<String, usize>,
+    /// Maximum line width for wide layouts like the correlation matrix; `None` is unlimited.
+    pub width: Option<usize>,
+    /// Print whole-valued order statistics (min, quartiles, max, IQR, range) without decimals.
+    pub round: bool,
+    /// Borders of tables drawn for the terminal.
+    pub style: TableStyle,
+    /// Most variables shown by the types, summary and correlation formatters; `None` is unlimited.
+    pub max_columns: Option<usize>,
+    /// Highlight values with ANSI colors (off for `--no-color`, `NO_COLOR` and tests).
+    pub color: bool,
+}
+
+impl DisplayOptions {
+    /// Options for the values of `column`, with its entry in `precisions` (if any)
+    /// taking the place of the global precision.
+    pub fn for_column(&self, column: &str) -> DisplayOptions {
+        DisplayOptions {
+            precision: self.precisions.get(column).copied().or(self.precision),
+            precisions: HashMap::new(),
+            ..*self
+        }
+    }
+
+    /// The first `max_columns` of `items`, or all of them when unlimited.
+    pub fn limit<'a, T>(&self, items: &'a [T]) -> &'a [T] {
+        let n = self.max_columns.map_or(items.len(), |max| max.min(items.len()));
+        &items[..n]
+    }
+
+    /// "... and M more" when `max_columns` leaves out some of `total` variables.
+    pub fn truncation_note(&self, total: usize) -> Option<String> {
+        self.max_columns
+            .filter(|&max| max < total)
+            .map(|max| format!("... and {} more", total - max))
+    }
+
+    /// `text` with `style` applied, or unchanged when color is off.
+    fn paint(&self, text: &str, style: impl Fn(&str) -> ColoredString) -> String {
+        if self.color {
+            style(text).to_string()
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// These options, with color also turned off when `color` is false.
+    pub fn with_color(&self, color: bool) -> DisplayOptions {
+        DisplayOptions {
+            color: self.color && color,
+            ..self.clone()
+        }
+    }
+
+    /// Decimal places to use where a formatter would otherwise use `default`.
+    pub fn decimals(&self, default: usize) -> usize {
+        self.precision.unwrap_or(default)
+    }
+
+    /// How many of `total` matrix columns of `col_width` fit beside a row label.
+    ///
+    /// Never more than `max_columns`.
+    fn visible_columns(&self, total: usize, col_width: usize) -> usize {
+        let fit = match self.width {
+            Some(width) => (width.saturating_sub(col_width + 1) / col_width).clamp(1, total.max(1)),
+            None => total,
+        };
+        self.max_columns.map_or(fit, |max| fit.min(max))
+    }
+}
+
+/// Reads an optional summary statistic from a row.
+type OptionalStat = fn(&DescriptiveStats) -> Option<f64>;
+
+/// Optional statistics that at least one row carries, with their labels.
+fn optional_stats(stats: &[DescriptiveStats]) -> Vec<(&'static str, OptionalStat)> {
+    let candidates: [(&'static str, OptionalStat); 3] = [
+        ("Trimmed Mean", |s| s.trimmed_mean),
+        ("Geometric Mean", |s| s.geometric_mean),
+        ("Harmonic Mean", |s| s.harmonic_mean),
+    ];
+    candidates
+        .into_iter()
+        .filter(|(_, get)| stats.iter().any(|s| get(s).is_some()))
+        .collect()
+}
+
+/// Column label of the Jarque–Bera normality p-value.
+const JB_LABEL: &str = "JB p";
+
+/// Column label of the bootstrap median interval (see `stats::BOOTSTRAP_LEVEL`).
+const MEDIAN_CI_LABEL: &str = "Median 95% CI";
+
+/// A bootstrap interval as `[low, high]`, or `NaN` when it was not computed.
+fn format_interval(ci: Option<(f64, f64)>, opts: &DisplayOptions) -> String {
+    match ci {
+        Some((low, high)) if !low.is_nan() => {
+            format!("[{}, {}]", format_f64(low, opts), format_f64(high, opts))
+        }
+        _ => "NaN".to_string(),
+    }
+}
 
 /// Format descriptive statistics as a table.
-pub fn format_summary(stats: &[DescriptiveStats]) -> String {
-    let mut builder = Builder::new();
-    builder.push_record([
-        "Variable", "Count", "Mean", "Std", "Min", "Q1", "Median", "Q3", "Max",
-    ]);
+///
+/// Optional statistics such as the trimmed or geometric mean get a column after
+/// Mean when present, and a bootstrap median interval one after Median. With
+/// `spread`, IQR and Range columns follow Max, then a Jarque–Bera p-value if computed.
+/// Each row uses its column's entry in `opts.precisions`, if any. Only the first
+/// `opts.max_columns` variables are listed (see `DisplayOptions::truncation_note`).
+pub fn format_summary(
+    stats: &[DescriptiveStats],
+    bimodal_threshold: f64,
+    spread: bool,
+    signs: bool,
+    opts: &DisplayOptions,
+) -> Table {
+    let stats = opts.limit(stats);
+    let optional = optional_stats(stats);
+    let median_ci = stats.iter().any(|s| s.median_ci.is_some());
+    let mut header = vec!["Variable", "Count"];
+    if signs {
+        header.extend(["Zeros", "Negatives"]);
+    }
+    header.push("Mean");
+    header.extend(optional.iter().map(|(label, _)| *label));
+    header.extend(["Std", "Min", "Q1", "Median"]);
+    if median_ci {
+        header.push(MEDIAN_CI_LABEL);
+    }
+    header.extend(["Q3", "Max"]);
+    if spread {
+        header.extend(["IQR", "Range"]);
+    }
+    let normality = stats.iter().any(|s| s.jb_p.is_some());
+    if normality {
+        header.push(JB_LABEL);
+    }
+    header.push("Multimodal?");
+    let mut table = Table::new(&header).with_name("summary");
 
     for s in stats {
-        builder.push_record([
-            s.name.clone(),
-            s.count.to_string(),
-            format_f64(s.mean),
-            format_f64(s.std_dev),
-            format_f64(s.min),
-            format_f64(s.q1),
-            format_f64(s.median),
-            format_f64(s.q3),
-            format_f64(s.max),
+        let opts = &opts.for_column(&s.name);
+        let mut row = vec![s.name.clone(), s.count.to_string()];
+        if signs {
+            row.extend([s.zeros.to_string(), s.negatives.to_string()]);
+        }
+        row.push(format_f64(s.mean, opts));
+        row.extend(
+            optional
+                .iter()
+                .map(|(_, get)| format_f64(get(s).unwrap_or(f64::NAN), opts)),
+        );
+        row.extend([
+            format_f64(s.std_dev, opts),
+            format_order_stat(s.min, opts),
+            format_order_stat(s.q1, opts),
+            format_order_stat(s.median, opts),
+        ]);
+        if median_ci {
+            row.push(format_interval(s.median_ci, opts));
+        }
+        row.extend([
+            format_order_stat(s.q3, opts),
+            format_order_stat(s.max, opts),
         ]);
+        if spread {
+            row.extend([
+                format_order_stat(s.iqr, opts),
+                format_order_stat(s.range, opts),
+            ]);
+        }
+        if normality {
+            row.push(format_p_cell(s.jb_p.unwrap_or(f64::NAN), opts));
+        }
+        row.push(multimodal_hint(s, bimodal_threshold));
+        table.push_row(row);
+    }
+
+    table
+}
+
+/// Note naming the columns too small for the Jarque–Bera test, if any were tested.
+pub fn normality_note(stats: &[DescriptiveStats]) -> Option<String> {
+    let small: Vec<&str> = stats
+        .iter()
+        .filter(|s| s.jb_p.is_some() && s.count < JARQUE_BERA_MIN_N)
+        .map(|s| s.name.as_str())
+        .collect();
+    (!small.is_empty()).then(|| {
+        format!(
+            "JB p is NaN with fewer than {} values, where the test is unreliable: {}",
+            JARQUE_BERA_MIN_N,
+            small.join(", ")
+        )
+    })
+}
+
+/// Note that the summarized values were winsorized at `proportion` per tail.
+pub fn winsorize_note(proportion: f64) -> String {
+    let pct = |p: f64| (p * 1000.0).round() / 10.0;
+    format!(
+        "Values were winsorized to the {}%–{}% percentile range before summarizing.",
+        pct(proportion),
+        pct(1.0 - proportion)
+    )
+}
+
+/// Note that the unique counts of `columns` are sketch estimates, without value frequencies.
+pub fn approx_cardinality_note(columns: &[String]) -> String {
+    let error = sketch::relative_error(sketch::DEFAULT_PRECISION);
+    format!(
+        "Unique counts of {} are HyperLogLog estimates (standard error about {:.1}%); \
+         their value frequencies are not tracked.",
+        columns.join(", "),
+        error * 100.0
+    )
+}
+
+/// "yes" when the bimodality coefficient exceeds `threshold`, blank when it is unknown.
+fn multimodal_hint(s: &DescriptiveStats, threshold: f64) -> String {
+    let bc = s.bimodality();
+    if bc.is_nan() {
+        String::new()
+    } else if bc > threshold {
+        "yes".to_string()
+    } else {
+        "no".to_string()
+    }
+}
+
+/// Format descriptive statistics with statistics as rows and variables as columns.
+///
+/// Only the first `opts.max_columns` variables get a column.
+pub fn format_summary_transposed(
+    stats: &[DescriptiveStats],
+    bimodal_threshold: f64,
+    spread: bool,
+    signs: bool,
+    opts: &DisplayOptions,
+) -> Table {
+    let stats = opts.limit(stats);
+    let mut header = vec!["Statistic".to_string()];
+    header.extend(stats.iter().map(|s| s.name.clone()));
+    let mut table = Table::new(&header).with_name("summary");
+
+    table.push_row(
+        std::iter::once("Count".to_string())
+            .chain(stats.iter().map(|s| s.count.to_string()))
+            .collect(),
+    );
+    if signs {
+        table.push_row(
+            std::iter::once("Zeros".to_string())
+                .chain(stats.iter().map(|s| s.zeros.to_string()))
+                .collect(),
+        );
+        table.push_row(
+            std::iter::once("Negatives".to_string())
+                .chain(stats.iter().map(|s| s.negatives.to_string()))
+                .collect(),
+        );
+    }
+
+    let mut labels = vec!["Mean", "Std", "Min", "Q1", "Median", "Q3", "Max"];
+    if spread {
+        labels.extend(["IQR", "Range"]);
     }
+    for (k, label) in labels.iter().enumerate() {
+        // Everything after the mean and std is an order statistic or a difference of two
+        let format = if k < 2 { format_f64 } else { format_order_stat };
+        table.push_row(
+            std::iter::once(label.to_string())
+                .chain(stats.iter().map(|s| {
+                    let values = [
+                        s.mean, s.std_dev, s.min, s.q1, s.median, s.q3, s.max, s.iqr, s.range,
+                    ];
+                    format(values[k], &opts.for_column(&s.name))
+                }))
+                .collect(),
+        );
+        if k == 0 {
+            for (optional_label, get) in optional_stats(stats) {
+                table.push_row(
+                    std::iter::once(optional_label.to_string())
+                        .chain(stats.iter().map(|s| {
+                            format_f64(get(s).unwrap_or(f64::NAN), &opts.for_column(&s.name))
+                        }))
+                        .collect(),
+                );
+            }
+        }
+        if *label == "Median" && stats.iter().any(|s| s.median_ci.is_some()) {
+            table.push_row(
+                std::iter::once(MEDIAN_CI_LABEL.to_string())
+                    .chain(
+                        stats
+                            .iter()
+                            .map(|s| format_interval(s.median_ci, &opts.for_column(&s.name))),
+                    )
+                    .collect(),
+            );
+        }
+    }
+
+    if stats.iter().any(|s| s.jb_p.is_some()) {
+        table.push_row(
+            std::iter::once(JB_LABEL.to_string())
+                .chain(
+                    stats
+                        .iter()
+                        .map(|s| format_p_cell(s.jb_p.unwrap_or(f64::NAN), opts)),
+                )
+                .collect(),
+        );
+    }
+    table.push_row(
+        std::iter::once("Multimodal?".to_string())
+            .chain(stats.iter().map(|s| multimodal_hint(s, bimodal_threshold)))
+            .collect(),
+    );
 
-    let table = builder.build().with(Style::rounded()).to_string();
     table
 }
 
 /// Format categorical summaries as a table.
-pub fn format_categorical(summaries: &[CategoricalSummary]) -> String {
-    let mut builder = Builder::new();
-    builder.push_record(["Variable", "Total", "Missing", "Unique", "Top Values"]);
+///
+/// With `diversity`, entropy, normalized entropy and Gini impurity columns are
+/// added before the top values.
+pub fn format_categorical(summaries: &[CategoricalSummary], diversity: bool) -> Table {
+    let mut header = vec!["Variable", "Total", "Missing", "Unique"];
+    if diversity {
+        header.extend(["Entropy", "Norm. Entropy", "Gini"]);
+    }
+    header.push("Top Values");
+    let mut table = Table::new(&header)
+        .with_title("Categorical Variables")
+        .with_name("categorical");
 
     for s in summaries {
         let top: String = s
@@ -44,102 +372,376 @@ pub fn format_categorical(summaries: &[CategoricalSummary]) -> String {
             .collect::<Vec<_>>()
             .join(", ");
 
-        builder.push_record([
+        let mut row = vec![
             s.name.clone(),
             s.total.to_string(),
             s.missing.to_string(),
             s.unique.to_string(),
-            top,
-        ]);
+        ];
+        if diversity {
+            row.extend([s.entropy, s.normalized_entropy, s.gini].map(|v| format!("{:.3}", v)));
+        }
+        row.push(top);
+        table.push_row(row);
+    }
+
+    table
+}
+
+/// Format the missing-value tokens found in each categorical column, one row per token.
+///
+/// Empty cells are shown as `(empty)`; columns without missing values are left out.
+pub fn format_missing_tokens(summaries: &[CategoricalSummary]) -> Table {
+    let mut table = Table::new(&["Variable", "Token", "Count"])
+        .with_title("Missing tokens")
+        .with_name("missing_tokens");
+    for s in summaries {
+        for (token, count) in s.missing_tokens.iter().flatten() {
+            let token = if token.is_empty() {
+                "(empty)".to_string()
+            } else {
+                token.clone()
+            };
+            table.push_row(vec![s.name.clone(), token, count.to_string()]);
+        }
+    }
+    table
+}
+
+/// Format categorical summaries on their own, or say there are none.
+pub fn format_categorical_only(summaries: &[CategoricalSummary], diversity: bool) -> Report {
+    let mut report = Report::new();
+    if summaries.is_empty() {
+        report.push_text("No categorical columns found in the dataset.");
+    } else {
+        report.push_table(format_categorical(summaries, diversity));
+    }
+    report
+}
+
+/// Format every distinct value of a column with its count, in the given order.
+pub fn format_unique(vc: &ValueCounts) -> Table {
+    let mut table = Table::new(&["Value", "Count"]).with_title(&format!(
+        "Unique values: {} ({} distinct, {} missing)",
+        vc.name,
+        vc.counts.len(),
+        vc.missing
+    ));
+    for (value, count) in &vc.counts {
+        table.push_row(vec![value.clone(), count.to_string()]);
+    }
+    table
+}
+
+/// Format value counts as a frequency table with percentages of all rows.
+///
+/// With `top`, only the first `top` values are listed; missing values always get
+/// their own "(missing)" row at the end.
+pub fn format_value_counts(vc: &ValueCounts, top: Option<usize>, opts: &DisplayOptions) -> Report {
+    let mut report = Report::new();
+    let mut table = Table::new(&["Value", "Count", "Percent"])
+        .with_title(&format!("Frequencies: {} (n={})", vc.name, vc.total));
+
+    let pct = |count: usize| {
+        if vc.total == 0 {
+            "NaN".to_string()
+        } else {
+            format!("{:.*}%", opts.decimals(2), count as f64 / vc.total as f64 * 100.0)
+        }
+    };
+
+    let mut rows: Vec<&(String, usize)> = vc.counts.iter().collect();
+    if vc.binned && top.is_some() {
+        // Keep the fullest bins rather than the lowest ones; ties stay in bin order
+        rows.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    }
+    let shown = top.unwrap_or(rows.len()).min(rows.len());
+    for (value, count) in &rows[..shown] {
+        table.push_row(vec![value.clone(), count.to_string(), pct(*count)]);
     }
+    if vc.missing > 0 {
+        table.push_row(vec!["(missing)".to_string(), vc.missing.to_string(), pct(vc.missing)]);
+    }
+    report.push_table(table);
+
+    if shown < rows.len() {
+        let unit = if vc.binned { "bins" } else { "distinct values" };
+        report.push_text(&format!(
+            "\n... {} more {} not shown",
+            rows.len() - shown,
+            unit
+        ));
+    }
+
+    report
+}
+
+/// Format a contingency table with row and column totals.
+///
+/// With `normalize`, cells are shown as proportions of their row, column or the
+/// grand total instead of counts.
+pub fn format_crosstab(
+    ct: &Crosstab,
+    normalize: Option<Normalize>,
+    opts: &DisplayOptions,
+) -> Table {
+    let row_totals = ct.row_totals();
+    let col_totals = ct.col_totals();
+    let total = ct.total();
+
+    let cell = |count: usize, row_total: usize, col_total: usize| -> String {
+        let denom = match normalize {
+            None => return count.to_string(),
+            Some(Normalize::Row) => row_total,
+            Some(Normalize::Col) => col_total,
+            Some(Normalize::All) => total,
+        };
+        if denom == 0 {
+            "NaN".to_string()
+        } else {
+            format!("{:.*}", opts.decimals(3), count as f64 / denom as f64)
+        }
+    };
+
+    let mut header = vec![format!("{} \\ {}", ct.row_var, ct.col_var)];
+    header.extend(ct.col_levels.iter().cloned());
+    header.push("Total".to_string());
+
+    let suffix = match normalize {
+        None => "",
+        Some(Normalize::Row) => " (row proportions)",
+        Some(Normalize::Col) => " (column proportions)",
+        Some(Normalize::All) => " (proportions of total)",
+    };
+    let title = format!("Crosstab: {} by {}{}", ct.row_var, ct.col_var, suffix);
+    let mut table = Table::new(&header).with_title(&title);
+
+    for (i, level) in ct.row_levels.iter().enumerate() {
+        let mut row = vec![level.clone()];
+        for (j, &count) in ct.counts[i].iter().enumerate() {
+            row.push(cell(count, row_totals[i], col_totals[j]));
+        }
+        row.push(cell(row_totals[i], row_totals[i], total));
+        table.push_row(row);
+    }
+
+    let mut totals = vec!["Total".to_string()];
+    for &col_total in &col_totals {
+        totals.push(cell(col_total, total, col_total));
+    }
+    totals.push(cell(total, total, total));
+    table.push_row(totals);
 
-    builder.build().with(Style::rounded()).to_string()
+    table
 }
 
 /// Format missing data report as a table.
-pub fn format_missing(infos: &[MissingInfo]) -> String {
-    let mut builder = Builder::new();
-    builder.push_record(["Variable", "Missing", "% Missing"]);
+pub fn format_missing(infos: &[MissingInfo], opts: &DisplayOptions) -> Table {
+    let mut table =
+        Table::new(&["Variable", "Missing", "% Missing"]).with_title("Missing Data Report");
 
     for info in infos {
-        builder.push_record([
+        table.push_row(vec![
             info.name.clone(),
             info.missing.to_string(),
-            format!("{:.2}%", info.pct),
+            format!("{:.*}%", opts.decimals(2), info.pct),
         ]);
     }
 
-    let mut output = "Missing Data Report:\n".to_string();
-    output.push_str(&builder.build().with(Style::rounded()).to_string());
-    output
+    table
+}
+
+/// Format a one-line-per-column overview of type, cardinality, missingness and an
+/// example value.
+pub fn format_profile(profiles: &[ColumnProfile], nrows: usize, opts: &DisplayOptions) -> Table {
+    let mut table = Table::new(&["Variable", "Type", "Unique", "% Missing", "Example"])
+        .with_title(&format!("Column profile ({} rows)", nrows));
+
+    for profile in profiles {
+        let example = match &profile.example {
+            Some(value) => truncate_cell(value),
+            None => "(all missing)".to_string(),
+        };
+        table.push_row(vec![
+            profile.name.clone(),
+            profile.type_label.clone(),
+            profile.unique.to_string(),
+            format!("{:.*}%", opts.decimals(2), profile.missing_pct),
+            example,
+        ]);
+    }
+
+    table
+}
+
+/// Format the uniqueness of single columns followed by any composite key pairs.
+pub fn format_keys(keys: &[KeyCandidate], nrows: usize, opts: &DisplayOptions) -> Table {
+    let mut table = Table::new(&[
+        "Column(s)",
+        "Unique",
+        "Missing",
+        "Uniqueness",
+        "Key candidate",
+    ])
+    .with_title(&format!("Key candidates ({} rows)", nrows));
+
+    for key in keys {
+        table.push_row(vec![
+            key.columns.join(" + "),
+            key.unique.to_string(),
+            key.missing.to_string(),
+            format!("{:.*}%", opts.decimals(2), key.ratio * 100.0),
+            if key.candidate { "yes" } else { "no" }.to_string(),
+        ]);
+    }
+
+    table
+}
+
+/// Format bin edges and counts, numbering bins from 1.
+pub fn format_bins(binning: &Binning, opts: &DisplayOptions) -> Table {
+    let method = match binning.method {
+        BinMethod::Width => "equal width",
+        BinMethod::Quantile => "equal frequency",
+    };
+    let mut table = Table::new(&["Bin", "Lower", "Upper", "Count"])
+        .with_title(&format!("Bins of {} ({})", binning.column, method));
+    for (i, count) in binning.counts.iter().enumerate() {
+        table.push_row(vec![
+            (i + 1).to_string(),
+            format_f64(binning.edges[i], opts),
+            format_f64(binning.edges[i + 1], opts),
+            count.to_string(),
+        ]);
+    }
+    table
+}
+
+/// Most row numbers listed per unparseable value before summarizing the rest.
+const MAX_LISTED_ROWS: usize = 10;
+
+/// Format the parse check of numeric columns: one row per distinct bad value.
+pub fn format_validation(results: &[ColumnValidation]) -> Report {
+    let mut report = Report::new();
+    let bad: Vec<&ColumnValidation> = results.iter().filter(|r| !r.failures.is_empty()).collect();
+    if bad.is_empty() {
+        report.push_text(&format!(
+            "All non-missing values in {} checked column(s) parse as numbers.",
+            results.len()
+        ));
+        return report;
+    }
+
+    let mut table = Table::new(&["Variable", "Value", "Count", "Rows"])
+        .with_title("Values that do not parse as numbers");
+    for result in &bad {
+        for failure in &result.failures {
+            let mut rows: Vec<String> = failure
+                .rows
+                .iter()
+                .take(MAX_LISTED_ROWS)
+                .map(|r| r.to_string())
+                .collect();
+            if failure.rows.len() > MAX_LISTED_ROWS {
+                rows.push(format!(
+                    "... (+{} more)",
+                    failure.rows.len() - MAX_LISTED_ROWS
+                ));
+            }
+            table.push_row(vec![
+                result.name.clone(),
+                failure.value.clone(),
+                failure.rows.len().to_string(),
+                rows.join(", "),
+            ]);
+        }
+    }
+    report.push_table(table);
+    let cells: usize = bad.iter().map(|r| r.failed_cells()).sum();
+    report.push_text(&format!(
+        "\n{} unparseable cell(s) in {} of {} checked column(s); rows are 1-based data rows",
+        cells,
+        bad.len(),
+        results.len()
+    ));
+    report
 }
 
 /// Format missing data pattern report.
-pub fn format_missing_patterns(report: &MissingPatternReport) -> String {
-    let mut output = String::new();
+pub fn format_missing_patterns(report: &MissingPatternReport, opts: &DisplayOptions) -> Report {
+    let mut output = Report::new();
 
     if report.rows_with_missing == 0 {
-        output.push_str("No missing data found.\n");
+        output.push_text("No missing data found.\n");
         return output;
     }
 
-    output.push_str(&format!(
-        "\n{:.2}% of observations ({}/{}) have at least one missing value\n",
-        report.pct_with_missing, report.rows_with_missing, report.total_rows
+    output.push_text(&format!(
+        "\n{:.*}% of observations ({}/{}) have at least one missing value\n",
+        opts.decimals(2),
+        report.pct_with_missing,
+        report.rows_with_missing,
+        report.total_rows
     ));
 
     if !report.patterns.is_empty() {
-        output.push_str("\nMost common missing patterns:\n");
-        let mut builder = Builder::new();
-        builder.push_record(["Missing Columns", "Count"]);
+        output.push_text("\n");
+        let mut table =
+            Table::new(&["Missing Columns", "Count"]).with_title("Most common missing patterns");
         for (cols, count) in &report.patterns {
-            builder.push_record([cols.join(", "), count.to_string()]);
+            table.push_row(vec![cols.join(", "), count.to_string()]);
         }
-        output.push_str(&builder.build().with(Style::rounded()).to_string());
+        output.push_table(table);
+    }
+
+    if report.hidden_patterns > 0 {
+        output.push_text(&format!(
+            "\n{} more pattern(s) not shown (see --top-patterns, --min-pattern-count)\n",
+            report.hidden_patterns
+        ));
     }
 
     output
 }
 
 /// Format a correlation matrix.
-pub fn format_correlation(cm: &CorrelationMatrix) -> String {
-    let mut output = "Correlation Matrix (Pearson):\n".to_string();
+///
+/// With `min_n`, coefficients resting on fewer pairwise complete observations are
+/// dimmed and marked with `*`.
+pub fn format_correlation(
+    cm: &CorrelationMatrix,
+    min_n: Option<usize>,
+    opts: &DisplayOptions,
+) -> String {
+    let mut output = format!("Correlation Matrix ({}):\n", cm.method.label());
 
-    // Header row
-    let col_width = 8;
-    output.push_str(&format!("{:>width$}", "", width = col_width + 1));
-    for col in &cm.columns {
-        let name = if col.len() > col_width {
-            &col[..col_width]
-        } else {
-            col
-        };
-        output.push_str(&format!("{:>width$}", name, width = col_width));
-    }
-    output.push('\n');
+    // Header row (columns widen to fit a larger precision)
+    let decimals = opts.decimals(2);
+    let col_width = (decimals + 4).max(8);
+    let shown = opts.visible_columns(cm.columns.len(), col_width);
+    output.push_str(&correlation_header(&cm.columns[..shown], col_width));
 
     // Data rows
     for (i, row_name) in cm.columns.iter().enumerate() {
-        let name = if row_name.len() > col_width {
-            &row_name[..col_width]
-        } else {
-            row_name
-        };
+        let name = truncate_label(row_name, col_width);
         output.push_str(&format!("{:>width$} ", name, width = col_width));
-        for j in 0..cm.columns.len() {
+        for j in 0..shown {
             let val = cm.matrix[i][j];
             let formatted = if val.is_nan() {
                 format!("{:>width$}", "NaN", width = col_width)
             } else {
-                format!("{:>width$.2}", val, width = col_width)
+                format!("{:>width$.prec$}", val, width = col_width, prec = decimals)
             };
 
-            // Color high correlations
-            if i != j && !val.is_nan() {
+            // Sparse cells are dimmed instead of highlighted
+            if is_sparse(cm, i, j, min_n) {
+                let marked = format!("{:>width$}*", formatted.trim(), width = col_width - 1);
+                output.push_str(&opts.paint(&marked, |s| s.dimmed()));
+            } else if i != j && !val.is_nan() {
                 if val.abs() >= 0.7 {
-                    output.push_str(&formatted.red().bold().to_string());
+                    output.push_str(&opts.paint(&formatted, |s| s.red().bold()));
                 } else if val.abs() >= 0.5 {
-                    output.push_str(&formatted.yellow().to_string());
+                    output.push_str(&opts.paint(&formatted, |s| s.yellow()));
                 } else {
                     output.push_str(&formatted);
                 }
@@ -149,59 +751,402 @@ pub fn format_correlation(cm: &CorrelationMatrix) -> String {
         }
         output.push('\n');
     }
+    output.push_str(&hidden_columns_note(shown, cm.columns.len(), opts));
+    output.push_str(&sparse_note(cm, min_n));
+
+    output
+}
+
+/// True if the off-diagonal cell (i, j) rests on fewer than `min_n` pairs.
+fn is_sparse(cm: &CorrelationMatrix, i: usize, j: usize, min_n: Option<usize>) -> bool {
+    i != j && min_n.is_some_and(|min| cm.pairs[i][j] < min)
+}
+
+/// Footnote explaining the `*` marker, if any cell carries it.
+fn sparse_note(cm: &CorrelationMatrix, min_n: Option<usize>) -> String {
+    let n = cm.columns.len();
+    let any = (0..n).any(|i| (0..n).any(|j| is_sparse(cm, i, j, min_n)));
+    match min_n {
+        Some(min) if any => format!("* based on fewer than {} complete pairs\n", min),
+        _ => String::new(),
+    }
+}
+
+/// Correlation matrix as a table with one row per variable, for export.
+///
+/// Cells below `min_n` pairs carry the same `*` marker as the terminal view.
+pub fn correlation_table(
+    cm: &CorrelationMatrix,
+    min_n: Option<usize>,
+    opts: &DisplayOptions,
+) -> Table {
+    let mut header = vec!["Variable".to_string()];
+    header.extend(cm.columns.iter().cloned());
+    let title = format!("Correlation Matrix ({})", cm.method.label());
+    let mut table = Table::new(&header).with_title(&title);
+
+    for (i, (name, row)) in cm.columns.iter().zip(&cm.matrix).enumerate() {
+        let mut cells = vec![name.clone()];
+        cells.extend(row.iter().enumerate().map(|(j, v)| {
+            let cell = if v.is_nan() {
+                "NaN".to_string()
+            } else {
+                format!("{:.*}", opts.decimals(2), v)
+            };
+            if is_sparse(cm, i, j, min_n) {
+                cell + "*"
+            } else {
+                cell
+            }
+        }));
+        table.push_row(cells);
+
+        // Mirror the terminal highlighting for HTML export
+        for (j, val) in row.iter().enumerate() {
+            if i == j || val.is_nan() || is_sparse(cm, i, j, min_n) {
+                continue;
+            }
+            if val.abs() >= 0.7 {
+                table.set_class(i, j + 1, "strong");
+            } else if val.abs() >= 0.5 {
+                table.set_class(i, j + 1, "moderate");
+            }
+        }
+    }
+
+    table
+}
+
+/// Format the pairwise complete counts as a matrix aligned like the correlation matrix.
+pub fn format_overlap(om: &OverlapMatrix, opts: &DisplayOptions) -> String {
+    let mut output = String::from("Pairwise Complete Observations:\n");
+
+    // Columns widen to fit the largest possible count
+    let col_width = (om.nrows.to_string().len() + 2).max(8);
+    let shown = opts.visible_columns(om.columns.len(), col_width);
+    output.push_str(&correlation_header(&om.columns[..shown], col_width));
+
+    for (row_name, row) in om.columns.iter().zip(&om.counts) {
+        let name = truncate_label(row_name, col_width);
+        output.push_str(&format!("{:>width$} ", name, width = col_width));
+        for count in &row[..shown] {
+            output.push_str(&format!("{:>width$}", count, width = col_width));
+        }
+        output.push('\n');
+    }
+    output.push_str(&hidden_columns_note(shown, om.columns.len(), opts));
+    output.push_str(&format!(
+        "Out of {} rows; the diagonal counts each column's non-missing values.\n",
+        om.nrows
+    ));
 
     output
 }
 
+/// Pairwise complete counts as a table with one row per variable, for export.
+pub fn overlap_table(om: &OverlapMatrix) -> Table {
+    let mut header = vec!["Variable".to_string()];
+    header.extend(om.columns.iter().cloned());
+    let mut table = Table::new(&header).with_title("Pairwise Complete Observations");
+
+    for (name, row) in om.columns.iter().zip(&om.counts) {
+        let mut cells = vec![name.clone()];
+        cells.extend(row.iter().map(|count| count.to_string()));
+        table.push_row(cells);
+    }
+
+    table
+}
+
+/// Shades used by the correlation heatmap, from r = -1 (light) to r = +1 (dark).
+const HEATMAP_SHADES: [char; 4] = ['░', '▒', '▓', '█'];
+
+/// Format a correlation matrix as a heatmap of shaded blocks.
+///
+/// Each cell is shaded by its coefficient from light (-1) to dark (+1) and colored
+/// like the numeric matrix; `NaN` cells show `?`.
+pub fn format_correlation_heatmap(cm: &CorrelationMatrix, opts: &DisplayOptions) -> String {
+    let mut output = format!("Correlation Heatmap ({}):\n", cm.method.label());
+
+    let col_width = 8;
+    let shown = opts.visible_columns(cm.columns.len(), col_width);
+    output.push_str(&correlation_header(&cm.columns[..shown], col_width));
+
+    for (i, row_name) in cm.columns.iter().enumerate() {
+        let name = truncate_label(row_name, col_width);
+        output.push_str(&format!("{:>width$} ", name, width = col_width));
+        for j in 0..shown {
+            let val = cm.matrix[i][j];
+            let glyph = if val.is_nan() {
+                '?'
+            } else {
+                let level = ((val.clamp(-1.0, 1.0) + 1.0) / 2.0 * HEATMAP_SHADES.len() as f64)
+                    .floor() as usize;
+                HEATMAP_SHADES[level.min(HEATMAP_SHADES.len() - 1)]
+            };
+            let block = glyph.to_string().repeat(col_width - 2);
+            let cell = format!("{:>width$}", block, width = col_width);
+
+            if i != j && !val.is_nan() && val.abs() >= 0.7 {
+                output.push_str(&opts.paint(&cell, |s| s.red().bold()));
+            } else if i != j && !val.is_nan() && val.abs() >= 0.5 {
+                output.push_str(&opts.paint(&cell, |s| s.yellow()));
+            } else {
+                output.push_str(&cell);
+            }
+        }
+        output.push('\n');
+    }
+    output.push_str(&hidden_columns_note(shown, cm.columns.len(), opts));
+
+    output.push_str(&format!(
+        "\nLegend: {} [-1, -0.5)  {} [-0.5, 0)  {} [0, 0.5)  {} [0.5, 1]  ? NaN\n",
+        HEATMAP_SHADES[0], HEATMAP_SHADES[1], HEATMAP_SHADES[2], HEATMAP_SHADES[3]
+    ));
+
+    output
+}
+
+/// Note shown below a matrix whose columns were cut by `--max-columns` or to fit the terminal.
+fn hidden_columns_note(shown: usize, total: usize, opts: &DisplayOptions) -> String {
+    match opts.truncation_note(total) {
+        Some(note) if opts.max_columns == Some(shown) => format!("{}\n", note),
+        _ if shown < total => format!(
+            "({} of {} columns shown; widen the terminal or use --vars to select columns)\n",
+            shown, total
+        ),
+        _ => String::new(),
+    }
+}
+
+/// Header line of right-aligned, truncated column names for matrix output.
+fn correlation_header(columns: &[String], col_width: usize) -> String {
+    let mut header = format!("{:>width$}", "", width = col_width + 1);
+    for col in columns {
+        let name = truncate_label(col, col_width);
+        header.push_str(&format!("{:>width$}", name, width = col_width));
+    }
+    header.push('\n');
+    header
+}
+
+/// Truncate a label to at most `width` characters.
+fn truncate_label(label: &str, width: usize) -> String {
+    label.chars().take(width).collect()
+}
+
 /// Format high correlation warnings.
-pub fn format_high_correlations(pairs: &[(String, String, f64)], threshold: f64) -> String {
+///
+/// Positive pairs are marked `↗` (green) and negative ones `↘` (red).
+pub fn format_high_correlations(
+    pairs: &[HighCorrelation],
+    threshold: f64,
+    method: CorrelationMethod,
+    opts: &DisplayOptions,
+) -> String {
     if pairs.is_empty() {
         return String::new();
     }
 
     let mut output = format!(
-        "\nHigh correlations (|r| > {:.1}):\n",
+        "\nHigh correlations (|{}| > {:.1}):\n",
+        method.symbol(),
         threshold
     );
 
-    for (a, b, r) in pairs {
-        let arrow = if *r > 0.0 { "↔" } else { "↔" };
-        output.push_str(&format!("  - {} {} {}: {:.2}\n", a, arrow, b, r));
+    for pair in pairs {
+        // Cramér's V has no sign, so only signed coefficients get a direction
+        let arrow = match method {
+            CorrelationMethod::CramersV => "↔".to_string(),
+            _ if pair.r < 0.0 => opts.paint("↘", |s| s.red()),
+            _ => opts.paint("↗", |s| s.green()),
+        };
+        let detail = match pair.p {
+            Some(p) => format!("n={}, {}", pair.n, format_p_value(p, opts)),
+            None => format!("n={}", pair.n),
+        };
+        output.push_str(&format!(
+            "  - {} {} {}: {:.*} ({})\n",
+            pair.a,
+            arrow,
+            pair.b,
+            opts.decimals(2),
+            pair.r,
+            detail
+        ));
     }
 
     output
 }
 
+/// Format correlations against a target column as a table of r, R² and n.
+pub fn format_target_correlations(
+    target: &str,
+    results: &[TargetCorrelation],
+    opts: &DisplayOptions,
+) -> Table {
+    let decimals = opts.decimals(3);
+    let mut table = Table::new(&["Variable", "r", "R²", "n"])
+        .with_title(&format!("Linear fit against {}", target));
+
+    for t in results {
+        let fmt = |v: f64| {
+            if v.is_nan() {
+                "NaN".to_string()
+            } else {
+                format!("{:.*}", decimals, v)
+            }
+        };
+        table.push_row(vec![t.column.clone(), fmt(t.r), fmt(t.r2), t.n.to_string()]);
+    }
+
+    table
+}
+
+/// Format a partial correlation next to the zero-order coefficient.
+pub fn format_partial_correlation(pc: &PartialCorrelation, opts: &DisplayOptions) -> String {
+    let decimals = opts.decimals(2);
+    let plain = format!("r({}, {})", pc.x, pc.y);
+    let partial = format!("r({}, {} | {})", pc.x, pc.y, pc.control);
+    let width = partial.chars().count();
+    format!(
+        "Partial correlation controlling for {}:\n  {:<width$} = {:.*}\n  {:<width$} = {:.*}",
+        pc.control,
+        plain,
+        decimals,
+        pc.r,
+        partial,
+        decimals,
+        pc.partial_r,
+        width = width
+    )
+}
+
+/// Format a one-way ANOVA: group means, the F test table and eta-squared.
+pub fn format_anova(anova: &Anova, opts: &DisplayOptions) -> Report {
+    let mut report = Report::new();
+    let mut means = Table::new(&["Group", "n", "Mean"])
+        .with_title(&format!("Group means: {} by {}", anova.var, anova.by));
+    for group in &anova.groups {
+        means.push_row(vec![
+            group.group.clone(),
+            group.n.to_string(),
+            format_f64(group.mean, opts),
+        ]);
+    }
+    report.push_table(means);
+    report.push_text("\n\n");
+
+    let p = format_p_value(anova.p, opts);
+    let mut table = Table::new(&["Source", "SS", "df", "F", "p"])
+        .with_title(&format!("One-way ANOVA: {} by {}", anova.var, anova.by));
+    table.push_row(vec![
+        "Between groups".to_string(),
+        format_f64(anova.ss_between, opts),
+        anova.df_between.to_string(),
+        format_f64(anova.f, opts),
+        p.trim_start_matches("p=").to_string(),
+    ]);
+    table.push_row(vec![
+        "Within groups".to_string(),
+        format_f64(anova.ss_within, opts),
+        anova.df_within.to_string(),
+        String::new(),
+        String::new(),
+    ]);
+    table.push_row(vec![
+        "Total".to_string(),
+        format_f64(anova.ss_between + anova.ss_within, opts),
+        (anova.df_between + anova.df_within).to_string(),
+        String::new(),
+        String::new(),
+    ]);
+    report.push_table(table);
+
+    report.push_text(&format!(
+        "\nη² = {:.*} ({} explains this share of the variance in {})",
+        opts.decimals(3),
+        anova.eta_squared,
+        anova.by,
+        anova.var
+    ));
+    if anova.f.is_nan() && anova.groups.len() > 1 {
+        report.push_text("\nF is undefined: every group has a single observation");
+    }
+    report
+}
+
+/// Format a p-value for a table cell as `0.042`, or `<0.001` when it rounds to zero.
+fn format_p_cell(p: f64, opts: &DisplayOptions) -> String {
+    let decimals = opts.decimals(3);
+    if p.is_nan() {
+        "NaN".to_string()
+    } else if p < 10f64.powi(-(decimals as i32)) {
+        format!("<{:.*}", decimals, 10f64.powi(-(decimals as i32)))
+    } else {
+        format!("{:.*}", decimals, p)
+    }
+}
+
+/// Format a p-value as `p=0.042`, or `p<0.001` when it rounds to zero.
+fn format_p_value(p: f64, opts: &DisplayOptions) -> String {
+    let decimals = opts.decimals(3);
+    if p.is_nan() {
+        "p=NaN".to_string()
+    } else if p < 10f64.powi(-(decimals as i32)) {
+        format!("p<{:.*}", decimals, 10f64.powi(-(decimals as i32)))
+    } else {
+        format!("p={:.*}", decimals, p)
+    }
+}
+
 /// Format column type information as a table.
-pub fn format_types(infos: &[ColumnTypeInfo], show_levels: bool) -> String {
+///
+/// A "Note" column flags constant columns, and near-constant ones when a
+/// `near_constant` threshold is given; it is only shown if some column is flagged.
+/// Columns past `opts.max_columns` are left out and counted in a closing note.
+pub fn format_types(
+    infos: &[ColumnTypeInfo],
+    show_levels: bool,
+    near_constant: Option<f64>,
+    opts: &DisplayOptions,
+) -> String {
+    let total = infos.len();
+    let infos = opts.limit(infos);
+    let notes: Vec<Option<String>> = infos.iter().map(|i| i.quality_note(near_constant)).collect();
+    let show_notes = notes.iter().any(|n| n.is_some());
+
     let mut builder = Builder::new();
 
+    let mut header = vec!["Variable", "Type", "Unique"];
     if show_levels {
-        builder.push_record(["Variable", "Type", "Unique", "Levels"]);
-    } else {
-        builder.push_record(["Variable", "Type", "Unique"]);
+        header.push("Levels");
     }
+    if show_notes {
+        header.push("Note");
+    }
+    builder.push_record(header);
 
-    for info in infos {
-        let levels_str = info.levels.join(", ");
+    for (info, note) in infos.iter().zip(notes) {
+        let mut row = vec![
+            info.name.clone(),
+            info.type_label(),
+            info.unique_count.to_string(),
+        ];
         if show_levels {
-            builder.push_record([
-                info.name.clone(),
-                info.col_type.to_string(),
-                info.unique_count.to_string(),
-                levels_str,
-            ]);
-        } else {
-            builder.push_record([
-                info.name.clone(),
-                info.col_type.to_string(),
-                info.unique_count.to_string(),
-            ]);
+            row.push(info.levels.join(", "));
+        }
+        if show_notes {
+            row.push(note.unwrap_or_default());
         }
+        builder.push_record(row);
     }
 
     let mut output = "Data Types:\n".to_string();
-    output.push_str(&builder.build().with(Style::rounded()).to_string());
+    output.push_str(&opts.style.draw(builder.build()));
+    if let Some(note) = opts.truncation_note(total) {
+        output.push_str(&format!("\n{}", note));
+    }
     output
 }
 
@@ -211,6 +1156,7 @@ pub fn format_comparison(
     stats2: &[DescriptiveStats],
     label1: &str,
     label2: &str,
+    opts: &DisplayOptions,
 ) -> String {
     let mut builder = Builder::new();
     builder.push_record([
@@ -229,29 +1175,152 @@ pub fn format_comparison(
             let diff = if s1.mean.is_nan() || s2.mean.is_nan() {
                 "NaN".to_string()
             } else {
-                format_f64(s2.mean - s1.mean)
+                format_f64(s2.mean - s1.mean, opts)
             };
 
             builder.push_record([
                 s1.name.clone(),
                 s1.count.to_string(),
                 s2.count.to_string(),
-                format_f64(s1.mean),
-                format_f64(s2.mean),
+                format_f64(s1.mean, opts),
+                format_f64(s2.mean, opts),
                 diff,
-                format_f64(s1.std_dev),
-                format_f64(s2.std_dev),
+                format_f64(s1.std_dev, opts),
+                format_f64(s2.std_dev, opts),
             ]);
         }
     }
 
     let mut output = format!("Comparison: {} vs {}\n", label1, label2);
-    output.push_str(&builder.build().with(Style::rounded()).to_string());
+    output.push_str(&opts.style.draw(builder.build()));
     output
 }
 
-/// Format a float for display (reasonable precision).
-fn format_f64(val: f64) -> String {
+/// Reads one summary statistic from a row.
+type Stat = fn(&DescriptiveStats) -> f64;
+
+/// Format a comparison as one block per variable, statistics as rows.
+///
+/// Covers count, mean, std and the five-number summary, with the second file's
+/// value minus the first's in a Diff column. Variables found in only one file are
+/// listed with a "missing in" note instead of a block.
+pub fn format_comparison_detailed(
+    stats1: &[DescriptiveStats],
+    stats2: &[DescriptiveStats],
+    label1: &str,
+    label2: &str,
+    opts: &DisplayOptions,
+) -> String {
+    let mut names: Vec<&str> = stats1.iter().map(|s| s.name.as_str()).collect();
+    for s in stats2 {
+        if !names.contains(&s.name.as_str()) {
+            names.push(&s.name);
+        }
+    }
+
+    let mut blocks = vec![format!("Comparison: {} vs {}", label1, label2)];
+    for name in names {
+        let (s1, s2) = match (
+            stats1.iter().find(|s| s.name == name),
+            stats2.iter().find(|s| s.name == name),
+        ) {
+            (Some(s1), Some(s2)) => (s1, s2),
+            (Some(_), None) => {
+                blocks.push(format!("{}: missing in {}", name, label2));
+                continue;
+            }
+            _ => {
+                blocks.push(format!("{}: missing in {}", name, label1));
+                continue;
+            }
+        };
+
+        let mut builder = Builder::new();
+        builder.push_record(["Statistic", label1, label2, "Diff"]);
+        let count_diff = s2.count as i64 - s1.count as i64;
+        builder.push_record([
+            "Count".to_string(),
+            s1.count.to_string(),
+            s2.count.to_string(),
+            if count_diff > 0 {
+                format!("+{}", count_diff)
+            } else {
+                count_diff.to_string()
+            },
+        ]);
+        let rows: [(&str, Stat); 7] = [
+            ("Mean", |s| s.mean),
+            ("Std", |s| s.std_dev),
+            ("Min", |s| s.min),
+            ("Q1", |s| s.q1),
+            ("Median", |s| s.median),
+            ("Q3", |s| s.q3),
+            ("Max", |s| s.max),
+        ];
+        for (label, get) in rows {
+            builder.push_record([
+                label.to_string(),
+                format_f64(get(s1), opts),
+                format_f64(get(s2), opts),
+                format_f64(get(s2) - get(s1), opts),
+            ]);
+        }
+        blocks.push(format!("{}:\n{}", name, opts.style.draw(builder.build())));
+    }
+    blocks.join("\n\n")
+}
+
+/// Format two-sample Kolmogorov–Smirnov tests, one row per shared variable.
+pub fn format_distribution_shift(tests: &[KsTest], opts: &DisplayOptions) -> String {
+    let mut builder = Builder::new();
+    builder.push_record(["Variable", "n1", "n2", "D", "p"]);
+    for ks in tests {
+        builder.push_record([
+            ks.var.clone(),
+            ks.n1.to_string(),
+            ks.n2.to_string(),
+            format_f64(ks.d, opts),
+            format_p_value(ks.p, opts),
+        ]);
+    }
+    opts.style.draw(builder.build())
+}
+
+/// Widest cell shown by `format_rows` before the value is cut off.
+const MAX_CELL_WIDTH: usize = 30;
+
+/// Format the rows at `indices` as a table, prefixed by their 1-based row numbers.
+///
+/// Values wider than `MAX_CELL_WIDTH` characters are truncated with an ellipsis
+/// so a single long text column doesn't blow up the layout.
+pub fn format_rows(df: &DataFrame, indices: &[usize], opts: &DisplayOptions) -> String {
+    let mut builder = Builder::new();
+
+    let mut header = vec!["#".to_string()];
+    header.extend(df.headers.iter().map(|h| truncate_cell(h)));
+    builder.push_record(header);
+
+    for &i in indices {
+        let mut row = vec![(i + 1).to_string()];
+        row.extend(df.rows[i].iter().map(|v| truncate_cell(v)));
+        builder.push_record(row);
+    }
+
+    opts.style.draw(builder.build())
+}
+
+fn truncate_cell(value: &str) -> String {
+    if value.chars().count() > MAX_CELL_WIDTH {
+        let mut cut: String = value.chars().take(MAX_CELL_WIDTH - 1).collect();
+        cut.push('…');
+        cut
+    } else {
+        value.to_string()
+    }
+}
+
+/// Format a float for display: a fixed precision if set, otherwise by magnitude.
+pub fn format_f64(val: f64, opts: &DisplayOptions) -> String {
     if val.is_nan() {
         "NaN".to_string()
     } else if val.is_infinite() {
@@ -260,10 +1329,8 @@ fn format_f64(val: f64) -> String {
         } else {
             "-Inf".to_string()
         }
-    } else if val.abs() >= 1_000_000.0 {
-        format!("{:.2}", val)
-    } else if val.abs() >= 100.0 {
-        format!("{:.2}", val)
+    } else if let Some(precision) = opts.precision {
+        format!("{:.*}", precision, val)
     } else if val.abs() >= 1.0 {
         format!("{:.2}", val)
     } else if val == 0.0 {
@@ -273,20 +1340,494 @@ fn format_f64(val: f64) -> String {
     }
 }
 
-/// Convert output to a specific format for export.
-pub fn export_output(content: &str, format: &str) -> String {
-    match format {
-        "json" => {
-            // Wrap as a simple JSON object
-            serde_json::json!({ "output": content }).to_string()
+/// Format a value that is whole whenever the data is, such as a quartile.
+///
+/// With `opts.round`, whole numbers are printed without decimals; anything with a
+/// fractional part falls back to [`format_f64`].
+fn format_order_stat(val: f64, opts: &DisplayOptions) -> String {
+    if opts.round && val.is_finite() && val.fract() == 0.0 {
+        format!("{:.0}", val)
+    } else {
+        format_f64(val, opts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::BIMODALITY_THRESHOLD;
+
+    fn sample_matrix() -> CorrelationMatrix {
+        CorrelationMatrix {
+            method: CorrelationMethod::Pearson,
+            columns: vec!["a_very_long_name".to_string(), "b".to_string(), "c".to_string()],
+            matrix: vec![
+                vec![1.0, -0.9, f64::NAN],
+                vec![-0.9, 1.0, 0.2],
+                vec![f64::NAN, 0.2, 1.0],
+            ],
+            pairs: vec![vec![10, 10, 0], vec![10, 12, 12], vec![0, 12, 12]],
         }
-        "csv" => {
-            // For CSV export, keep as-is (tables are already structured)
-            content.to_string()
+    }
+
+    #[test]
+    fn test_summary_markdown_pipe_table() {
+        let df = crate::reader::read_file("tests/data/sample.csv").unwrap();
+        let stats = crate::stats::describe_selected(&df, &["age", "income"]);
+        let md = format_summary(
+            &stats,
+            BIMODALITY_THRESHOLD,
+            false,
+            false,
+            &DisplayOptions::default(),
+        )
+        .to_markdown();
+        let lines: Vec<&str> = md.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("| Variable | Count | Mean |"));
+        assert_eq!(lines[1].matches("---").count(), 10);
+        assert!(lines[2].starts_with("| age | 30 |"));
+        assert!(lines[2].ends_with("| no |"));
+        assert!(lines.iter().all(|l| l.starts_with('|') && l.ends_with('|')));
+    }
+
+    #[test]
+    fn test_correlation_without_color_has_no_escapes() {
+        let matrix = format_correlation(&sample_matrix(), None, &DisplayOptions::default());
+        let heatmap = format_correlation_heatmap(&sample_matrix(), &DisplayOptions::default());
+        assert!(matrix.contains("-0.90"));
+        assert!(!matrix.contains('\x1b'));
+        assert!(!heatmap.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_precision_overrides_magnitude_defaults() {
+        let default = DisplayOptions::default();
+        assert_eq!(format_f64(12.3456, &default), "12.35");
+        assert_eq!(format_f64(0.012345, &default), "0.0123");
+
+        let six = DisplayOptions {
+            precision: Some(6),
+            ..Default::default()
+        };
+        assert_eq!(format_f64(0.012345, &six), "0.012345");
+        let zero = DisplayOptions {
+            precision: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(format_f64(12.6, &zero), "13");
+
+        for opts in [default, six, zero] {
+            assert_eq!(format_f64(f64::NAN, &opts), "NaN");
+            assert_eq!(format_f64(f64::NEG_INFINITY, &opts), "-Inf");
         }
-        _ => {
-            // Markdown / plain text
-            content.to_string()
+    }
+
+    #[test]
+    fn test_correlation_marks_sparse_cells() {
+        let out = format_correlation(&sample_matrix(), Some(11), &DisplayOptions::default());
+        let lines: Vec<&str> = out.lines().collect();
+        // a-b rests on 10 pairs, b-c on 12; the diagonal is never marked
+        assert!(lines[2].contains("-0.90*"));
+        assert!(lines[3].contains(" 0.20") && !lines[3].contains("0.20*"));
+        assert!(!lines[2].contains("1.00*"));
+        assert!(out.ends_with("* based on fewer than 11 complete pairs\n"));
+
+        let table = correlation_table(&sample_matrix(), Some(11), &DisplayOptions::default());
+        assert_eq!(table.rows[0][2], "-0.90*");
+        assert!(!format_correlation(&sample_matrix(), None, &DisplayOptions::default())
+            .contains('*'));
+    }
+
+    #[test]
+    fn test_correlation_precision_widens_columns() {
+        let out = format_correlation(&sample_matrix(), None, &DisplayOptions {
+            precision: Some(6),
+            ..Default::default()
+        });
+        assert!(out.contains(" -0.900000"));
+        assert!(out.contains(" 0.200000"));
+    }
+
+    #[test]
+    fn test_summary_precision_per_column() {
+        let df = crate::reader::read_file("tests/data/sample.csv").unwrap();
+        let stats = crate::stats::describe_selected(&df, &["age", "income", "score"]);
+        let opts = DisplayOptions {
+            precision: Some(1),
+            precisions: HashMap::from([("age".to_string(), 4), ("income".to_string(), 0)]),
+            ..Default::default()
+        };
+
+        let table = format_summary(&stats, BIMODALITY_THRESHOLD, false, false, &opts);
+        assert_eq!(table.header[2], "Mean");
+        assert_eq!(table.rows[0][2], "35.7333");
+        assert_eq!(table.rows[1][2], "50222");
+        // Columns outside the map keep the global precision
+        assert_eq!(table.rows[2][2], "74.3");
+
+        let transposed =
+            format_summary_transposed(&stats, BIMODALITY_THRESHOLD, false, false, &opts);
+        assert_eq!(transposed.rows[1], ["Mean", "35.7333", "50222", "74.3"]);
+    }
+
+    #[test]
+    fn test_summary_optional_trimmed_mean_column() {
+        let df = crate::reader::read_file("tests/data/sample.csv").unwrap();
+        let mut stats = crate::stats::describe_selected(&df, &["age", "income"]);
+        let opts = DisplayOptions::default();
+        let plain = format_summary(&stats, BIMODALITY_THRESHOLD, false, false, &opts);
+        assert!(!plain.header.contains(&"Trimmed Mean".to_string()));
+
+        stats[0].trimmed_mean = Some(35.25);
+        let table = format_summary(&stats, BIMODALITY_THRESHOLD, false, false, &opts);
+        assert_eq!(table.header[3], "Trimmed Mean");
+        assert_eq!(table.rows[0][3], "35.25");
+        assert_eq!(table.rows[1][3], "NaN");
+    }
+
+    #[test]
+    fn test_summary_median_ci_column() {
+        let df = crate::reader::read_file("tests/data/sample.csv").unwrap();
+        let mut stats = crate::stats::describe_selected(&df, &["age", "income"]);
+        stats[0].median_ci = Some((31.0, 38.5));
+        stats[1].median_ci = Some((f64::NAN, f64::NAN));
+        let opts = DisplayOptions::default();
+
+        let table = format_summary(&stats, BIMODALITY_THRESHOLD, false, false, &opts);
+        assert_eq!(table.header[6..8], ["Median", "Median 95% CI"]);
+        assert_eq!(table.rows[0][7], "[31.00, 38.50]");
+        assert_eq!(table.rows[1][7], "NaN");
+
+        let transposed =
+            format_summary_transposed(&stats, BIMODALITY_THRESHOLD, false, false, &opts);
+        assert_eq!(
+            transposed.rows[6],
+            ["Median 95% CI", "[31.00, 38.50]", "NaN"]
+        );
+    }
+
+    #[test]
+    fn test_summary_spread_columns() {
+        let rows: Vec<[String; 2]> = (1..=5).map(|i| [i.to_string(), "NA".to_string()]).collect();
+        let df = crate::reader::DataFrame::from_rows(&["x", "empty"], &rows);
+        let stats = crate::stats::describe_selected(&df, &["x", "empty"]);
+        let opts = DisplayOptions::default();
+        let table = format_summary(&stats, BIMODALITY_THRESHOLD, true, false, &opts);
+        assert_eq!(table.header[9..], ["IQR", "Range", "Multimodal?"]);
+        assert_eq!(table.rows[0][9..11], ["2.00", "4.00"]);
+        assert_eq!(table.rows[1][9..11], ["NaN", "NaN"]);
+
+        let transposed =
+            format_summary_transposed(&stats, BIMODALITY_THRESHOLD, true, false, &opts);
+        assert_eq!(transposed.rows[8], ["IQR", "2.00", "NaN"]);
+        assert_eq!(transposed.rows[9], ["Range", "4.00", "NaN"]);
+    }
+
+    #[test]
+    fn test_summary_round_drops_decimals_of_whole_order_stats() {
+        let rows: Vec<[String; 2]> = (1..=5)
+            .map(|i| [i.to_string(), (i as f64 * 1.5).to_string()])
+            .collect();
+        let df = crate::reader::DataFrame::from_rows(&["x", "y"], &rows);
+        let stats = crate::stats::describe_selected(&df, &["x", "y"]);
+        let opts = DisplayOptions {
+            round: true,
+            ..Default::default()
+        };
+        let table = format_summary(&stats, BIMODALITY_THRESHOLD, true, false, &opts);
+        // Mean and std keep their decimals even when whole
+        assert_eq!(
+            table.rows[0][1..11],
+            ["5", "3.00", "1.58", "1", "2", "3", "4", "5", "2", "4"]
+        );
+        assert_eq!(table.rows[1][4..9], ["1.50", "3", "4.50", "6", "7.50"]);
+
+        let transposed =
+            format_summary_transposed(&stats, BIMODALITY_THRESHOLD, false, false, &opts);
+        assert_eq!(transposed.rows[1], ["Mean", "3.00", "4.50"]);
+        assert_eq!(transposed.rows[3], ["Min", "1", "1.50"]);
+
+        let opts = DisplayOptions::default();
+        let plain = format_summary(&stats, BIMODALITY_THRESHOLD, false, false, &opts);
+        assert_eq!(plain.rows[0][4], "1.00");
+    }
+
+    #[test]
+    fn test_summary_signs_columns() {
+        let df = crate::reader::DataFrame::from_column(
+            "revenue",
+            &["120", "0", "-35.5", "0", "NA", "80", "-0.0"],
+        );
+        let stats = crate::stats::describe_selected(&df, &["revenue"]);
+        assert_eq!((stats[0].zeros, stats[0].negatives), (3, 1));
+        let streamed = crate::stats::describe_with(&df, "revenue", false).unwrap();
+        assert_eq!((streamed.zeros, streamed.negatives), (3, 1));
+
+        let opts = DisplayOptions::default();
+        let table = format_summary(&stats, BIMODALITY_THRESHOLD, false, true, &opts);
+        assert_eq!(table.header[1..5], ["Count", "Zeros", "Negatives", "Mean"]);
+        assert_eq!(table.rows[0][1..4], ["6", "3", "1"]);
+
+        let transposed =
+            format_summary_transposed(&stats, BIMODALITY_THRESHOLD, false, true, &opts);
+        assert_eq!(transposed.rows[1], ["Zeros", "3"]);
+        assert_eq!(transposed.rows[2], ["Negatives", "1"]);
+        assert_eq!(transposed.rows[3][0], "Mean");
+    }
+
+    #[test]
+    fn test_comparison_detailed_blocks() {
+        let df = crate::reader::read_file("tests/data/sample.csv").unwrap();
+        let stats1 = crate::stats::describe_selected(&df, &["age", "income"]);
+        let stats2 = crate::stats::describe_selected(&df, &["age", "score"]);
+        let out = format_comparison_detailed(
+            &stats1,
+            &stats2,
+            "a.csv",
+            "b.csv",
+            &DisplayOptions::default(),
+        );
+        assert!(out.starts_with("Comparison: a.csv vs b.csv"));
+        assert!(out.contains("age:\n"));
+        assert!(out.contains("│ Median    │ 34.50 │ 34.50 │ 0.00 │"));
+        assert!(out.contains("income: missing in b.csv"));
+        assert!(out.contains("score: missing in a.csv"));
+    }
+
+    #[test]
+    fn test_categorical_only_reports_numeric_only_file() {
+        let df = crate::reader::read_file("tests/data/sample.csv").unwrap();
+        let numeric = df.select_columns(&["age", "income"]);
+        let summaries = crate::stats::categorical_summaries(&numeric);
+        assert!(summaries.is_empty());
+        let text = format_categorical_only(&summaries, false).to_terminal(TableStyle::Rounded);
+        assert!(text.contains("No categorical columns"));
+
+        let summaries = crate::stats::categorical_summaries(&df);
+        let names: Vec<&str> = summaries.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["name", "gender", "city", "employed"]);
+        assert!(format_categorical_only(&summaries, false)
+            .to_terminal(TableStyle::Rounded)
+            .contains("Categorical Variables"));
+    }
+
+    #[test]
+    fn test_summary_transposed_layout() {
+        let df = crate::reader::read_file("tests/data/sample.csv").unwrap();
+        let stats = crate::stats::describe_selected(&df, &["age", "income"]);
+        let opts = DisplayOptions::default();
+        let table = format_summary_transposed(&stats, BIMODALITY_THRESHOLD, false, false, &opts);
+        assert_eq!(table.header, ["Statistic", "age", "income"]);
+        assert_eq!(table.rows.len(), 9);
+        assert_eq!(table.rows[0], ["Count", "30", "27"]);
+        assert_eq!(table.rows[2][0], "Std");
+        assert!(table.render(TableStyle::Rounded).contains('╭'));
+    }
+
+    #[test]
+    fn test_correlation_truncates_to_width() {
+        let narrow = DisplayOptions {
+            width: Some(30),
+            ..Default::default()
+        };
+        let out = format_correlation(&sample_matrix(), None, &narrow);
+        let full = format_correlation(&sample_matrix(), None, &DisplayOptions::default());
+
+        let rows: Vec<&str> = out.lines().skip(2).take(3).collect();
+        assert!(rows.iter().all(|r| r.chars().count() <= 30));
+        assert!(out.contains("(2 of 3 columns shown;"));
+        assert!(!full.contains("columns shown"));
+    }
+
+    #[test]
+    fn test_max_columns_keeps_first_columns_with_note() {
+        let headers: Vec<String> = (1..=10).map(|i| format!("c{}", i)).collect();
+        let rows = (0..5)
+            .map(|r| (1..=10).map(|c| (r * c).to_string()).collect())
+            .collect();
+        let df = DataFrame { headers, rows };
+        let opts = DisplayOptions {
+            max_columns: Some(3),
+            ..Default::default()
+        };
+
+        let types = format_types(&crate::types::infer_types(&df), false, None, &opts);
+        assert!(types.contains("c3") && !types.contains("c4"));
+        assert!(types.ends_with("... and 7 more"));
+
+        let stats = crate::stats::describe_all(&df);
+        let vertical = format_summary(&stats, BIMODALITY_THRESHOLD, false, false, &opts);
+        let rows: Vec<&str> = vertical.rows.iter().map(|r| r[0].as_str()).collect();
+        assert_eq!(rows, ["c1", "c2", "c3"]);
+        let wide = format_summary_transposed(&stats, BIMODALITY_THRESHOLD, false, false, &opts);
+        assert_eq!(wide.header, ["Statistic", "c1", "c2", "c3"]);
+        assert_eq!(opts.truncation_note(stats.len()).unwrap(), "... and 7 more");
+    }
+
+    #[test]
+    fn test_value_counts_table_top_and_missing() {
+        let vc = ValueCounts {
+            name: "city".to_string(),
+            total: 10,
+            missing: 1,
+            counts: vec![
+                ("Cali".to_string(), 5),
+                ("Bogota".to_string(), 3),
+                ("Pasto".to_string(), 1),
+            ],
+            binned: false,
+        };
+        let report = format_value_counts(&vc, Some(2), &DisplayOptions::default());
+        let md = report.to_markdown();
+        assert!(md.contains("| Cali | 5 | 50.00% |"));
+        assert!(!md.contains("Pasto"));
+        assert!(md.contains("| (missing) | 1 | 10.00% |"));
+        assert!(md.contains("1 more distinct values not shown"));
+    }
+
+    #[test]
+    fn test_binned_value_counts_top_keeps_fullest_bins() {
+        let vc = ValueCounts {
+            name: "income".to_string(),
+            total: 10,
+            missing: 0,
+            counts: vec![
+                ("[0, 10)".to_string(), 1),
+                ("[10, 20)".to_string(), 6),
+                ("[20, 30)".to_string(), 3),
+            ],
+            binned: true,
+        };
+        let report = format_value_counts(&vc, Some(2), &DisplayOptions::default());
+        let md = report.to_markdown();
+        assert!(md.find("[10, 20)").unwrap() < md.find("[20, 30)").unwrap());
+        assert!(!md.contains("[0, 10)"));
+        assert!(md.contains("1 more bins not shown"));
+
+        // Without --top every bin is listed in bin order
+        let md = format_value_counts(&vc, None, &DisplayOptions::default()).to_markdown();
+        assert!(md.find("[0, 10)").unwrap() < md.find("[10, 20)").unwrap());
+    }
+
+    fn sample_crosstab() -> Crosstab {
+        Crosstab {
+            row_var: "g".to_string(),
+            col_var: "e".to_string(),
+            row_levels: vec!["F".to_string(), "M".to_string()],
+            col_levels: vec!["no".to_string(), "yes".to_string()],
+            counts: vec![vec![1, 3], vec![2, 2]],
+            dropped_rows: 0,
+            dropped_cols: 0,
         }
     }
+
+    #[test]
+    fn test_crosstab_counts_with_totals() {
+        let table = format_crosstab(&sample_crosstab(), None, &DisplayOptions::default());
+        assert_eq!(table.header, ["g \\ e", "no", "yes", "Total"]);
+        assert_eq!(table.rows[0], ["F", "1", "3", "4"]);
+        assert_eq!(table.rows[2], ["Total", "3", "5", "8"]);
+    }
+
+    #[test]
+    fn test_crosstab_normalized() {
+        let opts = DisplayOptions::default();
+        let row = format_crosstab(&sample_crosstab(), Some(Normalize::Row), &opts);
+        assert_eq!(row.rows[0], ["F", "0.250", "0.750", "1.000"]);
+        assert_eq!(row.rows[2], ["Total", "0.375", "0.625", "1.000"]);
+
+        let col = format_crosstab(&sample_crosstab(), Some(Normalize::Col), &opts);
+        assert_eq!(col.rows[0], ["F", "0.333", "0.600", "0.500"]);
+        assert_eq!(col.rows[2], ["Total", "1.000", "1.000", "1.000"]);
+
+        let all = format_crosstab(&sample_crosstab(), Some(Normalize::All), &opts);
+        assert_eq!(all.rows[1], ["M", "0.250", "0.250", "0.500"]);
+        assert_eq!(all.rows[2][3], "1.000");
+    }
+
+    #[test]
+    fn test_format_rows_numbers_and_truncates() {
+        let note = "x".repeat(40);
+        let df = DataFrame::from_rows(&["id", "note"], &[["1", "short"], ["2", &note]]);
+        let out = format_rows(&df, &[1], &DisplayOptions::default());
+        assert!(out.contains(&format!("{}…", "x".repeat(MAX_CELL_WIDTH - 1))));
+        assert!(!out.contains(&"x".repeat(MAX_CELL_WIDTH)));
+        assert!(!out.contains("short"));
+    }
+
+    #[test]
+    fn test_format_rows_file_shorter_than_n() {
+        let df = crate::reader::read_file("tests/data/sample.csv").unwrap();
+        let out = format_rows(&df, &df.tail_indices(1000), &DisplayOptions::default());
+        // Header, every data row and three border lines
+        assert_eq!(out.lines().count(), df.nrows() + 4);
+    }
+
+    #[test]
+    fn test_high_correlations_show_n_and_p() {
+        let pairs = vec![
+            HighCorrelation {
+                a: "x".to_string(),
+                b: "y".to_string(),
+                r: 0.6,
+                n: 12,
+                p: Some(0.0393),
+            },
+            HighCorrelation {
+                a: "x".to_string(),
+                b: "z".to_string(),
+                r: -0.99,
+                n: 40,
+                p: Some(1e-9),
+            },
+        ];
+        let out = format_high_correlations(
+            &pairs,
+            0.5,
+            CorrelationMethod::Pearson,
+            &DisplayOptions::default(),
+        );
+        assert!(out.contains("x ↗ y: 0.60 (n=12, p=0.039)"));
+        assert!(out.contains("(n=40, p<0.001)"));
+    }
+
+    #[test]
+    fn test_high_correlations_mark_direction() {
+        let pair = |b: &str, r: f64| HighCorrelation {
+            a: "x".to_string(),
+            b: b.to_string(),
+            r,
+            n: 20,
+            p: None,
+        };
+        let pairs = vec![pair("up", 0.8), pair("down", -0.8)];
+        let opts = DisplayOptions::default();
+
+        let out = format_high_correlations(&pairs, 0.5, CorrelationMethod::Pearson, &opts);
+        let cramers =
+            format_high_correlations(&pairs[..1], 0.5, CorrelationMethod::CramersV, &opts);
+        assert!(out.contains("x ↗ up: 0.80"));
+        assert!(out.contains("x ↘ down: -0.80"));
+        assert!(!out.contains('\x1b'));
+        assert!(cramers.contains("x ↔ up: 0.80"));
+
+        let colored = DisplayOptions {
+            color: true,
+            ..Default::default()
+        };
+        assert!(colored.with_color(true).color);
+        assert!(!colored.with_color(false).color);
+    }
+
+    #[test]
+    fn test_heatmap_shades_and_nan_placeholder() {
+        let out = format_correlation_heatmap(&sample_matrix(), &DisplayOptions::default());
+        let rows: Vec<&str> = out.lines().skip(2).take(3).collect();
+        assert!(rows[0].contains("a_very_l"));
+        assert!(rows[0].contains("██████"));
+        assert!(rows[0].contains("░░░░░░"));
+        assert!(rows[0].contains("??????"));
+        assert!(rows[1].contains("▓▓▓▓▓▓"));
+    }
 }
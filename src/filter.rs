@@ -0,0 +1,232 @@
+use crate::reader::DataFrame;
+use crate::types;
+use crate::utils::is_missing;
+use anyhow::{bail, Result};
+
+/// Comparison operator of a predicate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
+    Gt,
+    Lt,
+    Ge,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl Op {
+    fn symbol(&self) -> &'static str {
+        match self {
+            Op::Gt => ">",
+            Op::Lt => "<",
+            Op::Ge => ">=",
+            Op::Le => "<=",
+            Op::Eq => "==",
+            Op::Ne => "!=",
+        }
+    }
+}
+
+/// Operators in matching order: two-character forms before their prefixes.
+const OPS: [Op; 6] = [Op::Ge, Op::Le, Op::Eq, Op::Ne, Op::Gt, Op::Lt];
+
+/// A single `column <op> value` condition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Predicate {
+    pub column: String,
+    pub op: Op,
+    pub value: String,
+}
+
+impl Predicate {
+    /// Parse an expression such as `age>30`, `income <= 50000` or `city==Denver`.
+    ///
+    /// The value may be wrapped in single or double quotes to keep surrounding spaces.
+    pub fn parse(expr: &str) -> Result<Predicate> {
+        let found = expr.char_indices().find_map(|(i, _)| {
+            OPS.iter()
+                .find(|op| expr[i..].starts_with(op.symbol()))
+                .map(|op| (i, *op))
+        });
+        let Some((pos, op)) = found else {
+            bail!(
+                "Invalid filter '{}': expected column, operator and value, e.g. age>30 (operators: >, <, >=, <=, ==, !=)",
+                expr
+            );
+        };
+
+        let column = expr[..pos].trim();
+        let value = unquote(expr[pos + op.symbol().len()..].trim());
+        if column.is_empty() {
+            bail!(
+                "Invalid filter '{}': missing column name before '{}'",
+                expr,
+                op.symbol()
+            );
+        }
+        if value.is_empty() {
+            bail!(
+                "Invalid filter '{}': missing value after '{}'",
+                expr,
+                op.symbol()
+            );
+        }
+
+        Ok(Predicate {
+            column: column.to_string(),
+            op,
+            value: value.to_string(),
+        })
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+/// A predicate resolved against a DataFrame's columns.
+enum Condition {
+    Numeric { idx: usize, op: Op, value: f64 },
+    Text { idx: usize, op: Op, value: String },
+}
+
+impl Condition {
+    fn matches(&self, row: &[String]) -> bool {
+        match self {
+            Condition::Numeric { idx, op, value } => {
+                let cell = row[*idx].trim();
+                if is_missing(cell) {
+                    return false;
+                }
+                let Ok(x) = cell.parse::<f64>() else {
+                    return false;
+                };
+                match op {
+                    Op::Gt => x > *value,
+                    Op::Lt => x < *value,
+                    Op::Ge => x >= *value,
+                    Op::Le => x <= *value,
+                    Op::Eq => x == *value,
+                    Op::Ne => x != *value,
+                }
+            }
+            Condition::Text { idx, op, value } => {
+                let cell = row[*idx].trim();
+                match op {
+                    Op::Ne => cell != value,
+                    _ => cell == value,
+                }
+            }
+        }
+    }
+}
+
+/// Indices of the rows matching every predicate.
+///
+/// Numeric columns are compared as numbers (missing values never match); other
+/// columns only support `==` and `!=` on the trimmed text.
+pub fn filter_rows(df: &DataFrame, predicates: &[Predicate]) -> Result<Vec<usize>> {
+    let numeric = types::numeric_columns(df);
+    let mut conditions = Vec::new();
+
+    for pred in predicates {
+        let Some(idx) = df.col_index(&pred.column) else {
+            bail!("Column '{}' not found", pred.column);
+        };
+        if numeric.contains(&pred.column) {
+            let Ok(value) = pred.value.parse::<f64>() else {
+                bail!(
+                    "Column '{}' is numeric but '{}' is not a number",
+                    pred.column,
+                    pred.value
+                );
+            };
+            conditions.push(Condition::Numeric {
+                idx,
+                op: pred.op,
+                value,
+            });
+        } else {
+            if !matches!(pred.op, Op::Eq | Op::Ne) {
+                bail!(
+                    "Column '{}' is not numeric; only == and != are supported",
+                    pred.column
+                );
+            }
+            conditions.push(Condition::Text {
+                idx,
+                op: pred.op,
+                value: pred.value.clone(),
+            });
+        }
+    }
+
+    Ok((0..df.nrows())
+        .filter(|&i| conditions.iter().all(|c| c.matches(&df.rows[i])))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::read_file;
+
+    #[test]
+    fn test_parse_predicates() {
+        let p = Predicate::parse("age>=30").unwrap();
+        assert_eq!(
+            (p.column.as_str(), p.op, p.value.as_str()),
+            ("age", Op::Ge, "30")
+        );
+
+        let p = Predicate::parse(" city == \"New York\" ").unwrap();
+        assert_eq!(
+            (p.column.as_str(), p.op, p.value.as_str()),
+            ("city", Op::Eq, "New York")
+        );
+
+        assert_eq!(Predicate::parse("a!=b").unwrap().op, Op::Ne);
+        assert_eq!(Predicate::parse("a<1").unwrap().op, Op::Lt);
+        assert!(Predicate::parse("age 30").is_err());
+        assert!(Predicate::parse(">30").is_err());
+        assert!(Predicate::parse("age>").is_err());
+    }
+
+    #[test]
+    fn test_filter_numeric_and_text_combined() {
+        let df = read_file("tests/data/sample.csv").unwrap();
+        let preds = [
+            Predicate::parse("age>40").unwrap(),
+            Predicate::parse("gender==M").unwrap(),
+        ];
+        let rows = filter_rows(&df, &preds).unwrap();
+        assert!(!rows.is_empty());
+        for i in rows {
+            assert!(df.rows[i][2].parse::<f64>().unwrap() > 40.0);
+            assert_eq!(df.rows[i][3], "M");
+        }
+    }
+
+    #[test]
+    fn test_filter_missing_never_matches_numeric() {
+        let df = read_file("tests/data/sample.csv").unwrap();
+        let above = filter_rows(&df, &[Predicate::parse("income>0").unwrap()]).unwrap();
+        assert_eq!(above.len(), 27);
+    }
+
+    #[test]
+    fn test_filter_errors() {
+        let df = read_file("tests/data/sample.csv").unwrap();
+        assert!(filter_rows(&df, &[Predicate::parse("nope==1").unwrap()]).is_err());
+        assert!(filter_rows(&df, &[Predicate::parse("age>abc").unwrap()]).is_err());
+        assert!(filter_rows(&df, &[Predicate::parse("city>A").unwrap()]).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use crate::reader::DataFrame;
+use crate::stats::{mean, percentile, value_counts};
+use crate::types::{column_type, infer_types, ColumnType};
+use crate::utils::is_missing;
+use anyhow::{bail, Result};
+
+/// How the fill value for a column is chosen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Method {
+    Mean,
+    Median,
+    Mode,
+}
+
+impl Method {
+    /// Parse "mean", "median" or "mode".
+    pub fn parse(value: &str) -> Option<Method> {
+        match value {
+            "mean" => Some(Method::Mean),
+            "median" => Some(Method::Median),
+            "mode" => Some(Method::Mode),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Method {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Method::Mean => write!(f, "mean"),
+            Method::Median => write!(f, "median"),
+            Method::Mode => write!(f, "mode"),
+        }
+    }
+}
+
+/// Result of filling one column.
+#[derive(Debug, Clone)]
+pub struct Imputation {
+    pub column: String,
+    pub method: Method,
+    /// Value written into the missing cells; `None` if the column had no values to use.
+    pub value: Option<String>,
+    pub filled: usize,
+}
+
+/// Fill value for a numeric column: the mean or median of its finite values.
+fn numeric_fill(df: &DataFrame, col: &str, method: Method) -> Option<String> {
+    let mut values = df.valid_numeric_column(col)?;
+    // A NaN or inf cell would otherwise become the fill value
+    values.retain(|v| v.is_finite());
+    if values.is_empty() {
+        return None;
+    }
+    let value = match method {
+        Method::Mean => mean(&values),
+        _ => {
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            percentile(&values, 50.0)
+        }
+    };
+    Some(value.to_string())
+}
+
+/// Fill value for any column: its most frequent value (ties go to the smallest).
+fn mode_fill(df: &DataFrame, col: &str) -> Option<String> {
+    value_counts(df, col)?
+        .counts
+        .into_iter()
+        .next()
+        .map(|(v, _)| v)
+}
+
+/// Replace the missing cells of `col` in place.
+///
+/// Mean and median require a numeric column; mode works for any column.
+pub fn impute_column(df: &mut DataFrame, col: &str, method: Method) -> Result<Imputation> {
+    let Some(col_type) = column_type(df, col) else {
+        bail!("Column '{}' not found", col);
+    };
+    fill_column(df, col, method, &col_type)
+}
+
+/// Fill `col`, whose type the caller has already inferred as `col_type`.
+fn fill_column(
+    df: &mut DataFrame,
+    col: &str,
+    method: Method,
+    col_type: &ColumnType,
+) -> Result<Imputation> {
+    let Some(idx) = df.col_index(col) else {
+        bail!("Column '{}' not found", col);
+    };
+    let value = match method {
+        Method::Mode => mode_fill(df, col),
+        _ => {
+            if *col_type != ColumnType::Numeric {
+                bail!(
+                    "Column '{}' is not numeric; use --method mode to fill it",
+                    col
+                );
+            }
+            numeric_fill(df, col, method)
+        }
+    };
+
+    let mut filled = 0;
+    if let Some(value) = &value {
+        for row in &mut df.rows {
+            if is_missing(&row[idx]) {
+                row[idx] = value.clone();
+                filled += 1;
+            }
+        }
+    }
+
+    Ok(Imputation {
+        column: col.to_string(),
+        method,
+        value,
+        filled,
+    })
+}
+
+/// Fill every column by type: numeric columns with `method`, the rest with the mode.
+pub fn impute_all(df: &mut DataFrame, method: Method) -> Result<Vec<Imputation>> {
+    let types = infer_types(df);
+    types
+        .iter()
+        .map(|info| {
+            let method = if info.col_type == ColumnType::Numeric {
+                method
+            } else {
+                Method::Mode
+            };
+            fill_column(df, &info.name, method, &info.col_type)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::read_file;
+
+    #[test]
+    fn test_impute_median_fills_numeric() {
+        let mut df = read_file("tests/data/sample.csv").unwrap();
+        let mut expected = df.valid_numeric_column("income").unwrap();
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let result = impute_column(&mut df, "income", Method::Median).unwrap();
+        assert_eq!(result.filled, 3);
+        assert_eq!(result.value, Some(percentile(&expected, 50.0).to_string()));
+        assert_eq!(
+            df.numeric_column("income")
+                .unwrap()
+                .iter()
+                .flatten()
+                .count(),
+            30
+        );
+    }
+
+    #[test]
+    fn test_impute_mode_and_non_numeric_error() {
+        let mut df = read_file("tests/data/sample.csv").unwrap();
+        assert!(impute_column(&mut df, "city", Method::Mean).is_err());
+        let result = impute_column(&mut df, "city", Method::Mode).unwrap();
+        assert_eq!(result.filled, 2);
+        assert!(!df.column("city").unwrap().iter().any(|v| is_missing(v)));
+    }
+
+    #[test]
+    fn test_impute_ignores_nan_cells() {
+        let mut df = DataFrame::from_column("x", &["1", "NAN", "", "3", "inf"]);
+        let median = impute_column(&mut df.clone(), "x", Method::Median).unwrap();
+        assert_eq!(median.value.as_deref(), Some("2"));
+        let mean = impute_column(&mut df, "x", Method::Mean).unwrap();
+        assert_eq!(mean.value.as_deref(), Some("2"));
+        assert_eq!(df.rows[2], ["2"]);
+    }
+
+    #[test]
+    fn test_impute_all_leaves_no_missing() {
+        let mut df = read_file("tests/data/sample.csv").unwrap();
+        let results = impute_all(&mut df, Method::Mean).unwrap();
+        assert_eq!(results.iter().map(|r| r.filled).sum::<usize>(), 6);
+        assert!(crate::missing::analyze(&df).iter().all(|m| m.missing == 0));
+    }
+}
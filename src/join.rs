@@ -0,0 +1,171 @@
+use crate::dedupe::KEY_SEPARATOR;
+use crate::reader::DataFrame;
+use crate::utils::is_missing;
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+
+/// Which rows of the left table are kept.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JoinKind {
+    /// Only rows with a matching key on both sides.
+    Inner,
+    /// Every left row; unmatched ones get empty right-hand cells.
+    Left,
+}
+
+impl JoinKind {
+    /// Parse "inner" or "left".
+    pub fn parse(value: &str) -> Option<JoinKind> {
+        match value {
+            "inner" => Some(JoinKind::Inner),
+            "left" => Some(JoinKind::Left),
+            _ => None,
+        }
+    }
+}
+
+/// Suffix added to right-hand columns whose name is already used on the left.
+const RIGHT_SUFFIX: &str = "_right";
+
+/// Result of joining two tables.
+pub struct Joined {
+    pub df: DataFrame,
+    /// Keys that matched more than one right-hand row, multiplying the left rows.
+    pub duplicate_keys: Vec<String>,
+}
+
+/// Indices of the key columns, failing on the first unknown name.
+fn key_indices(df: &DataFrame, on: &[String], side: &str) -> Result<Vec<usize>> {
+    on.iter()
+        .map(|col| match df.col_index(col) {
+            Some(idx) => Ok(idx),
+            None => bail!("Key column '{}' not found in the {} file", col, side),
+        })
+        .collect()
+}
+
+/// Key of a row compared as trimmed text, so "7" and " 7" match; `None` if any part is missing.
+fn row_key(row: &[String], keys: &[usize]) -> Option<String> {
+    let parts: Vec<&str> = keys.iter().map(|&k| row[k].trim()).collect();
+    if parts.iter().any(|p| is_missing(p)) {
+        return None;
+    }
+    Some(parts.join(KEY_SEPARATOR))
+}
+
+/// Hash-join `right` onto `left` on the `on` columns.
+///
+/// The result has all left columns followed by the non-key right columns; right
+/// names that clash with a left name get a `_right` suffix. Rows with a missing key
+/// never match. A key repeated on the right produces one row per combination.
+pub fn join(left: &DataFrame, right: &DataFrame, on: &[String], how: JoinKind) -> Result<Joined> {
+    if on.is_empty() {
+        bail!("Specify at least one key column with --on");
+    }
+    let left_keys = key_indices(left, on, "left")?;
+    let right_keys = key_indices(right, on, "right")?;
+
+    let right_cols: Vec<usize> = (0..right.ncols())
+        .filter(|i| !right_keys.contains(i))
+        .collect();
+    let mut headers = left.headers.clone();
+    for &i in &right_cols {
+        let name = &right.headers[i];
+        if headers.contains(name) {
+            headers.push(format!("{}{}", name, RIGHT_SUFFIX));
+        } else {
+            headers.push(name.clone());
+        }
+    }
+
+    let mut index: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, row) in right.rows.iter().enumerate() {
+        if let Some(key) = row_key(row, &right_keys) {
+            index.entry(key).or_default().push(i);
+        }
+    }
+
+    let mut rows = Vec::new();
+    let mut duplicate_keys = Vec::new();
+    for row in &left.rows {
+        let matches = row_key(row, &left_keys).and_then(|key| index.get(&key).map(|m| (key, m)));
+        match matches {
+            Some((key, matched)) => {
+                if matched.len() > 1 && !duplicate_keys.contains(&key) {
+                    duplicate_keys.push(key);
+                }
+                for &j in matched {
+                    let mut joined = row.clone();
+                    joined.extend(right_cols.iter().map(|&c| right.rows[j][c].clone()));
+                    rows.push(joined);
+                }
+            }
+            None if how == JoinKind::Left => {
+                let mut joined = row.clone();
+                joined.resize(headers.len(), String::new());
+                rows.push(joined);
+            }
+            None => {}
+        }
+    }
+
+    let duplicate_keys = duplicate_keys
+        .into_iter()
+        .map(|k| k.replace(KEY_SEPARATOR, ","))
+        .collect();
+    Ok(Joined {
+        df: DataFrame { headers, rows },
+        duplicate_keys,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn people() -> DataFrame {
+        DataFrame::from_rows(
+            &["id", "name", "score"],
+            &[["1", "Ana", "80"], ["2", "Ben", "75"], ["3", "Carla", "90"]],
+        )
+    }
+
+    fn visits() -> DataFrame {
+        DataFrame::from_rows(
+            &["id", "city", "score"],
+            &[
+                [" 1", "Cali", "5"],
+                ["3", "Bogota", "7"],
+                ["3", "Pasto", "8"],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_inner_join_expands_duplicate_keys() {
+        let on = ["id".to_string()];
+        let joined = join(&people(), &visits(), &on, JoinKind::Inner).unwrap();
+        assert_eq!(
+            joined.df.headers,
+            ["id", "name", "score", "city", "score_right"]
+        );
+        assert_eq!(
+            joined.df.rows,
+            [
+                ["1", "Ana", "80", "Cali", "5"],
+                ["3", "Carla", "90", "Bogota", "7"],
+                ["3", "Carla", "90", "Pasto", "8"],
+            ]
+        );
+        assert_eq!(joined.duplicate_keys, ["3"]);
+    }
+
+    #[test]
+    fn test_left_join_keeps_unmatched_rows() {
+        let on = ["id".to_string()];
+        let joined = join(&people(), &visits(), &on, JoinKind::Left).unwrap();
+        assert_eq!(joined.df.nrows(), 4);
+        assert_eq!(joined.df.rows[1], ["2", "Ben", "75", "", ""]);
+        assert!(join(&people(), &visits(), &["name".to_string()], JoinKind::Left).is_err());
+    }
+}
@@ -0,0 +1,125 @@
+use crate::dedupe;
+use crate::reader::DataFrame;
+use crate::types::{self, NumericKind};
+use crate::utils::is_missing;
+use serde::Serialize;
+
+/// How close one column, or a combination of columns, comes to identifying rows.
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyCandidate {
+    pub columns: Vec<String>,
+    /// Distinct values (or value combinations) among rows with no missing part.
+    pub unique: usize,
+    /// Rows where at least one of the columns is missing.
+    pub missing: usize,
+    /// `unique` as a fraction of all rows.
+    pub ratio: f64,
+    /// Every row has a value and no two rows share one.
+    pub candidate: bool,
+}
+
+/// Uniqueness of every column, in header order.
+pub fn single_keys(df: &DataFrame) -> Vec<KeyCandidate> {
+    types::infer_types(df)
+        .into_iter()
+        .map(|info| {
+            let missing = df
+                .column(&info.name)
+                .map_or(0, |values| values.iter().filter(|v| is_missing(v)).count());
+            candidate(df, vec![info.name], info.unique_count, missing)
+        })
+        .collect()
+}
+
+/// Pairs of columns that together identify every row although neither does alone.
+///
+/// Float columns are left out: continuous measurements are almost always unique
+/// in combination with anything, which says nothing about the row's identity.
+pub fn composite_keys(df: &DataFrame, singles: &[KeyCandidate]) -> Vec<KeyCandidate> {
+    let eligible: Vec<String> = types::infer_types(df)
+        .into_iter()
+        .zip(singles)
+        .filter(|(info, single)| {
+            info.numeric_kind != Some(NumericKind::Float)
+                && !single.candidate
+                && single.missing == 0
+        })
+        .map(|(info, _)| info.name)
+        .collect();
+
+    let mut pairs = Vec::new();
+    for (i, first) in eligible.iter().enumerate() {
+        for second in &eligible[i + 1..] {
+            let columns = vec![first.clone(), second.clone()];
+            let unique = dedupe::unique_rows(df, Some(&columns)).map_or(0, |rows| rows.len());
+            if unique == df.nrows() {
+                pairs.push(candidate(df, columns, unique, 0));
+            }
+        }
+    }
+    pairs
+}
+
+fn candidate(df: &DataFrame, columns: Vec<String>, unique: usize, missing: usize) -> KeyCandidate {
+    let nrows = df.nrows();
+    KeyCandidate {
+        columns,
+        unique,
+        missing,
+        ratio: if nrows == 0 {
+            f64::NAN
+        } else {
+            unique as f64 / nrows as f64
+        },
+        candidate: nrows > 0 && missing == 0 && unique == nrows,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::read_file;
+
+    #[test]
+    fn test_single_keys_find_id_column() {
+        let df = read_file("tests/data/sample.csv").unwrap();
+        let singles = single_keys(&df);
+        let find = |name: &str| singles.iter().find(|k| k.columns == [name]).unwrap();
+
+        let id = find("id");
+        assert!(id.candidate);
+        assert_eq!(id.unique, 30);
+        assert!((id.ratio - 1.0).abs() < 1e-10);
+
+        let gender = find("gender");
+        assert!(!gender.candidate);
+        assert_eq!(gender.unique, 3);
+
+        // Unique among its values, but missing in some rows
+        let income = find("income");
+        assert_eq!(income.missing, 3);
+        assert!(!income.candidate);
+    }
+
+    #[test]
+    fn test_composite_keys_pairs_neither_unique_alone() {
+        let df = DataFrame::from_rows(
+            &["store", "month", "sales"],
+            &[
+                ["a", "1", "1.5"],
+                ["a", "2", "2.5"],
+                ["b", "1", "3.5"],
+                ["b", "2", "4.5"],
+            ],
+        );
+        let singles = single_keys(&df);
+        assert!(singles
+            .iter()
+            .all(|k| k.columns[0] == "sales" || !k.candidate));
+
+        let pairs = composite_keys(&df, &singles);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].columns, ["store", "month"]);
+        assert!(pairs[0].candidate);
+    }
+}
@@ -1,14 +1,34 @@
+mod bin;
+#[cfg(feature = "image")]
+mod chart;
 mod correlation;
+mod crosstab;
+mod dedupe;
 mod display;
+mod filter;
+mod impute;
+mod join;
+mod keys;
 mod missing;
+mod normalize;
+mod pivot;
 mod plot;
+mod profile;
 mod reader;
+mod report;
+mod sample;
+mod sketch;
 mod stats;
+mod stream;
+mod tests;
+mod transform;
 mod types;
+mod validate;
 pub mod utils;
 
 use anyhow::{bail, Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use std::collections::HashMap;
 use std::fs;
 use std::process;
 
@@ -23,16 +43,96 @@ Common workflows:
   Quick overview:      statsctl summary data.csv
   Specific columns:    statsctl summary data.csv --vars age,income
   All columns:         statsctl summary data.csv --all
+  Summary per group:   statsctl summary data.csv --by region
   Missing analysis:    statsctl missing data.csv --patterns
   Visualize:           statsctl plot data.csv --var age --type histogram
   Correlations:        statsctl correlation data.csv --min 0.7
+  Complete pairs:      statsctl overlap data.csv
+  Value counts:        statsctl freq data.csv --var city
+  All distinct values: statsctl unique data.csv --var city --sort count
+  Percentile rank:     statsctl rank data.csv --var score --value 87
+  Contingency table:   statsctl crosstab data.csv --vars gender,city
+  Group means (ANOVA): statsctl anova data.csv --var income --by city
+  Column overview:     statsctl profile data.csv
+  Find key columns:    statsctl keys data.csv
+  Preview rows:        statsctl head data.csv -n 5
+  Random subset:       statsctl sample data.csv --n 1000 --seed 42
+  Subset rows:         statsctl filter data.csv --where \"age>30\"
+  Fill missing values: statsctl impute data.csv --var income --method median
+  Drop sparse columns: statsctl drop-missing data.csv --col-threshold 0.5
+  Remove duplicates:   statsctl dedupe data.csv --subset id
+  Merge two files:     statsctl join people.csv visits.csv --on id
+  Long to wide:        statsctl pivot sales.csv --index month --columns product --values sales
+  Rescale to [0, 1]:   statsctl normalize data.csv --method minmax
+  Moving average:      statsctl transform data.csv --var sales --method rolling-mean --window 7
+  Quantile bins:       statsctl bin data.csv --var income --bins 4 --method quantile
   Compare datasets:    statsctl compare train.csv test.csv
   Export markdown:     statsctl summary data.csv -o report.md
-  Pipe from stdin:     cat data.csv | statsctl summary --stdin"
+  Export HTML:         statsctl correlation data.csv -o corr.html
+  One file per table:  statsctl summary data.csv --all --output-dir reports/
+  Pipe from stdin:     cat data.csv | statsctl summary --stdin
+  Plain output:        statsctl correlation data.csv --no-color > corr.txt
+  ASCII table borders: statsctl summary data.csv --style plain
+  Very wide files:     statsctl summary wide.csv --transpose --max-columns 10
+  More decimals:       statsctl summary data.csv --precision 6
+  Semicolon files:     statsctl summary data.csv --delimiter ';'
+  Excel workbooks:     statsctl summary data.xlsx --sheet Results
+  Skip preamble:       statsctl summary export.csv --skip-rows 3
+  Ignore comments:     statsctl summary results.csv --comment '#'
+  Find typos:          statsctl validate data.csv --var age
+  Headerless files:    statsctl summary raw.csv --no-header --vars col2,col3
+  Sentinel values:     statsctl missing data.csv --na-values \"-999,Unknown\"
+  Reject broken rows:  statsctl summary export.csv --strict-rows
+  Large files:         statsctl summary big.csv --stream
+  Monthly files:       statsctl summary 'data_2024_*.csv'"
 )]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Disable colored output (also honored via the NO_COLOR environment variable)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Fixed number of decimal places for statistics (default: based on magnitude)
+    #[arg(long, global = true, value_name = "N")]
+    precision: Option<usize>,
+
+    /// Table borders: table (rounded box-drawing) or plain (ASCII +, -, |)
+    #[arg(long, global = true, value_name = "STYLE", default_value = "table", value_parser = report::TableStyle::parse)]
+    style: report::TableStyle,
+
+    /// Show at most N columns in types, summary and correlation output (rows in the default summary)
+    #[arg(long, global = true, value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    max_columns: Option<usize>,
+
+    /// Field delimiter for input files, e.g. ';' '|' '\t' (default: auto-detect comma/tab)
+    #[arg(long, global = true, visible_alias = "sep", value_name = "CHAR", value_parser = reader::parse_delimiter)]
+    delimiter: Option<u8>,
+
+    /// Worksheet to read from .xlsx/.xls files (default: the first sheet)
+    #[arg(long, global = true)]
+    sheet: Option<String>,
+
+    /// Skip the first N lines of the input (e.g. metadata before the header)
+    #[arg(long, global = true, value_name = "N", default_value_t = 0)]
+    skip_rows: usize,
+
+    /// Ignore lines starting with this character, e.g. '#'
+    #[arg(long, global = true, value_name = "CHAR", value_parser = reader::parse_comment)]
+    comment: Option<u8>,
+
+    /// The input has no header row; columns are named col1, col2, ...
+    #[arg(long, global = true)]
+    no_header: bool,
+
+    /// Fail on rows whose field count differs from the header instead of padding or truncating
+    #[arg(long, global = true)]
+    strict_rows: bool,
+
+    /// Extra comma-separated values to treat as missing, e.g. "-999,Unknown"
+    #[arg(long, global = true, value_name = "VALUES", allow_hyphen_values = true)]
+    na_values: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -49,39 +149,94 @@ Examples:
   statsctl summary data.csv --vars age,income,score
       Describe only the specified columns
 
+  statsctl summary data.csv --vars 1,3,5-8
+      Select columns by 1-based position or inclusive range
+
+  statsctl summary survey.csv --columns-regex '^q[0-9]+_'
+      Select every column whose name matches a regular expression (adds to --vars)
+
   statsctl summary data.csv --all
       Include categorical variable summaries (top values, unique counts)
 
+  statsctl summary data.csv --categorical-only --diversity
+      Add entropy, normalized entropy and Gini impurity per categorical column
+
+  statsctl summary data.csv --categorical-only
+      Show only the categorical summaries
+
+  statsctl summary data.csv --categorical-only --missing-tokens
+      List which missing markers (NA, N/A, empty, null...) each column uses
+
+  statsctl summary events.csv --categorical-only --stream --approx-cardinality
+      Estimate unique counts in fixed memory, for ID-like columns with millions of values
+
+  statsctl summary data.csv --by region
+      One summary table per region; rows missing the region form a '(missing)' group
+
   statsctl summary data.csv -o report.md
       Export the summary table to a Markdown file
 
-  cat data.csv | statsctl summary --stdin
-      Read data from a piped command via stdin")]
-    Summary {
-        /// Path to the CSV/TSV file
-        file: Option<String>,
+  statsctl summary data.csv --all --output-dir reports/
+      Write summary.csv and categorical.csv as separate files in reports/
 
-        /// Comma-separated list of column names
-        #[arg(long)]
-        vars: Option<String>,
+  statsctl summary data.csv --format jsonl
+      Print one compact JSON object per column, for log pipelines
 
-        /// Include all columns (numeric + categorical)
-        #[arg(long)]
-        all: bool,
+  statsctl summary data.csv --sort-by std --desc
+      List the highest-variance columns first
 
-        /// Output file path (supports .md, .json, .csv)
-        #[arg(long, short)]
-        output: Option<String>,
+  statsctl summary data.csv --vars age,income --transpose
+      Show statistics as rows and variables as columns
 
-        /// Read from stdin
-        #[arg(long)]
-        stdin: bool,
-    },
+  statsctl summary data.csv --signs
+      Count zeros and negatives, e.g. to catch a revenue column that dips below 0
+
+  statsctl summary data.csv --round
+      Show whole-number quartiles of count-like columns as 25 rather than 25.00
+
+  statsctl summary prices.csv --precision-map \"price=2,rate=4\"
+      Two decimals for price and four for rate; other columns keep the default
+
+  statsctl summary data.csv --spread
+      Add IQR and range columns
+
+  statsctl summary data.csv --bootstrap-median --seed 42
+      Add a reproducible 95% bootstrap confidence interval for each median
+
+  statsctl summary data.csv --normality
+      Add a Jarque–Bera normality p-value per column (NaN below 8 values)
+
+  statsctl summary data.csv --trim 0.1
+      Add a 10% trimmed mean that ignores the extreme tails
+
+  statsctl summary data.csv --winsorize 0.05
+      Clip values beyond the 5th and 95th percentiles to them before summarizing
+
+  statsctl summary rates.csv --means geometric,harmonic
+      Add geometric and harmonic means (positive values only)
+
+  statsctl summary data.csv --bimodal-threshold 0.6
+      Flag fewer columns as possibly multimodal (default 0.555)
+
+  cat data.csv | statsctl summary --stdin
+      Read data from a piped command via stdin
+
+  statsctl summary 'data_2024_*.csv'
+      Combine every matching file (same columns required) into one dataset
+
+  statsctl summary big.csv --stream
+      Read the file in one pass without loading it; quartiles are not computed
+
+  cat big.csv | statsctl summary --stdin --approx --reservoir-size 50000
+      Stream the input and estimate quartiles from a random sample of each column")]
+    Summary(SummaryArgs),
 
     /// Missing data analysis
     #[command(long_about = "\
 Analyze missing data across all columns, showing counts and percentages. \
-Use --patterns to reveal co-occurrence patterns of missingness.
+Use --patterns to reveal co-occurrence patterns of missingness, or \
+--correlations for the phi coefficient between the missing-value indicators of \
+each pair of columns that have missing values.
 
 Examples:
   statsctl missing data.csv
@@ -93,32 +248,27 @@ Examples:
   statsctl missing data.csv --patterns
       Show which columns tend to be missing together (co-occurrence patterns)
 
+  statsctl missing data.csv --patterns --top-patterns 25 --min-pattern-count 3
+      List up to 25 patterns, leaving out those seen in fewer than 3 rows
+
   statsctl missing data.csv --patterns -o missing_report.md
       Export the full missing data report to Markdown
 
   statsctl missing survey_responses.tsv --only-missing --patterns
-      Combine filters: only missing columns with pattern analysis")]
-    Missing {
-        /// Path to the CSV/TSV file
-        file: String,
-
-        /// Show only columns with missing values
-        #[arg(long)]
-        only_missing: bool,
+      Combine filters: only missing columns with pattern analysis
 
-        /// Show missing data patterns
-        #[arg(long)]
-        patterns: bool,
+  statsctl missing data.csv --correlations
+      Correlate missingness indicators to see which columns go missing together
 
-        /// Output file path
-        #[arg(long, short)]
-        output: Option<String>,
-    },
+  statsctl missing big.csv --stream
+      Count missing values in one pass without loading the file")]
+    Missing(MissingArgs),
 
     /// Correlation matrix for numeric variables
     #[command(long_about = "\
 Compute the Pearson correlation matrix for numeric columns using pairwise \
-complete observations. Highlights high correlations with color coding.
+complete observations. Highlights high correlations with color coding. With \
+--categorical, compute Cramér's V between categorical columns instead.
 
 Examples:
   statsctl correlation data.csv
@@ -130,24 +280,59 @@ Examples:
   statsctl correlation data.csv --min 0.7
       Highlight pairs with |r| >= 0.7
 
+  statsctl correlation data.csv --min 0.3 --max-p 0.05
+      List pairs with |r| >= 0.3 that are also significant at the 5% level
+
+  statsctl correlation data.csv --heatmap
+      Render the matrix as shaded blocks for a quick visual scan
+
   statsctl correlation data.csv --min 0.3 -o corr.json
       Export correlations as JSON with a lower threshold
 
   statsctl correlation wide_dataset.csv --vars x1,x2,x3,x4,x5
-      Focused correlation analysis on a subset of features")]
-    Correlation {
+      Focused correlation analysis on a subset of features
+
+  statsctl correlation data.csv --categorical
+      Cramér's V between categorical columns (0 = independent, 1 = fully associated)
+
+  statsctl correlation data.csv --heatmap --cluster
+      Reorder columns so strongly correlated variables sit next to each other
+
+  statsctl correlation data.csv --show-n --min-n 50
+      Mark coefficients computed from fewer than 50 complete pairs with *
+
+  statsctl correlation data.csv --target price
+      Rank every other numeric column by R² against price
+
+  statsctl correlation data.csv --vars income,score --control age
+      Correlation of income and score after removing the linear effect of age")]
+    Correlation(CorrelationArgs),
+
+    /// Count rows where both columns are present
+    #[command(long_about = "\
+Matrix of pairwise complete observations: for each pair of numeric columns, the \
+number of rows where both values are present. This is the n behind each cell of \
+the correlation matrix, so it shows which coefficients rest on few rows. The \
+diagonal counts each column's own non-missing values.
+
+Examples:
+  statsctl overlap data.csv
+      Complete pairs between all numeric columns
+
+  statsctl overlap data.csv --vars age,income,score
+      Restrict the matrix to a few columns
+
+  statsctl overlap survey.csv -o overlap.csv
+      Export the counts as CSV")]
+    Overlap {
         /// Path to the CSV/TSV file
         file: String,
 
-        /// Comma-separated list of column names
+        /// Comma-separated column names, 1-based indices or ranges (e.g. 1,3,5-8)
         #[arg(long)]
         vars: Option<String>,
 
-        /// Minimum correlation threshold to highlight
-        #[arg(long, default_value = "0.5")]
-        min: f64,
-
-        /// Output file path
+        /// Output file path (supports .md, .json, .csv, .html)
         #[arg(long, short)]
         output: Option<String>,
     },
@@ -155,47 +340,287 @@ Examples:
     /// Quick ASCII plots
     #[command(long_about = "\
 Generate ASCII-art visualizations directly in the terminal. Supports histograms, \
-boxplots, and scatter plots for quick exploratory data analysis.
+empirical CDFs, boxplots, scatter plots and scatter matrices, line plots, normal QQ plots and missing-data maps for \
+quick exploratory data analysis. In an interactive terminal, single-column plots \
+without --var offer a numbered list of the numeric columns to pick from.
 
 Examples:
   statsctl plot data.csv --var age --type histogram
       Histogram of the age column
 
+  statsctl plot data.csv --type histogram
+      Pick the column from a numbered list (terminal only; scripts must pass --var)
+
+  statsctl plot data.csv --var latency --type ecdf
+      Empirical CDF with the median and 95th percentile marked
+
   statsctl plot data.csv --var income --type boxplot
       Boxplot showing quartiles and outliers for income
 
+  statsctl plot data.csv --var income --type boxplot --by region
+      One boxplot per region on a shared scale
+
   statsctl plot data.csv --vars age,income --type scatter
       Scatter plot of age (x) vs income (y)
 
+  statsctl plot data.csv --vars age,income,score --type splom
+      Scatter matrix: a small scatter for every pair of up to 6 columns
+
+  statsctl plot data.csv --var sales --type line
+      Line plot of a time-ordered column against its row index
+
+  statsctl plot data.csv --vars revenue,cost --type line
+      Up to 4 series on one shared scale, one glyph each with a legend
+
+  statsctl plot data.csv --var residuals --type qq
+      Normal QQ plot to check whether residuals look normally distributed
+
+  statsctl plot data.csv --type missing
+      Map of missing cells: one column per variable, one line per (group of) rows
+
+  statsctl plot data.csv --var score --type hist --bins 30
+      Histogram with an explicit bin count instead of Sturges' rule
+
+  statsctl plot data.csv --var score --type hist --density
+      Overlay a kernel density estimate on the histogram
+
+  statsctl plot data.csv --var revenue --type hist --log
+      Bin on a log10 scale for data spanning orders of magnitude
+
   statsctl plot data.csv --var score --type hist -o plot.txt
       Save a histogram to a text file
 
+  statsctl plot data.csv --var score --type hist --density -o plot.png
+      Render the histogram as a PNG (or .svg) image; needs the `image` feature
+
   statsctl plot data.csv --var income --type box
       Shorthand: 'hist' and 'box' are accepted aliases")]
-    Plot {
+    Plot(PlotArgs),
+
+    /// Contingency table of two categorical variables
+    #[command(long_about = "\
+Cross-tabulate two categorical columns into a two-way frequency table with row \
+and column totals. Rows with a missing value in either column are skipped. \
+Variables with more than 30 levels are truncated to their most frequent levels.
+
+Examples:
+  statsctl crosstab data.csv --vars gender,city
+      Counts of each gender/city combination
+
+  statsctl crosstab data.csv --vars gender,employed --normalize row
+      Proportions within each row (each row sums to 1)
+
+  statsctl crosstab data.csv --vars gender,city --normalize all -o table.md
+      Proportions of the grand total, exported to Markdown")]
+    Crosstab {
+        /// Path to the CSV/TSV file
+        file: String,
+
+        /// Two comma-separated column names: row,column
+        #[arg(long)]
+        vars: String,
+
+        /// Show proportions instead of counts: row, col, all
+        #[arg(long)]
+        normalize: Option<String>,
+
+        /// Output file path (supports .md, .json, .csv, .html)
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+
+    /// One-way ANOVA of a numeric variable across groups
+    #[command(long_about = "\
+Test whether the mean of a numeric column differs across the levels of a \
+categorical column with a one-way analysis of variance. Reports each group's \
+size and mean, the F statistic with its degrees of freedom and p-value, and \
+eta-squared, the share of the variance explained by the groups. Rows missing \
+either value are skipped.
+
+Examples:
+  statsctl anova data.csv --var income --by city
+      Does mean income differ between cities?
+
+  statsctl anova data.csv --var score --by gender -o anova.md
+      Export the group means and ANOVA table to Markdown")]
+    Anova {
+        /// Path to the CSV/TSV file
+        file: String,
+
+        /// Numeric column to compare
+        #[arg(long)]
+        var: String,
+
+        /// Categorical column defining the groups
+        #[arg(long)]
+        by: String,
+
+        /// Output file path (supports .md, .json, .csv, .html)
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+
+    /// Frequency table of the values in one column
+    #[command(long_about = "\
+List every distinct value of a column with its count and percentage of rows, \
+most frequent first. Missing values are counted in their own \"(missing)\" row. \
+Numeric columns can be bucketed into equal-width bins with --bins.
+
+Examples:
+  statsctl freq data.csv --var city
+      Count each city, most frequent first
+
+  statsctl freq data.csv --var city --top 5
+      Show only the five most frequent values
+
+  statsctl freq data.csv --var income --bins 10
+      Bucket a numeric column into 10 bins (same bins as the histogram)
+
+  statsctl freq data.csv --var city -o city_counts.csv
+      Export the frequency table to CSV")]
+    Freq {
+        /// Path to the CSV/TSV file
+        file: String,
+
+        /// Column to count
+        #[arg(long)]
+        var: String,
+
+        /// Show only the N most frequent values (the N fullest bins with --bins)
+        #[arg(long, value_name = "N")]
+        top: Option<usize>,
+
+        /// Bucket a numeric column into this many equal-width bins
+        #[arg(long)]
+        bins: Option<usize>,
+
+        /// Output file path (supports .md, .json, .csv, .html)
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+
+    /// Discretize a numeric column into bins, as CSV
+    #[command(long_about = "\
+Assign every value of a numeric column to a bin and write value,bin pairs to stdout \
+as CSV (bins are numbered from 1; missing values get an empty bin). The bin edges \
+and counts are printed to stderr. width makes equal-width bins over the range; \
+quantile puts edges at evenly spaced percentiles so bins hold about the same number \
+of values. A value on an edge goes to the lower bin. Heavy ties can leave quantile \
+bins empty; these are reported.
+
+Examples:
+  statsctl bin data.csv --var income --bins 4 --method quantile
+      Income quartile of every row
+
+  statsctl bin data.csv --var age --bins 5 > age_bins.csv
+      Five equal-width age bins, saved to a file")]
+    Bin {
+        /// Path to the CSV/TSV file
+        file: String,
+
+        /// Numeric column to bin
+        #[arg(long)]
+        var: String,
+
+        /// Number of bins
+        #[arg(long, default_value_t = 10)]
+        bins: usize,
+
+        /// Bin edges: width (equal width), quantile (equal frequency)
+        #[arg(long, default_value = "width")]
+        method: String,
+    },
+
+    /// Find values that do not parse in numeric columns
+    #[command(long_about = "\
+Check that numeric columns really are numeric. For every column inferred as numeric \
+(or just the one named with --var), list each distinct non-missing value that does \
+not parse as a number, with the data rows it appears on (1-based, header excluded). \
+Summaries silently skip these cells, so this is the place to find typos like 3O.
+
+Examples:
+  statsctl validate data.csv
+      Check every numeric column
+
+  statsctl validate data.csv --var age
+      Check one column, even if too little of it parses to count as numeric
+
+  statsctl validate data.csv -o problems.csv
+      Export the offending values and rows to CSV")]
+    Validate {
         /// Path to the CSV/TSV file
         file: String,
 
-        /// Column name (for histogram, boxplot)
+        /// Column to check (default: every numeric column)
         #[arg(long)]
         var: Option<String>,
 
-        /// Comma-separated column names (for scatter: x,y)
+        /// Output file path (supports .md, .json, .csv, .html)
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+
+    /// Every distinct value of a column with its count
+    #[command(long_about = "\
+List every distinct non-missing value of a column with its count, however many \
+there are (types --show-levels stops at 20). Values are sorted by value by default \
+(numerically when they are all numbers), or by count with --sort count.
+
+Examples:
+  statsctl unique data.csv --var city
+      Every city, alphabetically, with its count
+
+  statsctl unique data.csv --var zip --sort count
+      Most frequent zip codes first
+
+  statsctl unique data.csv --var customer_id -o ids.csv
+      Write a long list to a file instead of the terminal")]
+    Unique {
+        /// Path to the CSV/TSV file
+        file: String,
+
+        /// Column to list
         #[arg(long)]
-        vars: Option<String>,
+        var: String,
 
-        /// Plot type: histogram, boxplot, scatter
-        #[arg(long = "type", default_value = "histogram")]
-        plot_type: String,
+        /// Order: value, count (most frequent first)
+        #[arg(long, default_value = "value")]
+        sort: String,
 
-        /// Output file path
+        /// Output file path (supports .md, .json, .csv, .html)
         #[arg(long, short)]
         output: Option<String>,
     },
 
+    /// Percentile rank of a value within a numeric column
+    #[command(long_about = "\
+Report the percentile rank of a value within a numeric column: the percentage of \
+non-missing values at or below it. Values below the column's minimum rank 0 and \
+values at or above its maximum rank 100.
+
+Examples:
+  statsctl rank data.csv --var score --value 87
+      What share of scores are 87 or lower?
+
+  statsctl rank data.csv --var income --value 100000
+      Where a six-figure income falls in the distribution")]
+    Rank {
+        /// Path to the CSV/TSV file
+        file: String,
+
+        /// Numeric column to rank against
+        #[arg(long)]
+        var: String,
+
+        /// Value to look up
+        #[arg(long, allow_hyphen_values = true)]
+        value: f64,
+    },
+
     /// Infer and display data types
     #[command(long_about = "\
-Analyze each column and infer its data type (Numeric, Boolean, or Categorical). \
+Analyze each column and infer its data type (Integer, Float, Boolean, or Categorical). \
+Numeric columns are reported as Integer when every value is a whole number. \
 Optionally display the unique levels for categorical and boolean columns.
 
 Examples:
@@ -205,8 +630,14 @@ Examples:
   statsctl types data.csv --show-levels
       Also display the distinct values for categorical/boolean columns
 
+  statsctl types data.csv --near-constant 0.99
+      Also flag columns where one value covers more than 99% of rows
+
   statsctl types survey.tsv
-      Works with tab-separated files as well")]
+      Works with tab-separated files as well
+
+  statsctl types big.csv --stream
+      Infer types in one pass without loading the file")]
     Types {
         /// Path to the CSV/TSV file
         file: String,
@@ -214,12 +645,64 @@ Examples:
         /// Show unique values / levels for categorical variables
         #[arg(long)]
         show_levels: bool,
+
+        /// Flag columns where one value exceeds this fraction of non-missing values
+        #[arg(long, value_name = "FRACTION")]
+        near_constant: Option<f64>,
+
+        /// Read rows one at a time instead of loading the file
+        #[arg(long)]
+        stream: bool,
+    },
+
+    /// One-table overview of every column
+    #[command(long_about = "\
+Profile every column in a single table: inferred type, number of unique values, \
+percentage missing and an example value (the first non-missing cell, or \
+\"(all missing)\"). Combines what types, missing and head show separately.
+
+Examples:
+  statsctl profile data.csv
+      Type, cardinality, missingness and an example for each column
+
+  statsctl profile survey.xlsx --sheet Responses -o profile.md
+      Export the overview of a worksheet to Markdown")]
+    Profile {
+        /// Path to the CSV/TSV file
+        file: String,
+
+        /// Output file path (supports .md, .json, .csv, .html)
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+
+    /// Find columns that uniquely identify rows
+    #[command(long_about = "\
+Report how unique each column is and flag candidate primary keys: columns with a \
+value in every row and no value repeated. Pairs of columns that identify every \
+row together, although neither does alone, are listed as composite keys. Float \
+columns are not paired, since continuous measurements are nearly always unique.
+
+Examples:
+  statsctl keys data.csv
+      Uniqueness of each column, with id-like columns marked as key candidates
+
+  statsctl keys panel.csv -o keys.md
+      Find the (unit, period) pair that identifies each row of a panel")]
+    Keys {
+        /// Path to the CSV/TSV file
+        file: String,
+
+        /// Output file path (supports .md, .json, .csv, .html)
+        #[arg(long, short)]
+        output: Option<String>,
     },
 
     /// Compare two datasets
     #[command(long_about = "\
 Side-by-side comparison of descriptive statistics and missing data between two \
-CSV/TSV files. Useful for comparing train/test splits, before/after transformations, \
+CSV/TSV files, plus a two-sample Kolmogorov–Smirnov test per numeric column to flag \
+distribution shift. Useful for comparing train/test splits, before/after transformations, \
 or different time periods.
 
 Examples:
@@ -229,324 +712,2482 @@ Examples:
   statsctl compare train.csv test.csv --vars age,income
       Compare only specific columns
 
+  statsctl compare train.csv test.csv --detailed
+      One block per variable comparing count, mean, std and quartiles vertically
+
+  statsctl compare train.csv test.csv --var age --plot
+      Overlay both files' histograms of age on shared bins (█ first file, ▒ second)
+
   statsctl compare before.csv after.csv -o comparison.md
       Export the comparison report to Markdown
 
   statsctl compare 2023_data.csv 2024_data.csv --vars revenue,users
       Compare specific metrics across yearly snapshots")]
-    Compare {
-        /// First file path
-        file1: String,
+    Compare(CompareArgs),
 
-        /// Second file path
-        file2: String,
+    /// Show the first rows of a file
+    #[command(long_about = "\
+Print the first N rows of a file as an aligned table, with their row numbers. \
+Very wide values are truncated. Files shorter than N rows are shown in full.
 
-        /// Comma-separated list of column names to compare
-        #[arg(long)]
-        vars: Option<String>,
+Examples:
+  statsctl head data.csv
+      First 10 rows
 
-        /// Output file path
-        #[arg(long, short)]
-        output: Option<String>,
+  statsctl head data.csv -n 25
+      First 25 rows
+
+  statsctl head raw.csv --no-header --skip-rows 2
+      Check how preamble and header options parse a file")]
+    Head {
+        /// Path to the CSV/TSV file
+        file: String,
+
+        /// Number of rows to show
+        #[arg(short, long, default_value_t = 10)]
+        n: usize,
     },
-}
 
-fn main() {
-    let cli = Cli::parse();
+    /// Show the last rows of a file
+    #[command(long_about = "\
+Print the last N rows of a file as an aligned table, with their row numbers. \
+Very wide values are truncated. Files shorter than N rows are shown in full.
 
-    let result = match cli.command {
-        Commands::Summary {
-            file,
-            vars,
-            all,
-            output,
-            stdin,
-        } => cmd_summary(file, vars, all, output, stdin),
-        Commands::Missing {
-            file,
-            only_missing,
-            patterns,
-            output,
-        } => cmd_missing(&file, only_missing, patterns, output),
-        Commands::Correlation {
-            file,
-            vars,
-            min,
-            output,
-        } => cmd_correlation(&file, vars, min, output),
-        Commands::Plot {
-            file,
-            var,
-            vars,
-            plot_type,
-            output,
-        } => cmd_plot(&file, var, vars, &plot_type, output),
-        Commands::Types { file, show_levels } => cmd_types(&file, show_levels),
-        Commands::Compare {
-            file1,
-            file2,
-            vars,
-            output,
-        } => cmd_compare(&file1, &file2, vars, output),
-    };
+Examples:
+  statsctl tail data.csv
+      Last 10 rows
 
-    if let Err(e) = result {
-        eprintln!("Error: {:#}", e);
-        process::exit(1);
-    }
-}
+  statsctl tail data.csv -n 5
+      Last 5 rows")]
+    Tail {
+        /// Path to the CSV/TSV file
+        file: String,
 
-fn load_data(file: Option<&str>, stdin: bool) -> Result<reader::DataFrame> {
-    if stdin {
-        reader::read_stdin()
-    } else {
-        match file {
-            Some(path) => reader::read_file(path),
-            None => bail!("No file specified. Use --stdin to read from stdin."),
-        }
-    }
-}
+        /// Number of rows to show
+        #[arg(short, long, default_value_t = 10)]
+        n: usize,
+    },
 
-fn write_output(content: &str, output: Option<&str>) -> Result<()> {
-    match output {
-        Some(path) => {
-            // Determine format from extension
-            let format = if path.ends_with(".json") {
-                "json"
-            } else if path.ends_with(".csv") {
-                "csv"
-            } else {
+    /// Draw a random subset of rows as CSV
+    #[command(long_about = "\
+Select a random subset of rows and write it to stdout as CSV, header included, \
+so the result can be piped into another statsctl command. Rows keep their \
+original order. Pass --seed to get the same subset on every run.
+
+Examples:
+  statsctl sample data.csv --n 1000 --seed 42
+      1000 reproducible random rows
+
+  statsctl sample data.csv --frac 0.1 > subset.csv
+      A 10% sample saved to a new file
+
+  statsctl sample big.csv --n 5000 --seed 1 | statsctl summary --stdin
+      Summarize a sample of a large file")]
+    Sample {
+        /// Path to the CSV/TSV file
+        file: String,
+
+        /// Number of rows to draw (capped at the number of rows)
+        #[arg(long)]
+        n: Option<usize>,
+
+        /// Fraction of rows to draw, between 0 and 1
+        #[arg(long)]
+        frac: Option<f64>,
+
+        /// Seed for a reproducible sample
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+
+    /// Keep only rows matching conditions, as CSV
+    #[command(long_about = "\
+Write the rows that satisfy every --where condition to stdout as CSV, header \
+included. A condition is COLUMN OP VALUE with OP one of >, <, >=, <=, ==, !=. \
+Numeric columns are compared as numbers and missing values never match; other \
+columns support == and != on the text. Quote conditions in the shell.
+
+Examples:
+  statsctl filter data.csv --where \"age>30\"
+      Rows where age is above 30
+
+  statsctl filter data.csv --where \"age>30\" --where \"city==Denver\"
+      Both conditions must hold
+
+  statsctl filter data.csv --where \"income>=50000\" | statsctl summary --stdin
+      Summarize the filtered subset")]
+    Filter {
+        /// Path to the CSV/TSV file
+        file: String,
+
+        /// Condition such as \"age>30\"; repeat to combine with AND
+        #[arg(long = "where", value_name = "EXPR", required = true)]
+        conditions: Vec<String>,
+    },
+
+    /// Fill missing values and write the dataset as CSV
+    #[command(long_about = "\
+Replace missing cells with the mean or median of a numeric column, or the most \
+frequent value (mode) of any column, and write the full dataset to stdout as \
+CSV. With --all every column is filled by type: numeric columns use --method, \
+the others use the mode. The number of filled cells is reported on stderr.
+
+Examples:
+  statsctl impute data.csv --var income --method median
+      Fill missing incomes with the median income
+
+  statsctl impute data.csv --var city --method mode
+      Fill missing cities with the most common city
+
+  statsctl impute data.csv --all > filled.csv
+      Fill every column (numeric: mean, others: mode)")]
+    Impute {
+        /// Path to the CSV/TSV file
+        file: String,
+
+        /// Column to fill
+        #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+        var: Option<String>,
+
+        /// Fill value: mean, median, mode
+        #[arg(long, default_value = "mean")]
+        method: String,
+
+        /// Fill every column, choosing the method by type
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Drop columns or rows with too many missing values, as CSV
+    #[command(long_about = "\
+Remove columns and/or rows whose fraction of missing values exceeds a threshold \
+and write the reduced dataset to stdout as CSV. Columns are dropped first, then \
+rows are judged on the remaining columns. A threshold of 0 drops anything with \
+at least one missing value. What was dropped is reported on stderr.
+
+Examples:
+  statsctl drop-missing data.csv --col-threshold 0.5
+      Drop columns that are more than half empty
+
+  statsctl drop-missing data.csv --row-threshold 0
+      Keep only complete rows
+
+  statsctl drop-missing data.csv --col-threshold 0.3 --row-threshold 0.2 > clean.csv
+      Drop sparse columns, then rows missing more than 20% of what is left")]
+    DropMissing {
+        /// Path to the CSV/TSV file
+        file: String,
+
+        /// Drop columns whose missing fraction exceeds this value (0 to 1)
+        #[arg(long, value_name = "FRACTION")]
+        col_threshold: Option<f64>,
+
+        /// Drop rows whose missing fraction exceeds this value (0 to 1)
+        #[arg(long, value_name = "FRACTION")]
+        row_threshold: Option<f64>,
+    },
+
+    /// Remove duplicate rows, as CSV
+    #[command(long_about = "\
+Remove repeated rows and write the remaining ones to stdout as CSV, keeping the \
+first occurrence of each in its original position. With --subset, rows count as \
+duplicates when they match on those columns only. The number of removed rows is \
+reported on stderr.
+
+Examples:
+  statsctl dedupe data.csv > unique.csv
+      Drop rows that repeat every value of an earlier row
+
+  statsctl dedupe data.csv --subset id
+      Keep one row per id
+
+  statsctl dedupe joined.csv --subset name,date | statsctl summary --stdin
+      Summarize after removing repeated name/date pairs")]
+    Dedupe {
+        /// Path to the CSV/TSV file
+        file: String,
+
+        /// Comma-separated columns to compare (default: all columns)
+        #[arg(long)]
+        subset: Option<String>,
+    },
+
+    /// Join two files on key columns, as CSV
+    #[command(long_about = "\
+Match the rows of two files on one or more key columns and write the combined \
+rows to stdout as CSV. Keys are compared as trimmed text, so 7 and \" 7\" match, \
+and rows with a missing key never match. The result has every column of the \
+first file followed by the non-key columns of the second; names already used by \
+the first file get a _right suffix. A key that appears more than once in the \
+second file repeats the matching rows, with a warning on stderr.
+
+Examples:
+  statsctl join people.csv visits.csv --on id
+      Keep rows whose id appears in both files
+
+  statsctl join people.csv visits.csv --on id --how left
+      Keep every row of people.csv, with empty cells where there is no match
+
+  statsctl join a.csv b.csv --on country,year | statsctl summary --stdin
+      Join on two columns and summarize the result")]
+    Join {
+        /// Left file path
+        file1: String,
+
+        /// Right file path
+        file2: String,
+
+        /// Comma-separated key columns present in both files
+        #[arg(long)]
+        on: String,
+
+        /// Join type: inner, left
+        #[arg(long, default_value = "inner")]
+        how: String,
+    },
+
+    /// Reshape long data into a wide table, as CSV
+    #[command(long_about = "\
+Pivot long-format data wide: one row per level of --index, one column per level \
+of --columns, and each cell the aggregate of the --values that fall into it. \
+Levels keep their order of first appearance. Combinations with no values are \
+left empty, so they read as missing downstream. The table is written to stdout \
+as CSV.
+
+Examples:
+  statsctl pivot sales.csv --index month --columns product --values sales
+      Total sales per month and product
+
+  statsctl pivot scores.csv --index student --columns test --values score --agg mean
+      Average score per student and test
+
+  statsctl pivot visits.csv --index city --columns weekday --values id --agg count
+      Number of visits per city and weekday")]
+    Pivot {
+        /// Path to the CSV/TSV file
+        file: String,
+
+        /// Column whose levels become the rows
+        #[arg(long)]
+        index: String,
+
+        /// Column whose levels become the new columns
+        #[arg(long)]
+        columns: String,
+
+        /// Column holding the values to aggregate
+        #[arg(long)]
+        values: String,
+
+        /// Aggregate: sum, mean, median, min, max, count
+        #[arg(long, default_value = "sum")]
+        agg: String,
+    },
+
+    /// Rescale every numeric column, as CSV
+    #[command(long_about = "\
+Rescale all numeric columns at once and write the full dataset to stdout as CSV. \
+minmax maps each column onto [0, 1]; zscore subtracts the mean and divides by the \
+standard deviation. Categorical columns and missing cells are left untouched, and \
+a constant column becomes all zeros. The scaled columns are listed on stderr.
+
+Examples:
+  statsctl normalize data.csv
+      Min-max scale every numeric column
+
+  statsctl normalize data.csv --method zscore > standardized.csv
+      Standardize numeric columns and save the result
+
+  statsctl normalize data.csv | statsctl summary --stdin
+      Check the scaled ranges")]
+    Normalize {
+        /// Path to the CSV/TSV file
+        file: String,
+
+        /// Scaling: minmax, zscore
+        #[arg(long, default_value = "minmax")]
+        method: String,
+    },
+
+    /// Transform one numeric column, as CSV of row number and result
+    #[command(long_about = "\
+Apply a row-ordered transformation to one numeric column and write the result to \
+stdout as CSV with two columns: the 1-based row number and the transformed value. \
+Rows keep their file order.
+
+rolling-mean is a trailing moving average: each row averages itself and the \
+window - 1 rows before it. The first window - 1 rows, and any window that covers a \
+missing cell, are left empty. The window must be between 1 and the number of rows.
+
+cumsum is a running total from the first row. With --na zero (the default) a \
+missing cell adds nothing and its row shows the total so far; with --na skip the \
+row is left empty and the total carries on past it.
+
+rank replaces each value with its 1-based rank in the column, tied values sharing \
+the average of the ranks they span (1, 2.5, 2.5, 4). Missing cells stay empty.
+
+Examples:
+  statsctl transform data.csv --var sales --method rolling-mean --window 7
+      7-row trailing moving average of sales
+
+  statsctl transform ledger.csv --var amount --method cumsum
+      Running total of amount, treating missing amounts as 0
+
+  statsctl transform ledger.csv --var amount --method cumsum --na skip
+      Running total that leaves rows with a missing amount empty
+
+  statsctl transform data.csv --var score --method rank
+      Ranks of score for nonparametric analysis, in the original row order
+
+  statsctl transform prices.csv --var close --method rolling-mean --window 20 > ma20.csv
+      Save a 20-row moving average")]
+    Transform {
+        /// Path to the CSV/TSV file
+        file: String,
+
+        /// Column to transform
+        #[arg(long)]
+        var: String,
+
+        /// Transformation: rolling-mean, cumsum, rank
+        #[arg(long)]
+        method: String,
+
+        /// Rows per window for rolling-mean
+        #[arg(long, value_name = "N")]
+        window: Option<usize>,
+
+        /// Missing cells in cumsum: zero (add nothing, show the total) or skip (leave empty)
+        #[arg(long, value_name = "POLICY")]
+        na: Option<String>,
+    },
+}
+
+#[derive(Args)]
+struct SummaryArgs {
+    /// Path(s) to CSV/TSV files, or a quoted glob like 'data_*.csv', combined into one dataset
+    #[arg(value_name = "FILE")]
+    files: Vec<String>,
+
+    /// Comma-separated column names, 1-based indices or ranges (e.g. 1,3,5-8)
+    #[arg(long)]
+    vars: Option<String>,
+
+    /// Also select columns whose name matches this regular expression, e.g. '^score_'
+    #[arg(long, value_name = "REGEX")]
+    columns_regex: Option<String>,
+
+    /// Include all columns (numeric + categorical)
+    #[arg(long)]
+    all: bool,
+
+    /// Show only the categorical summaries, without the numeric table
+    #[arg(long, conflicts_with_all = ["all", "vars", "sort_by", "transpose", "trim", "means", "spread", "signs", "bootstrap_median", "normality", "winsorize"])]
+    categorical_only: bool,
+
+    /// Break down the missing count of each categorical column by token (NA, empty, null...)
+    #[arg(long, conflicts_with_all = ["stream", "by"])]
+    missing_tokens: bool,
+
+    /// Add entropy, normalized entropy and Gini impurity columns to the categorical table
+    #[arg(long)]
+    diversity: bool,
+
+    /// Estimate unique counts with a fixed-size HyperLogLog sketch per column instead of a table of every value
+    #[arg(long, conflicts_with = "diversity")]
+    approx_cardinality: bool,
+
+    /// Summarize within each level of this categorical column, one table per group
+    #[arg(long, value_name = "COLUMN", conflicts_with_all = ["all", "categorical_only", "stream"])]
+    by: Option<String>,
+
+    /// Sort rows by a statistic: name, count, missing, mean, std, min, q1, median, q3, max, iqr,
+    /// range
+    #[arg(long)]
+    sort_by: Option<String>,
+
+    /// Sort in descending order (with --sort-by)
+    #[arg(long)]
+    desc: bool,
+
+    /// Show statistics as rows and variables as columns
+    #[arg(long)]
+    transpose: bool,
+
+    /// Add IQR (Q3 - Q1) and range (max - min) columns
+    #[arg(long)]
+    spread: bool,
+
+    /// Add columns counting zero and negative values
+    #[arg(long)]
+    signs: bool,
+
+    /// Print whole-number min, quartiles, max, IQR and range without decimals
+    #[arg(long)]
+    round: bool,
+
+    /// Decimal places per column, e.g. "price=2,rate=4"; other columns use --precision
+    #[arg(long, value_name = "MAP")]
+    precision_map: Option<String>,
+
+    /// Add a Jarque–Bera normality test p-value column ("JB p")
+    #[arg(long, conflicts_with = "stream")]
+    normality: bool,
+
+    /// Add a 95% bootstrap confidence interval for the median
+    #[arg(long, conflicts_with = "stream")]
+    bootstrap_median: bool,
+
+    /// Resamples drawn for --bootstrap-median
+    #[arg(long, value_name = "N", default_value_t = 1000, requires = "bootstrap_median")]
+    bootstrap_iterations: usize,
+
+    /// Seed for --bootstrap-median, for reproducible intervals
+    #[arg(long, requires = "bootstrap_median")]
+    seed: Option<u64>,
+
+    /// Add a trimmed mean that drops this fraction of values from each end (e.g. 0.1)
+    #[arg(long, value_name = "PROPORTION", conflicts_with = "stream")]
+    trim: Option<f64>,
+
+    /// Clip values below this percentile and above its complement to those bounds first (e.g. 0.05)
+    #[arg(long, value_name = "PROPORTION", conflicts_with = "stream")]
+    winsorize: Option<f64>,
+
+    /// Add alternative means for positive data: geometric, harmonic (comma-separated)
+    #[arg(long, value_name = "KINDS", conflicts_with = "stream")]
+    means: Option<String>,
+
+    /// Bimodality coefficient above which a column is flagged as possibly multimodal
+    #[arg(long, value_name = "BC", default_value_t = stats::BIMODALITY_THRESHOLD)]
+    bimodal_threshold: f64,
+
+    /// Stdout format: table, or jsonl for one compact JSON object per column
+    #[arg(long, default_value = "table", conflicts_with_all = ["output", "output_dir", "transpose"])]
+    format: String,
+
+    /// Output file path (supports .md, .json, .csv, .html)
+    #[arg(long, short)]
+    output: Option<String>,
+
+    /// Write each table to its own CSV file in this directory (e.g. summary.csv, categorical.csv)
+    #[arg(long, value_name = "DIR", conflicts_with = "output")]
+    output_dir: Option<String>,
+
+    /// Read from stdin
+    #[arg(long)]
+    stdin: bool,
+
+    /// Read rows one at a time instead of loading the file (skips quartiles)
+    #[arg(long)]
+    stream: bool,
+
+    /// Stream the input and estimate Q1, median and Q3 from a random sample of each column
+    #[arg(long, conflicts_with_all = ["missing_tokens", "by", "bootstrap_median", "trim", "means", "normality", "winsorize"])]
+    approx: bool,
+
+    /// Values sampled per column for --approx; smaller inputs get exact quartiles
+    #[arg(long, value_name = "N", default_value_t = stats::DEFAULT_RESERVOIR_SIZE, requires = "approx")]
+    reservoir_size: usize,
+}
+
+#[derive(Args)]
+struct MissingArgs {
+    /// Path to the CSV/TSV file
+    file: String,
+
+    /// Show only columns with missing values
+    #[arg(long)]
+    only_missing: bool,
+
+    /// Show missing data patterns
+    #[arg(long)]
+    patterns: bool,
+
+    /// Number of missing patterns to list (with --patterns)
+    #[arg(long, value_name = "N", default_value_t = 10, requires = "patterns")]
+    top_patterns: usize,
+
+    /// Leave out patterns seen in fewer rows than this (with --patterns)
+    #[arg(long, value_name = "K", default_value_t = 1, requires = "patterns")]
+    min_pattern_count: usize,
+
+    /// Correlate missing-value indicators between columns with missing values
+    #[arg(long, conflicts_with = "stream")]
+    correlations: bool,
+
+    /// Output file path
+    #[arg(long, short)]
+    output: Option<String>,
+
+    /// Read rows one at a time instead of loading the file
+    #[arg(long)]
+    stream: bool,
+}
+
+#[derive(Args)]
+struct CompareArgs {
+    /// First file path
+    file1: String,
+
+    /// Second file path
+    file2: String,
+
+    /// Comma-separated column names, 1-based indices or ranges (e.g. 1,3,5-8)
+    #[arg(long)]
+    vars: Option<String>,
+
+    /// Also select columns whose name matches this regular expression, e.g. '^score_'
+    #[arg(long, value_name = "REGEX")]
+    columns_regex: Option<String>,
+
+    /// One block per variable with quartiles, statistics as rows
+    #[arg(long)]
+    detailed: bool,
+
+    /// Column to draw with --plot
+    #[arg(long, requires = "plot")]
+    var: Option<String>,
+
+    /// Overlay histograms of --var from both files on shared bins instead of the tables
+    #[arg(long, requires = "var")]
+    plot: bool,
+
+    /// Output file path
+    #[arg(long, short)]
+    output: Option<String>,
+}
+
+#[derive(Args)]
+struct CorrelationArgs {
+    /// Path to the CSV/TSV file
+    file: String,
+
+    /// Comma-separated column names, 1-based indices or ranges (e.g. 1,3,5-8)
+    #[arg(long)]
+    vars: Option<String>,
+
+    /// Also select columns whose name matches this regular expression, e.g. '^score_'
+    #[arg(long, value_name = "REGEX")]
+    columns_regex: Option<String>,
+
+    /// Minimum correlation threshold to highlight
+    #[arg(long, default_value = "0.5")]
+    min: f64,
+
+    /// Render the matrix as an ASCII heatmap instead of numbers
+    #[arg(long)]
+    heatmap: bool,
+
+    /// Order columns so strongly correlated ones are adjacent (default: header order)
+    #[arg(long)]
+    cluster: bool,
+
+    /// Cramér's V between categorical columns instead of Pearson's r
+    #[arg(long)]
+    categorical: bool,
+
+    /// Only list high correlations with a p-value at or below this level
+    #[arg(long, value_name = "P")]
+    max_p: Option<f64>,
+
+    /// Partial correlation of the two --vars columns controlling for this column
+    #[arg(long, value_name = "COLUMN")]
+    control: Option<String>,
+
+    /// Rank the other numeric columns by R² against this column
+    #[arg(long, value_name = "COLUMN", conflicts_with = "control")]
+    target: Option<String>,
+
+    /// Mark coefficients based on fewer than --min-n complete pairs with *
+    #[arg(long)]
+    show_n: bool,
+
+    /// Minimum number of complete pairs for --show-n
+    #[arg(long, value_name = "N", default_value_t = 30, requires = "show_n")]
+    min_n: usize,
+
+    /// Output file path
+    #[arg(long, short)]
+    output: Option<String>,
+}
+
+#[derive(Args)]
+struct PlotArgs {
+    /// Path to the CSV/TSV file
+    file: String,
+
+    /// Column name (for histogram, ecdf, boxplot, line, qq)
+    #[arg(long)]
+    var: Option<String>,
+
+    /// Comma-separated column names (for scatter: x,y; splom: 2-6 columns; line: up to 4 series;
+    /// missing: columns to map)
+    #[arg(long)]
+    vars: Option<String>,
+
+    /// Also select columns whose name matches this regular expression, e.g. '^score_'
+    #[arg(long, value_name = "REGEX")]
+    columns_regex: Option<String>,
+
+    /// Plot type: histogram, ecdf, boxplot, scatter, splom, line, qq, missing
+    #[arg(long = "type", default_value = "histogram")]
+    plot_type: String,
+
+    /// Categorical column to split boxplots by (one box per group)
+    #[arg(long)]
+    by: Option<String>,
+
+    /// Number of histogram bins (default: Sturges' rule)
+    #[arg(long)]
+    bins: Option<usize>,
+
+    /// Overlay a kernel density estimate on the histogram
+    #[arg(long)]
+    density: bool,
+
+    /// Bin the histogram on a log10 scale; non-positive values are skipped
+    #[arg(long)]
+    log: bool,
+
+    /// Output file path (.png or .svg renders an image; requires the `image` feature)
+    #[arg(long, short)]
+    output: Option<String>,
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let color = !cli.no_color && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty());
+    if !color {
+        colored::control::set_override(false);
+    }
+    if let Some(tokens) = parse_vars(&cli.na_values) {
+        utils::set_missing_values(&tokens);
+    }
+
+    let opts = display::DisplayOptions {
+        precision: cli.precision,
+        precisions: Default::default(),
+        width: utils::terminal_width(),
+        round: false,
+        style: cli.style,
+        max_columns: cli.max_columns,
+        color,
+    };
+    let read_opts = reader::ReadOptions {
+        delimiter: cli.delimiter,
+        sheet: cli.sheet,
+        skip_rows: cli.skip_rows,
+        comment: cli.comment,
+        no_header: cli.no_header,
+        strict_rows: cli.strict_rows,
+    };
+
+    let result = match cli.command {
+        Commands::Summary(args) => cmd_summary(args, &opts, &read_opts),
+        Commands::Missing(args) => cmd_missing(args, &opts, &read_opts),
+        Commands::Correlation(args) => cmd_correlation(args, &opts, &read_opts),
+        Commands::Overlap { file, vars, output } => {
+            cmd_overlap(&file, vars, output, &opts, &read_opts)
+        }
+        Commands::Plot(args) => cmd_plot(args, &opts, &read_opts),
+        Commands::Crosstab {
+            file,
+            vars,
+            normalize,
+            output,
+        } => cmd_crosstab(&file, &vars, normalize, output, &opts, &read_opts),
+        Commands::Anova {
+            file,
+            var,
+            by,
+            output,
+        } => cmd_anova(&file, &var, &by, output, &opts, &read_opts),
+        Commands::Freq {
+            file,
+            var,
+            top,
+            bins,
+            output,
+        } => cmd_freq(&file, &var, top, bins, output, &opts, &read_opts),
+        Commands::Types {
+            file,
+            show_levels,
+            near_constant,
+            stream,
+        } => cmd_types(&file, show_levels, near_constant, stream, &opts, &read_opts),
+        Commands::Profile { file, output } => cmd_profile(&file, output, &opts, &read_opts),
+        Commands::Keys { file, output } => cmd_keys(&file, output, &opts, &read_opts),
+        Commands::Compare(args) => cmd_compare(args, &opts, &read_opts),
+        Commands::Head { file, n } => cmd_preview(&file, n, false, &opts, &read_opts),
+        Commands::Tail { file, n } => cmd_preview(&file, n, true, &opts, &read_opts),
+        Commands::Sample {
+            file,
+            n,
+            frac,
+            seed,
+        } => cmd_sample(&file, n, frac, seed, &read_opts),
+        Commands::Filter { file, conditions } => cmd_filter(&file, &conditions, &read_opts),
+        Commands::Impute {
+            file,
+            var,
+            method,
+            all,
+        } => cmd_impute(&file, var.as_deref(), &method, all, &read_opts),
+        Commands::DropMissing {
+            file,
+            col_threshold,
+            row_threshold,
+        } => cmd_drop_missing(&file, col_threshold, row_threshold, &read_opts),
+        Commands::Dedupe { file, subset } => cmd_dedupe(&file, subset, &read_opts),
+        Commands::Join {
+            file1,
+            file2,
+            on,
+            how,
+        } => cmd_join(&file1, &file2, &on, &how, &read_opts),
+        Commands::Pivot {
+            file,
+            index,
+            columns,
+            values,
+            agg,
+        } => cmd_pivot(&file, &index, &columns, &values, &agg, &read_opts),
+        Commands::Normalize { file, method } => cmd_normalize(&file, &method, &read_opts),
+        Commands::Transform {
+            file,
+            var,
+            method,
+            window,
+            na,
+        } => cmd_transform(&file, &var, &method, window, na.as_deref(), &read_opts),
+        Commands::Bin {
+            file,
+            var,
+            bins,
+            method,
+        } => cmd_bin(&file, &var, bins, &method, &opts, &read_opts),
+        Commands::Unique {
+            file,
+            var,
+            sort,
+            output,
+        } => cmd_unique(&file, &var, &sort, output, &opts, &read_opts),
+        Commands::Rank { file, var, value } => cmd_rank(&file, &var, value, &opts, &read_opts),
+        Commands::Validate { file, var, output } => {
+            cmd_validate(&file, var, output, &opts, &read_opts)
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {:#}", e);
+        process::exit(1);
+    }
+}
+
+fn load_data(
+    files: &[String],
+    stdin: bool,
+    read_opts: &reader::ReadOptions,
+) -> Result<reader::DataFrame> {
+    if stdin {
+        return reader::read_stdin(read_opts);
+    }
+    match files {
+        [] => bail!("No file specified. Use --stdin to read from stdin."),
+        [path] => reader::read_file_with(path, read_opts),
+        paths => {
+            let frames = paths
+                .iter()
+                .map(|path| reader::read_file_with(path, read_opts))
+                .collect::<Result<Vec<_>>>()?;
+            reader::concat(frames).with_context(|| format!("Cannot combine {}", paths.join(", ")))
+        }
+    }
+}
+
+/// Expand glob patterns among the file arguments, keeping plain paths as given.
+fn expand_files(patterns: &[String]) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    for pattern in patterns {
+        if !pattern.contains(['*', '?', '[']) {
+            files.push(pattern.clone());
+            continue;
+        }
+        let matches = glob::glob(pattern)
+            .with_context(|| format!("Invalid glob pattern '{}'", pattern))?
+            .filter_map(|entry| entry.ok())
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        if matches.is_empty() {
+            bail!("No files match '{}'", pattern);
+        }
+        files.extend(matches);
+    }
+    Ok(files)
+}
+
+/// Gather streaming statistics from a file or stdin without keeping the rows.
+fn load_stream(
+    file: Option<&str>,
+    stdin: bool,
+    reservoir: Option<usize>,
+    sketch: bool,
+    read_opts: &reader::ReadOptions,
+) -> Result<stream::StreamStats> {
+    let mut streamed = match reservoir {
+        Some(size) => stream::StreamStats::with_reservoir(size),
+        None => stream::StreamStats::new(),
+    };
+    if sketch {
+        streamed = streamed.with_sketches();
+    }
+    if stdin {
+        reader::stream_stdin(read_opts, &mut streamed)?;
+    } else {
+        match file {
+            Some(path) => reader::stream_file_with(path, read_opts, &mut streamed)?,
+            None => bail!("No file specified. Use --stdin to read from stdin."),
+        }
+    }
+    Ok(streamed)
+}
+
+fn write_output(
+    report: &report::Report,
+    output: Option<&str>,
+    style: report::TableStyle,
+) -> Result<()> {
+    match output {
+        Some(path) => {
+            // Determine format from extension
+            let format = if path.ends_with(".json") {
+                "json"
+            } else if path.ends_with(".csv") {
+                "csv"
+            } else if path.ends_with(".html") || path.ends_with(".htm") {
+                "html"
+            } else {
                 "md"
             };
-            let exported = display::export_output(content, format);
-            fs::write(path, &exported)
-                .with_context(|| format!("Cannot write to '{}'", path))?;
-            println!("Output written to: {}", path);
-            Ok(())
+            let exported = report::export(report, format);
+            fs::write(path, &exported)
+                .with_context(|| format!("Cannot write to '{}'", path))?;
+            println!("Output written to: {}", path);
+            Ok(())
+        }
+        None => {
+            println!("{}", report.to_terminal(style));
+            Ok(())
+        }
+    }
+}
+
+/// Write each table of `report` to its own CSV file inside `dir`, creating it if needed.
+fn write_output_dir(report: &report::Report, dir: &str, style: report::TableStyle) -> Result<()> {
+    let files = report.csv_files();
+    if files.is_empty() {
+        // Nothing tabular to split up (e.g. "no numeric columns"); show the message instead
+        println!("{}", report.to_terminal(style));
+        return Ok(());
+    }
+    fs::create_dir_all(dir).with_context(|| format!("Cannot create directory '{}'", dir))?;
+    for (name, contents) in files {
+        let path = std::path::Path::new(dir).join(name);
+        fs::write(&path, contents)
+            .with_context(|| format!("Cannot write to '{}'", path.display()))?;
+        println!("Output written to: {}", path.display());
+    }
+    Ok(())
+}
+
+fn parse_vars(vars: &Option<String>) -> Option<Vec<String>> {
+    vars.as_ref().map(|v| {
+        v.split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    })
+}
+
+/// Parse `--vars` and resolve any column indices or ranges against `headers`.
+///
+/// Columns matching `--columns-regex` are added after those, skipping any already
+/// selected, so the two options combine as a union.
+fn resolve_vars(
+    vars: &Option<String>,
+    columns_regex: &Option<String>,
+    headers: &[String],
+) -> Result<Option<Vec<String>>> {
+    let mut selected = parse_vars(vars)
+        .map(|specs| utils::resolve_columns(&specs, headers))
+        .transpose()?;
+    if let Some(pattern) = columns_regex {
+        let columns = selected.get_or_insert_with(Vec::new);
+        for name in utils::match_columns(pattern, headers)? {
+            if !columns.contains(&name) {
+                columns.push(name);
+            }
+        }
+    }
+    Ok(selected)
+}
+
+/// Borrow resolved column names as the `&str` slices the stats functions take.
+fn as_refs(cols: &Option<Vec<String>>) -> Option<Vec<&str>> {
+    cols.as_ref()
+        .map(|cols| cols.iter().map(|s| s.as_str()).collect())
+}
+
+/// Fill in the requested trimmed, geometric and harmonic means of each summarized column.
+///
+/// Warns where trimming leaves no values, and notes the columns whose non-positive
+/// values leave the geometric and harmonic means undefined.
+fn add_optional_means(
+    df: &reader::DataFrame,
+    stats: &mut [stats::DescriptiveStats],
+    trim: Option<f64>,
+    means: &[stats::MeanKind],
+) {
+    let mut non_positive = Vec::new();
+    for s in stats.iter_mut() {
+        let mut values = df.valid_numeric_column(&s.name).unwrap_or_default();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        if let Some(proportion) = trim {
+            // Other NaNs (e.g. from inf and -inf) are not the trimming's doing
+            let n = values.len();
+            if n > 0 && 2 * stats::trim_count(n, proportion) >= n {
+                eprintln!(
+                    "Warning: --trim {} removes every value of '{}'; its trimmed mean is NaN",
+                    proportion, s.name
+                );
+            }
+            s.trimmed_mean = Some(stats::trimmed_mean(&values, proportion));
+        }
+        for &kind in means {
+            let value = kind.compute(&values);
+            match kind {
+                stats::MeanKind::Geometric => s.geometric_mean = Some(value),
+                stats::MeanKind::Harmonic => s.harmonic_mean = Some(value),
+            }
+        }
+        if !means.is_empty() && values.first().is_some_and(|&min| min <= 0.0) {
+            non_positive.push(s.name.clone());
+        }
+    }
+    if !non_positive.is_empty() {
+        eprintln!(
+            "Note: skipped --means for columns with zero or negative values: {}",
+            non_positive.join(", ")
+        );
+    }
+}
+
+fn cmd_summary(
+    args: SummaryArgs,
+    opts: &display::DisplayOptions,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    let SummaryArgs {
+        files,
+        vars,
+        columns_regex,
+        all,
+        categorical_only,
+        missing_tokens,
+        diversity,
+        approx_cardinality,
+        by,
+        sort_by,
+        desc,
+        transpose,
+        spread,
+        signs,
+        round,
+        precision_map,
+        normality,
+        bootstrap_median,
+        bootstrap_iterations,
+        seed,
+        trim,
+        winsorize,
+        means,
+        bimodal_threshold,
+        format,
+        output,
+        output_dir,
+        stdin,
+        stream,
+        approx,
+        reservoir_size,
+    } = args;
+    let precisions = match &precision_map {
+        Some(spec) => parse_precision_map(spec)?,
+        None => HashMap::new(),
+    };
+    let opts = &display::DisplayOptions {
+        round,
+        precisions,
+        ..*opts
+    };
+    // Approximate quartiles come from sampling the stream
+    let stream = stream || approx;
+    let write = |report: &report::Report| match &output_dir {
+        Some(dir) => write_output_dir(report, dir, opts.style),
+        None => write_output(report, output.as_deref(), opts.style),
+    };
+
+    let json_lines = match format.as_str() {
+        "table" => false,
+        "jsonl" => true,
+        other => bail!("Unknown format '{}'. Use: table, jsonl", other),
+    };
+    if !(0.0..=1.0).contains(&bimodal_threshold) {
+        bail!("--bimodal-threshold must be between 0 and 1");
+    }
+    if trim.is_some_and(|p| !(0.0..=1.0).contains(&p)) {
+        bail!("--trim must be between 0 and 1");
+    }
+    if winsorize.is_some_and(|p| !(0.0..0.5).contains(&p)) {
+        bail!("--winsorize must be at least 0 and below 0.5");
+    }
+    if bootstrap_median && bootstrap_iterations == 0 {
+        bail!("--bootstrap-iterations must be at least 1");
+    }
+    if approx && reservoir_size == 0 {
+        bail!("--reservoir-size must be at least 1");
+    }
+    if missing_tokens && !(all || categorical_only) {
+        bail!(
+            "--missing-tokens breaks down categorical summaries; add --all or --categorical-only"
+        );
+    }
+    if diversity && !(all || categorical_only) {
+        bail!("--diversity describes categorical columns; add --all or --categorical-only");
+    }
+    if approx_cardinality && !(all || categorical_only) {
+        bail!(
+            "--approx-cardinality applies to categorical summaries; add --all or --categorical-only"
+        );
+    }
+    if json_lines && by.is_some() {
+        bail!("--format jsonl is not supported with --by");
+    }
+    let means = parse_vars(&means)
+        .unwrap_or_default()
+        .iter()
+        .map(|kind| match stats::MeanKind::parse(kind) {
+            Some(kind) => Ok(kind),
+            None => bail!("Unknown mean '{}'. Use: geometric, harmonic", kind),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let files = expand_files(&files)?;
+    if files.len() > 1 {
+        if stream {
+            // --approx streams too; name whichever flag was given
+            let flag = if approx { "--approx" } else { "--stream" };
+            bail!(
+                "{} reads a single file; drop it to combine several files",
+                flag
+            );
+        }
+        eprintln!("Combining {} files: {}", files.len(), files.join(", "));
+    }
+
+    // Columns whose unique counts come from a sketch rather than exact counts
+    let (mut numeric_stats, cat_summaries, sampled, sketched) = if stream {
+        let file = files.first().map(|f| f.as_str());
+        let reservoir = approx.then_some(reservoir_size);
+        let streamed = load_stream(file, stdin, reservoir, approx_cardinality, read_opts)?;
+        let selected = resolve_vars(&vars, &columns_regex, streamed.column_names())?;
+        let numeric_stats = match as_refs(&selected) {
+            _ if categorical_only => Vec::new(),
+            Some(cols) => streamed.describe_selected(&cols),
+            None => streamed.describe_all(),
+        };
+        let cat_summaries = if all || categorical_only {
+            streamed.categorical_summaries()
+        } else {
+            Vec::new()
+        };
+        let sampled: Vec<String> = streamed
+            .sampled_columns()
+            .into_iter()
+            .filter(|name| numeric_stats.iter().any(|s| s.name == *name))
+            .map(String::from)
+            .collect();
+        let sketched: Vec<String> = streamed
+            .sketched_columns()
+            .into_iter()
+            .filter(|name| cat_summaries.iter().any(|s| s.name == *name))
+            .map(String::from)
+            .collect();
+        (numeric_stats, cat_summaries, sampled, sketched)
+    } else {
+        let mut df = load_data(&files, stdin, read_opts)?;
+        let selected = resolve_vars(&vars, &columns_regex, &df.headers)?;
+        if let Some(proportion) = winsorize {
+            // Bounds come from the whole file; the grouping column is never clipped
+            let columns = selected
+                .clone()
+                .unwrap_or_else(|| types::numeric_columns(&df));
+            for col in columns.iter().filter(|c| by.as_ref() != Some(*c)) {
+                stats::winsorize_column(&mut df, col, proportion);
+            }
+        }
+        let describe = |df: &reader::DataFrame, columns: Option<&[&str]>| {
+            let mut numeric_stats = match columns {
+                _ if categorical_only => Vec::new(),
+                Some(cols) => stats::describe_selected(df, cols),
+                None => stats::describe_all(df),
+            };
+            if trim.is_some() || !means.is_empty() {
+                add_optional_means(df, &mut numeric_stats, trim, &means);
+            }
+            if normality {
+                for s in numeric_stats.iter_mut() {
+                    let values = df.valid_numeric_column(&s.name).unwrap_or_default();
+                    s.jb_p = Some(tests::jarque_bera(&values).1);
+                }
+            }
+            if bootstrap_median {
+                for s in numeric_stats.iter_mut() {
+                    let values = df.valid_numeric_column(&s.name).unwrap_or_default();
+                    s.median_ci = Some(stats::bootstrap_ci(
+                        &values,
+                        stats::median,
+                        bootstrap_iterations,
+                        stats::BOOTSTRAP_LEVEL,
+                        seed,
+                    ));
+                }
+            }
+            numeric_stats
+        };
+        if let Some(by) = by {
+            let Some(groups) = df.group_by(&by) else {
+                bail!("Column '{}' not found", by);
+            };
+            // Columns are chosen on the whole file so every group reports the same ones
+            let columns = selected
+                .clone()
+                .unwrap_or_else(|| types::numeric_columns(&df));
+            let columns: Vec<&str> = columns
+                .iter()
+                .map(|c| c.as_str())
+                .filter(|c| *c != by)
+                .collect();
+            if columns.is_empty() {
+                return write(&report::Report::from_text(
+                    "No numeric columns found in the dataset.",
+                ));
+            }
+            warn_unmatched_precisions(&opts.precisions, &columns);
+
+            let mut result = report::Report::new();
+            let mut json = serde_json::Map::new();
+            for (label, group) in &groups {
+                let mut group_stats = describe(group, Some(&columns));
+                sort_summary(&mut group_stats, sort_by.as_deref(), desc)?;
+                let table = if transpose {
+                    display::format_summary_transposed(
+                        &group_stats,
+                        bimodal_threshold,
+                        spread,
+                        signs,
+                        opts,
+                    )
+                } else {
+                    display::format_summary(&group_stats, bimodal_threshold, spread, signs, opts)
+                };
+                if !result.is_empty() {
+                    result.push_text("\n\n");
+                }
+                let title = format!("{} = {} (n={})", by, label, group.nrows());
+                result.push_table(
+                    table
+                        .with_title(&title)
+                        .with_name(&format!("summary_{}", label)),
+                );
+                if let Some(note) = opts.truncation_note(group_stats.len()) {
+                    result.push_text(&format!("\n{}", note));
+                }
+                if let Some(note) = display::normality_note(&group_stats) {
+                    result.push_text(&format!("\n{}", note));
+                }
+                if let Some(proportion) = winsorize {
+                    result.push_text(&format!("\n{}", display::winsorize_note(proportion)));
+                }
+                json.insert(label.clone(), serde_json::json!(group_stats));
+            }
+            result.set_json(serde_json::Value::Object(json));
+            return write(&result);
+        }
+
+        let numeric_stats = describe(&df, as_refs(&selected).as_deref());
+        let mut cat_summaries = if approx_cardinality {
+            stats::approx_categorical_summaries(&df)
+        } else if all || categorical_only {
+            stats::categorical_summaries(&df)
+        } else {
+            Vec::new()
+        };
+        if missing_tokens {
+            for s in cat_summaries.iter_mut() {
+                s.missing_tokens = stats::missing_tokens(&df, &s.name);
+            }
+        }
+        let sketched = if approx_cardinality {
+            cat_summaries.iter().map(|s| s.name.clone()).collect()
+        } else {
+            Vec::new()
+        };
+        (numeric_stats, cat_summaries, Vec::new(), sketched)
+    };
+
+    if categorical_only && json_lines {
+        print!("{}", report::json_lines(&cat_summaries)?);
+        return Ok(());
+    }
+    let token_table = missing_tokens
+        .then(|| display::format_missing_tokens(&cat_summaries))
+        .filter(|table| !table.rows.is_empty());
+    if categorical_only {
+        let mut result = display::format_categorical_only(&cat_summaries, diversity);
+        if !sketched.is_empty() {
+            let note = display::approx_cardinality_note(&sketched);
+            result.push_text(&format!("\n{}", note));
+        }
+        if let Some(table) = token_table {
+            result.push_text("\n\n");
+            result.push_table(table);
+        }
+        result.set_json(serde_json::json!(cat_summaries));
+        return write(&result);
+    }
+
+    sort_summary(&mut numeric_stats, sort_by.as_deref(), desc)?;
+    let summarized: Vec<&str> = numeric_stats.iter().map(|s| s.name.as_str()).collect();
+    warn_unmatched_precisions(&opts.precisions, &summarized);
+
+    if json_lines {
+        print!("{}", report::json_lines(&numeric_stats)?);
+        print!("{}", report::json_lines(&cat_summaries)?);
+        return Ok(());
+    }
+
+    let mut result = report::Report::new();
+
+    if !numeric_stats.is_empty() {
+        if transpose {
+            result.push_table(display::format_summary_transposed(
+                &numeric_stats,
+                bimodal_threshold,
+                spread,
+                signs,
+                opts,
+            ));
+        } else {
+            result.push_table(display::format_summary(
+                &numeric_stats,
+                bimodal_threshold,
+                spread,
+                signs,
+                opts,
+            ));
+        }
+        if let Some(note) = opts.truncation_note(numeric_stats.len()) {
+            result.push_text(&format!("\n{}", note));
+        }
+        if let Some(note) = display::normality_note(&numeric_stats) {
+            result.push_text(&format!("\n{}", note));
+        }
+        if let Some(proportion) = winsorize {
+            result.push_text(&format!("\n{}", display::winsorize_note(proportion)));
+        }
+        if !sampled.is_empty() {
+            result.push_text(&format!(
+                "\nQ1, median and Q3 are estimated from {} sampled values of: {}",
+                reservoir_size,
+                sampled.join(", ")
+            ));
+        }
+    }
+
+    if !cat_summaries.is_empty() {
+        // Also show categorical summaries
+        result.push_text("\n\n");
+        result.push_table(display::format_categorical(&cat_summaries, diversity));
+        if !sketched.is_empty() {
+            let note = display::approx_cardinality_note(&sketched);
+            result.push_text(&format!("\n{}", note));
+        }
+    }
+    if let Some(table) = token_table {
+        result.push_text("\n\n");
+        result.push_table(table);
+    }
+
+    if result.is_empty() {
+        result.push_text("No numeric columns found in the dataset.");
+    }
+
+    if all {
+        result.set_json(serde_json::json!({
+            "numeric": numeric_stats,
+            "categorical": cat_summaries,
+        }));
+    } else {
+        result.set_json(serde_json::json!(numeric_stats));
+    }
+
+    write(&result)
+}
+
+/// Parse `--precision-map` entries of the form `column=decimals`, separated by commas.
+fn parse_precision_map(spec: &str) -> Result<HashMap<String, usize>> {
+    let mut precisions = HashMap::new();
+    for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let parsed = entry
+            .split_once('=')
+            .and_then(|(col, n)| Some((col.trim(), n.trim().parse::<usize>().ok()?)));
+        match parsed {
+            Some((col, n)) if !col.is_empty() => {
+                precisions.insert(col.to_string(), n);
+            }
+            _ => bail!(
+                "Invalid --precision-map entry '{}'. Use column=decimals, e.g. price=2",
+                entry
+            ),
+        }
+    }
+    Ok(precisions)
+}
+
+/// Warn about `--precision-map` columns that are not among the summarized `columns`.
+fn warn_unmatched_precisions(precisions: &HashMap<String, usize>, columns: &[&str]) {
+    let mut unmatched: Vec<&String> = precisions
+        .keys()
+        .filter(|col| !columns.contains(&col.as_str()))
+        .collect();
+    unmatched.sort();
+    for col in unmatched {
+        eprintln!(
+            "Warning: --precision-map column '{}' is not in the summary; ignoring it",
+            col
+        );
+    }
+}
+
+/// Apply `--sort-by` to a summary, rejecting unknown keys.
+fn sort_summary(
+    stats: &mut [stats::DescriptiveStats],
+    key: Option<&str>,
+    desc: bool,
+) -> Result<()> {
+    if let Some(key) = key {
+        if !stats::sort_stats(stats, key, desc) {
+            bail!(
+                "Unknown sort key '{}'. Use: {}",
+                key,
+                stats::SORT_KEYS.join(", ")
+            );
+        }
+    }
+    Ok(())
+}
+
+fn cmd_missing(
+    args: MissingArgs,
+    opts: &display::DisplayOptions,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    let MissingArgs {
+        file,
+        only_missing: only_missing_flag,
+        patterns,
+        top_patterns,
+        min_pattern_count,
+        correlations,
+        output,
+        stream,
+    } = args;
+
+    if top_patterns == 0 {
+        bail!("--top-patterns must be at least 1");
+    }
+    let limits = missing::PatternLimits {
+        top: top_patterns,
+        min_count: min_pattern_count,
+    };
+    let (infos, pattern_report, indicator_matrix) = if stream {
+        let streamed = load_stream(Some(&file), false, None, false, read_opts)?;
+        (
+            streamed.missing_infos(),
+            streamed.missing_patterns(&limits),
+            None,
+        )
+    } else {
+        let df = reader::read_file_with(&file, read_opts)?;
+        let indicator_matrix = correlations.then(|| correlation::missingness_matrix(&df));
+        (
+            missing::analyze(&df),
+            missing::missing_patterns(&df, &limits),
+            indicator_matrix,
+        )
+    };
+
+    let mut result = report::Report::new();
+
+    let shown: Vec<missing::MissingInfo> = if only_missing_flag {
+        missing::only_missing(&infos).into_iter().cloned().collect()
+    } else {
+        infos
+    };
+    if shown.is_empty() {
+        result.push_text("No missing data found.");
+    } else {
+        result.push_table(display::format_missing(&shown, opts));
+    }
+
+    let mut json = serde_json::json!({ "columns": shown });
+    if patterns {
+        result.append(display::format_missing_patterns(&pattern_report, opts));
+        json["patterns"] = serde_json::json!(pattern_report);
+    } else {
+        // Show summary even without --patterns
+        let total = pattern_report.total_rows;
+        let rows_with_any_missing = pattern_report.rows_with_missing;
+
+        if rows_with_any_missing > 0 && total > 0 {
+            let pct = (rows_with_any_missing as f64 / total as f64) * 100.0;
+            result.push_text(&format!(
+                "\n{:.*}% of observations have at least one missing value",
+                opts.decimals(2),
+                pct
+            ));
+        }
+    }
+
+    if let Some(cm) = indicator_matrix {
+        result.push_text("\n\n");
+        if cm.columns.len() < 2 {
+            result.push_text(
+                "Missingness correlations need at least two columns with missing values.",
+            );
+        } else {
+            // Same cut-off as the default `correlation --min`
+            let high = correlation::high_correlations(&cm, 0.5);
+            result.push_styled(
+                &display::format_correlation(&cm, None, opts),
+                display::correlation_table(&cm, None, opts),
+            );
+            // Exported text keeps plain direction arrows
+            result.push_text(&display::format_high_correlations(
+                &high,
+                0.5,
+                cm.method,
+                &opts.with_color(output.is_none()),
+            ));
+            json["correlations"] = serde_json::json!({
+                "columns": cm.columns,
+                "matrix": cm.matrix,
+                "high_correlations": high,
+            });
+        }
+    }
+
+    result.set_json(json);
+    write_output(&result, output.as_deref(), opts.style)
+}
+
+fn cmd_correlation(
+    args: CorrelationArgs,
+    opts: &display::DisplayOptions,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    let CorrelationArgs {
+        file,
+        vars,
+        columns_regex,
+        min: min_threshold,
+        heatmap,
+        cluster,
+        categorical,
+        max_p,
+        control,
+        target,
+        show_n,
+        min_n,
+        output,
+    } = args;
+
+    if categorical && max_p.is_some() {
+        bail!("--max-p is only available for Pearson correlations");
+    }
+    if categorical && (control.is_some() || target.is_some()) {
+        bail!("--control and --target are only available for Pearson correlations");
+    }
+    if max_p.is_some_and(|p| !(0.0..=1.0).contains(&p)) {
+        bail!("--max-p must be between 0 and 1");
+    }
+
+    let df = reader::read_file_with(&file, read_opts)?;
+    let selected = resolve_vars(&vars, &columns_regex, &df.headers)?;
+    let min_n = show_n.then_some(min_n);
+
+    if let Some(control) = control {
+        let [x, y] = selected.as_deref().unwrap_or_default() else {
+            bail!("--control needs exactly two columns in --vars, e.g. --vars x,y");
+        };
+        let Some(pc) = correlation::partial_correlation_of(&df, x, y, &control) else {
+            bail!(
+                "Columns '{}', '{}' and '{}' must all exist and be numeric",
+                x,
+                y,
+                control
+            );
+        };
+        let mut report = report::Report::new();
+        report.push_text(&display::format_partial_correlation(&pc, opts));
+        report.set_json(serde_json::json!(pc));
+        return write_output(&report, output.as_deref(), opts.style);
+    }
+
+    let col_refs: Option<Vec<&str>> = selected
+        .as_ref()
+        .map(|cols| cols.iter().map(|s| s.as_str()).collect());
+
+    if let Some(target) = target {
+        let Some(results) = correlation::target_correlations(&df, &target, col_refs.as_deref())
+        else {
+            if df.col_index(&target).is_none() {
+                bail!("Column '{}' not found", target);
+            }
+            bail!("Target column '{}' is not numeric", target);
+        };
+        let mut report = report::Report::new();
+        report.push_table(display::format_target_correlations(&target, &results, opts));
+        report.set_json(serde_json::json!({
+            "target": target,
+            "correlations": results,
+        }));
+        return write_output(&report, output.as_deref(), opts.style);
+    }
+
+    let mut cm = if categorical {
+        correlation::cramers_v_matrix(&df, col_refs.as_deref())
+    } else {
+        correlation::correlation_matrix(&df, col_refs.as_deref())
+    };
+    if cluster {
+        cm = correlation::cluster_order(&cm);
+    }
+
+    if cm.columns.is_empty() {
+        if categorical {
+            bail!("No categorical columns found for association analysis.");
+        }
+        bail!("No numeric columns found for correlation analysis.");
+    }
+
+    let matrix = if heatmap {
+        display::format_correlation_heatmap(&cm, opts)
+    } else {
+        display::format_correlation(&cm, min_n, opts)
+    };
+
+    let mut high = correlation::high_correlations(&cm, min_threshold);
+    if let Some(max_p) = max_p {
+        high.retain(|pair| pair.p.is_some_and(|p| p <= max_p));
+    }
+
+    let mut report = report::Report::new();
+    report.push_styled(&matrix, display::correlation_table(&cm, min_n, opts));
+    // Exported text keeps plain direction arrows
+    report.push_text(&display::format_high_correlations(
+        &high,
+        min_threshold,
+        cm.method,
+        &opts.with_color(output.is_none()),
+    ));
+    report.set_json(serde_json::json!({
+        "method": cm.method,
+        "columns": cm.columns,
+        "matrix": cm.matrix,
+        "pairs": cm.pairs,
+        "high_correlations": high,
+    }));
+
+    write_output(&report, output.as_deref(), opts.style)
+}
+
+fn cmd_overlap(
+    file: &str,
+    vars: Option<String>,
+    output: Option<String>,
+    opts: &display::DisplayOptions,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    let df = reader::read_file_with(file, read_opts)?;
+    let selected = resolve_vars(&vars, &None, &df.headers)?;
+    let col_refs = as_refs(&selected);
+
+    let om = correlation::overlap_matrix(&df, col_refs.as_deref());
+    if om.columns.is_empty() {
+        bail!("No numeric columns found for overlap analysis.");
+    }
+
+    let mut report = report::Report::new();
+    report.push_styled(
+        &display::format_overlap(&om, opts),
+        display::overlap_table(&om),
+    );
+    report.set_json(serde_json::json!(om));
+    write_output(&report, output.as_deref(), opts.style)
+}
+
+/// Resolve the single column a plot needs from `--var`, or the first of `--vars`.
+///
+/// Without either, an interactive terminal is offered the numeric columns to pick
+/// from; pipes and scripts fail right away.
+fn plot_column(
+    var: Option<String>,
+    vars: &Option<Vec<String>>,
+    df: &reader::DataFrame,
+) -> Result<String> {
+    if let Some(col) = var.or_else(|| vars.as_ref().and_then(|v| v.first().cloned())) {
+        return Ok(col);
+    }
+    let columns = types::numeric_columns(df);
+    if !utils::is_interactive() || columns.is_empty() {
+        bail!("Please specify a column with --var");
+    }
+    eprintln!("Numeric columns:");
+    utils::prompt_choice("Column to plot", &columns)
+}
+
+fn cmd_plot(
+    args: PlotArgs,
+    opts: &display::DisplayOptions,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    let PlotArgs {
+        file,
+        var,
+        vars,
+        columns_regex,
+        by,
+        plot_type,
+        bins,
+        density,
+        log,
+        output,
+    } = args;
+
+    if bins == Some(0) {
+        bail!("--bins must be at least 1");
+    }
+
+    let df = reader::read_file_with(&file, read_opts)?;
+    let vars = resolve_vars(&vars, &columns_regex, &df.headers)?;
+
+    if let Some(path) = output.as_deref().filter(|p| is_image_path(p)) {
+        if !matches!(plot_type.as_str(), "histogram" | "hist") {
+            bail!("Image export is only supported for histograms (--type histogram)");
+        }
+        let col = plot_column(var, &vars, &df)?;
+        return export_histogram_image(&df, &col, bins, density, log, path);
+    }
+
+    // Size plots to the terminal, but keep exported plots at the fixed default widths
+    let term_width = if output.is_some() {
+        None
+    } else {
+        utils::terminal_width()
+    };
+    let width = |fallback| utils::plot_width(term_width, fallback);
+
+    let result = match plot_type.as_str() {
+        "histogram" | "hist" => {
+            let col = plot_column(var, &vars, &df)?;
+            plot::histogram(&df, &col, width(50), 12, bins, density, log)
+                .with_context(|| format!("Cannot create histogram for column '{}'", col))?
+        }
+        "boxplot" | "box" => {
+            let col = plot_column(var, &vars, &df)?;
+            match by {
+                Some(group) => plot::grouped_boxplot(&df, &col, &group, width(50)).with_context(|| {
+                    format!("Cannot create boxplot for column '{}' by '{}'", col, group)
+                })?,
+                None => plot::boxplot(&df, &col, width(50))
+                    .with_context(|| format!("Cannot create boxplot for column '{}'", col))?,
+            }
+        }
+        "line" | "lineplot" => {
+            // --var draws one series; --vars can overlay several on one scale
+            let cols = match (var, vars) {
+                (Some(col), _) => vec![col],
+                (None, Some(cols)) if !cols.is_empty() => cols,
+                _ => bail!(
+                    "Please specify a column with --var or up to {} with --vars",
+                    plot::MAX_LINE_SERIES
+                ),
+            };
+            if cols.len() > plot::MAX_LINE_SERIES {
+                bail!(
+                    "Line plots draw at most {} columns, got {}",
+                    plot::MAX_LINE_SERIES,
+                    cols.len()
+                );
+            }
+            let cols: Vec<&str> = cols.iter().map(|c| c.as_str()).collect();
+            plot::lineplot(&df, &cols, width(60), 15).with_context(|| {
+                format!(
+                    "Cannot create line plot for column(s) '{}'",
+                    cols.join(", ")
+                )
+            })?
+        }
+        "ecdf" | "cdf" => {
+            let col = plot_column(var, &vars, &df)?;
+            plot::ecdf(&df, &col, width(50), 11)
+                .with_context(|| format!("Cannot create ECDF plot for column '{}'", col))?
+        }
+        "qqplot" | "qq" => {
+            let col = plot_column(var, &vars, &df)?;
+            plot::qqplot(&df, &col, width(50), 15)
+                .with_context(|| format!("Cannot create QQ plot for column '{}'", col))?
+        }
+        "scatter" => {
+            let parts = vars.context("Please specify two columns with --vars x,y")?;
+            if parts.len() < 2 {
+                bail!("Scatter plot requires two columns: --vars x,y");
+            }
+            plot::scatter(&df, &parts[0], &parts[1], width(50), 15)
+                .with_context(|| format!(
+                    "Cannot create scatter plot for columns '{}' and '{}'",
+                    parts[0], parts[1]
+                ))?
+        }
+        "splom" | "pairs" => {
+            let cols = vars.context("Please specify the columns with --vars a,b,c")?;
+            if cols.len() < 2 {
+                bail!("Scatter matrix requires at least two columns: --vars a,b,c");
+            }
+            if cols.len() > plot::SPLOM_MAX_VARS {
+                bail!(
+                    "Scatter matrix supports at most {} columns (got {})",
+                    plot::SPLOM_MAX_VARS,
+                    cols.len()
+                );
+            }
+            for col in &cols {
+                if df.col_index(col).is_none() {
+                    bail!("Column '{}' not found", col);
+                }
+            }
+            plot::splom(&df, &cols, width(72)).context("Cannot create scatter matrix")?
+        }
+        "missing" => {
+            let df = match vars {
+                Some(names) => {
+                    let names: Vec<&str> = names.iter().map(|s| s.as_str()).collect();
+                    for name in &names {
+                        if df.col_index(name).is_none() {
+                            bail!("Column '{}' not found", name);
+                        }
+                    }
+                    df.select_columns(&names)
+                }
+                None => df,
+            };
+            plot::missing_heatmap(&df, width(60), 30)
         }
-        None => {
-            println!("{}", content);
-            Ok(())
+        _ => {
+            bail!(
+                "Unknown plot type '{}'. Use: histogram, ecdf, boxplot, scatter, splom, line, qq, missing",
+                plot_type
+            );
+        }
+    };
+
+    write_output(
+        &report::Report::from_text(&result),
+        output.as_deref(),
+        opts.style,
+    )
+}
+
+/// Returns true if `path` asks for a graphical plot rather than text.
+fn is_image_path(path: &str) -> bool {
+    let lower = path.to_ascii_lowercase();
+    lower.ends_with(".png") || lower.ends_with(".svg")
+}
+
+#[cfg(feature = "image")]
+fn export_histogram_image(
+    df: &reader::DataFrame,
+    col: &str,
+    bins: Option<usize>,
+    density: bool,
+    log: bool,
+    path: &str,
+) -> Result<()> {
+    // Same bin cap as the fixed-width ASCII export, so both outputs agree
+    let data = plot::histogram_data(df, col, 25, bins, density, log)
+        .with_context(|| format!("Cannot create histogram for column '{}'", col))?;
+    match data {
+        plot::HistogramData::Binned(data) => {
+            if data.skipped > 0 {
+                eprintln!(
+                    "Skipped {} non-positive value(s) that have no logarithm",
+                    data.skipped
+                );
+            }
+            chart::histogram(&data, col, path)?
+        }
+        plot::HistogramData::Empty if log => {
+            bail!("{}: No positive values to plot on a log scale", col)
+        }
+        plot::HistogramData::Empty => bail!("{}: No valid numeric data", col),
+        plot::HistogramData::Constant { value, n } => {
+            bail!("{}: all values are {} (n={})", col, value, n)
         }
     }
+    println!("Output written to: {}", path);
+    Ok(())
 }
 
-fn parse_vars(vars: &Option<String>) -> Option<Vec<String>> {
-    vars.as_ref().map(|v| {
-        v.split(',')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect()
-    })
+#[cfg(not(feature = "image"))]
+fn export_histogram_image(
+    _df: &reader::DataFrame,
+    _col: &str,
+    _bins: Option<usize>,
+    _density: bool,
+    _log: bool,
+    path: &str,
+) -> Result<()> {
+    bail!(
+        "Cannot write '{}': image export requires building with --features image",
+        path
+    )
 }
 
-fn cmd_summary(
-    file: Option<String>,
-    vars: Option<String>,
-    all: bool,
+fn cmd_crosstab(
+    file: &str,
+    vars: &str,
+    normalize: Option<String>,
     output: Option<String>,
-    stdin: bool,
+    opts: &display::DisplayOptions,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    let normalize = match normalize.as_deref() {
+        Some(value) => Some(crosstab::Normalize::parse(value).with_context(|| {
+            format!("Unknown normalization '{}'. Use: row, col, all", value)
+        })?),
+        None => None,
+    };
+
+    let df = reader::read_file_with(file, read_opts)?;
+    let parts = resolve_vars(&Some(vars.to_string()), &None, &df.headers)?.unwrap_or_default();
+    if parts.len() != 2 {
+        bail!("Crosstab requires two columns: --vars row,col");
+    }
+    for col in &parts {
+        if df.col_index(col).is_none() {
+            bail!("Column '{}' not found", col);
+        }
+    }
+    let ct = crosstab::crosstab(&df, &parts[0], &parts[1])
+        .context("Cannot build the contingency table")?;
+
+    for (var, dropped, kept) in [
+        (&ct.row_var, ct.dropped_rows, ct.row_levels.len()),
+        (&ct.col_var, ct.dropped_cols, ct.col_levels.len()),
+    ] {
+        if dropped > 0 {
+            eprintln!(
+                "Warning: '{}' has {} levels; showing the {} most frequent",
+                var,
+                kept + dropped,
+                kept
+            );
+        }
+    }
+
+    let mut report = report::Report::new();
+    report.push_table(display::format_crosstab(&ct, normalize, opts));
+    report.set_json(serde_json::json!(ct));
+
+    write_output(&report, output.as_deref(), opts.style)
+}
+
+fn cmd_freq(
+    file: &str,
+    var: &str,
+    top: Option<usize>,
+    bins: Option<usize>,
+    output: Option<String>,
+    opts: &display::DisplayOptions,
+    read_opts: &reader::ReadOptions,
 ) -> Result<()> {
-    let df = load_data(file.as_deref(), stdin)?;
-    let selected = parse_vars(&vars);
+    if bins == Some(0) {
+        bail!("--bins must be at least 1");
+    }
 
-    let numeric_stats = if let Some(ref cols) = selected {
-        let col_refs: Vec<&str> = cols.iter().map(|s| s.as_str()).collect();
-        stats::describe_selected(&df, &col_refs)
-    } else {
-        stats::describe_all(&df)
+    let df = reader::read_file_with(file, read_opts)?;
+    if df.col_index(var).is_none() {
+        bail!("Column '{}' not found", var);
+    }
+
+    let vc = match bins {
+        Some(n) => stats::binned_value_counts(&df, var, n, |lo, hi| {
+            format!(
+                "[{}, {})",
+                display::format_f64(lo, opts),
+                display::format_f64(hi, opts)
+            )
+        }),
+        None => stats::value_counts(&df, var),
+    }
+    .with_context(|| format!("Cannot count values of column '{}'", var))?;
+
+    let mut report = display::format_value_counts(&vc, top, opts);
+    report.set_json(serde_json::json!(vc));
+
+    write_output(&report, output.as_deref(), opts.style)
+}
+
+fn cmd_bin(
+    file: &str,
+    var: &str,
+    bins: usize,
+    method: &str,
+    opts: &display::DisplayOptions,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    let Some(method) = bin::BinMethod::parse(method) else {
+        bail!("Unknown binning method '{}'. Use: width, quantile", method);
     };
+    if bins == 0 {
+        bail!("--bins must be at least 1");
+    }
 
-    let mut result = String::new();
+    let df = reader::read_file_with(file, read_opts)?;
+    let Some(col) = df.col_index(var) else {
+        bail!("Column '{}' not found", var);
+    };
+    let binning = bin::bin_column(&df, var, bins, method)
+        .with_context(|| format!("Cannot bin column '{}'", var))?;
+    if binning.edges.is_empty() {
+        bail!("Column '{}' has no numeric values to bin", var);
+    }
 
-    if !numeric_stats.is_empty() {
-        result.push_str(&display::format_summary(&numeric_stats));
+    eprintln!(
+        "{}",
+        display::format_bins(&binning, opts).render(opts.style)
+    );
+    let empty = binning.empty_bins();
+    if !empty.is_empty() {
+        let labels: Vec<String> = empty.iter().map(|i| (i + 1).to_string()).collect();
+        eprintln!(
+            "Warning: {} empty bin(s): {} (ties at the bin edges)",
+            empty.len(),
+            labels.join(", ")
+        );
     }
 
-    if all {
-        // Also show categorical summaries
-        let type_infos = types::infer_types(&df);
-        let cat_cols: Vec<String> = type_infos
+    let out = reader::DataFrame {
+        headers: vec![var.to_string(), "bin".to_string()],
+        rows: df
+            .rows
             .iter()
-            .filter(|t| t.col_type != types::ColumnType::Numeric)
-            .map(|t| t.name.clone())
-            .collect();
-
-        if !cat_cols.is_empty() {
-            let cat_summaries: Vec<stats::CategoricalSummary> = cat_cols
-                .iter()
-                .filter_map(|col| stats::categorical_summary(&df, col))
-                .collect();
+            .zip(&binning.assignments)
+            .map(|(row, bin)| {
+                let bin = bin.map(|b| (b + 1).to_string()).unwrap_or_default();
+                vec![row[col].clone(), bin]
+            })
+            .collect(),
+    };
+    let indices: Vec<usize> = (0..out.nrows()).collect();
+    reader::write_csv_rows(&out, &indices, std::io::stdout().lock())
+        .context("Cannot write the binned values")
+}
 
-            if !cat_summaries.is_empty() {
-                result.push_str("\n\nCategorical Variables:\n");
-                result.push_str(&display::format_categorical(&cat_summaries));
-            }
+fn cmd_anova(
+    file: &str,
+    var: &str,
+    by: &str,
+    output: Option<String>,
+    opts: &display::DisplayOptions,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    let df = reader::read_file_with(file, read_opts)?;
+    for col in [var, by] {
+        if df.col_index(col).is_none() {
+            bail!("Column '{}' not found", col);
         }
     }
-
-    if result.is_empty() {
-        result = "No numeric columns found in the dataset.".to_string();
+    let anova = tests::one_way_anova(&df, var, by).context("Cannot run ANOVA")?;
+    if anova.groups.len() < 2 {
+        bail!(
+            "ANOVA needs at least two groups with numeric '{}' values in '{}'",
+            var,
+            by
+        );
     }
 
-    write_output(&result, output.as_deref())
+    let mut report = display::format_anova(&anova, opts);
+    report.set_json(serde_json::json!(anova));
+    write_output(&report, output.as_deref(), opts.style)
 }
 
-fn cmd_missing(
+/// Distinct values above which `unique` warns that the listing will be long.
+const LARGE_UNIQUE_LISTING: usize = 10_000;
+
+fn cmd_unique(
     file: &str,
-    only_missing_flag: bool,
-    patterns: bool,
+    var: &str,
+    sort: &str,
     output: Option<String>,
+    opts: &display::DisplayOptions,
+    read_opts: &reader::ReadOptions,
 ) -> Result<()> {
-    let df = reader::read_file(file)?;
-    let infos = missing::analyze(&df);
+    if sort != "value" && sort != "count" {
+        bail!("Unknown sort order '{}'. Use: value, count", sort);
+    }
 
-    let mut result = String::new();
+    let df = reader::read_file_with(file, read_opts)?;
+    let Some(mut vc) = stats::value_counts(&df, var) else {
+        bail!("Column '{}' not found", var);
+    };
+    if sort == "value" {
+        vc.sort_by_value();
+    }
+    if vc.counts.len() > LARGE_UNIQUE_LISTING && output.is_none() {
+        eprintln!(
+            "Warning: '{}' has {} distinct values; consider -o to write them to a file",
+            var,
+            vc.counts.len()
+        );
+    }
 
-    if only_missing_flag {
-        let filtered = missing::only_missing(&infos);
-        if filtered.is_empty() {
-            result.push_str("No missing data found.");
-        } else {
-            let owned: Vec<missing::MissingInfo> = filtered.into_iter().cloned().collect();
-            result.push_str(&display::format_missing(&owned));
+    let mut report = report::Report::new();
+    report.push_table(display::format_unique(&vc));
+    report.set_json(serde_json::json!(vc));
+    write_output(&report, output.as_deref(), opts.style)
+}
+
+fn cmd_rank(
+    file: &str,
+    var: &str,
+    value: f64,
+    opts: &display::DisplayOptions,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    let df = reader::read_file_with(file, read_opts)?;
+    let Some(mut values) = df.valid_numeric_column(var) else {
+        bail!("Column '{}' not found", var);
+    };
+    if values.is_empty() {
+        bail!("Column '{}' has no numeric values", var);
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let rank = stats::percentile_rank(&values, value);
+    let at_or_below = values.partition_point(|&v| v <= value);
+    println!(
+        "{} is at the {:.*} percentile of {} ({} of {} values at or below it)",
+        value,
+        opts.decimals(1),
+        rank,
+        var,
+        at_or_below,
+        values.len()
+    );
+    Ok(())
+}
+
+fn cmd_validate(
+    file: &str,
+    var: Option<String>,
+    output: Option<String>,
+    opts: &display::DisplayOptions,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    let df = reader::read_file_with(file, read_opts)?;
+    let columns = match var {
+        Some(var) => vec![var],
+        None => types::numeric_columns(&df),
+    };
+
+    let mut results = Vec::with_capacity(columns.len());
+    for col in &columns {
+        match validate::validate_column(&df, col) {
+            Some(result) => results.push(result),
+            None => bail!("Column '{}' not found", col),
         }
-    } else {
-        result.push_str(&display::format_missing(&infos));
     }
 
-    if patterns {
-        let pattern_report = missing::missing_patterns(&df);
-        result.push_str(&display::format_missing_patterns(&pattern_report));
+    let mut report = display::format_validation(&results);
+    report.set_json(serde_json::json!(results));
+    write_output(&report, output.as_deref(), opts.style)
+}
+
+fn cmd_types(
+    file: &str,
+    show_levels: bool,
+    near_constant: Option<f64>,
+    stream: bool,
+    opts: &display::DisplayOptions,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    if near_constant.is_some_and(|t| !(0.0..=1.0).contains(&t)) {
+        bail!("--near-constant must be between 0 and 1");
+    }
+
+    let (type_infos, sketched) = if stream {
+        let streamed = load_stream(Some(file), false, None, false, read_opts)?;
+        let sketched: Vec<String> = streamed
+            .sketched_columns()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        (streamed.type_infos(), sketched)
     } else {
-        // Show summary even without --patterns
-        let total = df.nrows();
-        let rows_with_any_missing = df
-            .rows
-            .iter()
-            .filter(|row| row.iter().any(|v| utils::is_missing(v)))
-            .count();
+        let df = reader::read_file_with(file, read_opts)?;
+        (types::infer_types(&df), Vec::new())
+    };
+    let result = display::format_types(&type_infos, show_levels, near_constant, opts);
+    println!("{}", result);
+    if !sketched.is_empty() {
+        println!("{}", display::approx_cardinality_note(&sketched));
+    }
 
-        if rows_with_any_missing > 0 && total > 0 {
-            let pct = (rows_with_any_missing as f64 / total as f64) * 100.0;
-            result.push_str(&format!(
-                "\n{:.2}% of observations have at least one missing value",
-                pct
-            ));
-        }
+    let constant: Vec<&str> = type_infos
+        .iter()
+        .filter(|t| t.is_constant())
+        .map(|t| t.name.as_str())
+        .collect();
+    if !constant.is_empty() {
+        println!("\nConstant columns (zero variance): {}", constant.join(", "));
     }
+    Ok(())
+}
+
+fn cmd_profile(
+    file: &str,
+    output: Option<String>,
+    opts: &display::DisplayOptions,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    let df = reader::read_file_with(file, read_opts)?;
+    let profiles = profile::profile(&df);
 
-    write_output(&result, output.as_deref())
+    let mut report = report::Report::new();
+    report.push_table(display::format_profile(&profiles, df.nrows(), opts));
+    report.set_json(serde_json::json!(profiles));
+    write_output(&report, output.as_deref(), opts.style)
 }
 
-fn cmd_correlation(
+fn cmd_keys(
     file: &str,
-    vars: Option<String>,
-    min_threshold: f64,
     output: Option<String>,
+    opts: &display::DisplayOptions,
+    read_opts: &reader::ReadOptions,
 ) -> Result<()> {
-    let df = reader::read_file(file)?;
-    let selected = parse_vars(&vars);
+    let df = reader::read_file_with(file, read_opts)?;
+    let mut keys = keys::single_keys(&df);
+    let pairs = keys::composite_keys(&df, &keys);
+    keys.extend(pairs);
+
+    let mut report = report::Report::new();
+    report.push_table(display::format_keys(&keys, df.nrows(), opts));
+    report.set_json(serde_json::json!(keys));
+    write_output(&report, output.as_deref(), opts.style)
+}
 
-    let cm = if let Some(ref cols) = selected {
-        let col_refs: Vec<&str> = cols.iter().map(|s| s.as_str()).collect();
-        correlation::correlation_matrix(&df, Some(&col_refs))
+fn cmd_preview(
+    file: &str,
+    n: usize,
+    tail: bool,
+    opts: &display::DisplayOptions,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    let df = reader::read_file_with(file, read_opts)?;
+    let (label, indices) = if tail {
+        ("Last", df.tail_indices(n))
     } else {
-        correlation::correlation_matrix(&df, None)
+        ("First", df.head_indices(n))
     };
 
-    if cm.columns.is_empty() {
-        bail!("No numeric columns found for correlation analysis.");
-    }
+    println!("{} {} of {} rows:", label, indices.len(), df.nrows());
+    println!("{}", display::format_rows(&df, &indices, opts));
+    Ok(())
+}
+
+fn cmd_sample(
+    file: &str,
+    n: Option<usize>,
+    frac: Option<f64>,
+    seed: Option<u64>,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    let df = reader::read_file_with(file, read_opts)?;
+    let size = sample::sample_size(df.nrows(), n, frac)?;
+    let indices = sample::sample_indices(df.nrows(), size, seed);
+
+    reader::write_csv_rows(&df, &indices, std::io::stdout().lock())
+        .context("Cannot write sampled rows")
+}
 
-    let mut result = display::format_correlation(&cm);
+fn cmd_filter(file: &str, conditions: &[String], read_opts: &reader::ReadOptions) -> Result<()> {
+    let predicates = conditions
+        .iter()
+        .map(|c| filter::Predicate::parse(c))
+        .collect::<Result<Vec<_>>>()?;
 
-    let high = correlation::high_correlations(&cm, min_threshold);
-    result.push_str(&display::format_high_correlations(&high, min_threshold));
+    let df = reader::read_file_with(file, read_opts)?;
+    let indices = filter::filter_rows(&df, &predicates)?;
 
-    write_output(&result, output.as_deref())
+    reader::write_csv_rows(&df, &indices, std::io::stdout().lock())
+        .context("Cannot write filtered rows")
 }
 
-fn cmd_plot(
+fn cmd_impute(
     file: &str,
-    var: Option<String>,
-    vars: Option<String>,
-    plot_type: &str,
-    output: Option<String>,
+    var: Option<&str>,
+    method: &str,
+    all: bool,
+    read_opts: &reader::ReadOptions,
 ) -> Result<()> {
-    let df = reader::read_file(file)?;
+    let Some(method) = impute::Method::parse(method) else {
+        bail!(
+            "Unknown imputation method '{}'. Use: mean, median, mode",
+            method
+        );
+    };
 
-    let result = match plot_type {
-        "histogram" | "hist" => {
-            let col = var
-                .or_else(|| vars.as_ref().and_then(|v| v.split(',').next().map(|s| s.trim().to_string())))
-                .context("Please specify a column with --var")?;
-            plot::histogram(&df, &col, 50, 12)
-                .with_context(|| format!("Cannot create histogram for column '{}'", col))?
+    let mut df = reader::read_file_with(file, read_opts)?;
+    let results = if all {
+        impute::impute_all(&mut df, method)?
+    } else {
+        let col = var.context("Please specify a column with --var, or use --all")?;
+        vec![impute::impute_column(&mut df, col, method)?]
+    };
+
+    for r in &results {
+        match &r.value {
+            Some(value) if r.filled > 0 => eprintln!(
+                "Filled {} cells in '{}' with {} {}",
+                r.filled, r.column, r.method, value
+            ),
+            Some(_) => {}
+            None => eprintln!("Warning: '{}' has no values to impute from", r.column),
         }
-        "boxplot" | "box" => {
-            let col = var
-                .or_else(|| vars.as_ref().and_then(|v| v.split(',').next().map(|s| s.trim().to_string())))
-                .context("Please specify a column with --var")?;
-            plot::boxplot(&df, &col, 50)
-                .with_context(|| format!("Cannot create boxplot for column '{}'", col))?
+    }
+    let total: usize = results.iter().map(|r| r.filled).sum();
+    eprintln!("Filled {} cells in total", total);
+
+    let indices: Vec<usize> = (0..df.nrows()).collect();
+    reader::write_csv_rows(&df, &indices, std::io::stdout().lock())
+        .context("Cannot write imputed rows")
+}
+
+fn cmd_drop_missing(
+    file: &str,
+    col_threshold: Option<f64>,
+    row_threshold: Option<f64>,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    if col_threshold.is_none() && row_threshold.is_none() {
+        bail!("Specify --col-threshold and/or --row-threshold");
+    }
+    for threshold in [col_threshold, row_threshold].into_iter().flatten() {
+        if !(0.0..=1.0).contains(&threshold) {
+            bail!("Thresholds must be between 0 and 1");
         }
-        "scatter" => {
-            let cols = vars.context("Please specify two columns with --vars x,y")?;
-            let parts: Vec<&str> = cols.split(',').map(|s| s.trim()).collect();
-            if parts.len() < 2 {
-                bail!("Scatter plot requires two columns: --vars x,y");
-            }
-            plot::scatter(&df, parts[0], parts[1], 50, 15)
-                .with_context(|| format!(
-                    "Cannot create scatter plot for columns '{}' and '{}'",
-                    parts[0], parts[1]
-                ))?
+    }
+
+    let mut df = reader::read_file_with(file, read_opts)?;
+
+    if let Some(threshold) = col_threshold {
+        let dropped = missing::columns_over_threshold(&missing::analyze(&df), threshold);
+        let keep: Vec<String> = df
+            .headers
+            .iter()
+            .filter(|h| !dropped.contains(h))
+            .cloned()
+            .collect();
+        let keep: Vec<&str> = keep.iter().map(|s| s.as_str()).collect();
+        df = df.select_columns(&keep);
+        if dropped.is_empty() {
+            eprintln!("Dropped 0 columns");
+        } else {
+            eprintln!("Dropped {} columns: {}", dropped.len(), dropped.join(", "));
         }
-        _ => {
-            bail!(
-                "Unknown plot type '{}'. Use: histogram, boxplot, scatter",
-                plot_type
-            );
+    }
+
+    let indices = match row_threshold {
+        Some(threshold) => {
+            let keep = missing::rows_within_threshold(&df, threshold);
+            eprintln!("Dropped {} of {} rows", df.nrows() - keep.len(), df.nrows());
+            keep
         }
+        None => (0..df.nrows()).collect(),
     };
 
-    write_output(&result, output.as_deref())
+    reader::write_csv_rows(&df, &indices, std::io::stdout().lock())
+        .context("Cannot write the reduced dataset")
 }
 
-fn cmd_types(file: &str, show_levels: bool) -> Result<()> {
-    let df = reader::read_file(file)?;
-    let type_infos = types::infer_types(&df);
-    let result = display::format_types(&type_infos, show_levels);
-    println!("{}", result);
-    Ok(())
+fn cmd_dedupe(file: &str, subset: Option<String>, read_opts: &reader::ReadOptions) -> Result<()> {
+    let df = reader::read_file_with(file, read_opts)?;
+    let subset = parse_vars(&subset);
+    let indices = dedupe::unique_rows(&df, subset.as_deref())?;
+    eprintln!(
+        "Removed {} duplicate rows of {}",
+        df.nrows() - indices.len(),
+        df.nrows()
+    );
+
+    reader::write_csv_rows(&df, &indices, std::io::stdout().lock())
+        .context("Cannot write the deduplicated dataset")
 }
 
-fn cmd_compare(
+fn cmd_join(
     file1: &str,
     file2: &str,
-    vars: Option<String>,
-    output: Option<String>,
+    on: &str,
+    how: &str,
+    read_opts: &reader::ReadOptions,
 ) -> Result<()> {
-    let df1 = reader::read_file(file1)?;
-    let df2 = reader::read_file(file2)?;
+    let Some(how) = join::JoinKind::parse(how) else {
+        bail!("Unknown join type '{}'. Use: inner, left", how);
+    };
 
-    let selected = parse_vars(&vars);
+    let left = reader::read_file_with(file1, read_opts)?;
+    let right = reader::read_file_with(file2, read_opts)?;
+    let on = parse_vars(&Some(on.to_string())).unwrap_or_default();
+    let joined = join::join(&left, &right, &on, how)?;
 
-    let stats1 = if let Some(ref cols) = selected {
-        let col_refs: Vec<&str> = cols.iter().map(|s| s.as_str()).collect();
-        stats::describe_selected(&df1, &col_refs)
-    } else {
-        stats::describe_all(&df1)
+    if !joined.duplicate_keys.is_empty() {
+        eprintln!(
+            "Warning: {} keys repeat in '{}', rows were duplicated for: {}",
+            joined.duplicate_keys.len(),
+            file2,
+            joined.duplicate_keys.join("; ")
+        );
+    }
+    eprintln!("Wrote {} joined rows", joined.df.nrows());
+
+    let indices: Vec<usize> = (0..joined.df.nrows()).collect();
+    reader::write_csv_rows(&joined.df, &indices, std::io::stdout().lock())
+        .context("Cannot write the joined dataset")
+}
+
+fn cmd_pivot(
+    file: &str,
+    index: &str,
+    columns: &str,
+    values: &str,
+    agg: &str,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    let Some(agg) = pivot::Agg::parse(agg) else {
+        bail!(
+            "Unknown aggregate '{}'. Use: sum, mean, median, min, max, count",
+            agg
+        );
+    };
+
+    let df = reader::read_file_with(file, read_opts)?;
+    let wide = pivot::pivot(&df, index, columns, values, agg)?;
+    let indices: Vec<usize> = (0..wide.nrows()).collect();
+    reader::write_csv_rows(&wide, &indices, std::io::stdout().lock())
+        .context("Cannot write the pivoted table")
+}
+
+fn cmd_normalize(file: &str, method: &str, read_opts: &reader::ReadOptions) -> Result<()> {
+    let Some(method) = normalize::Scaling::parse(method) else {
+        bail!("Unknown scaling method '{}'. Use: minmax, zscore", method);
     };
 
-    let stats2 = if let Some(ref cols) = selected {
-        let col_refs: Vec<&str> = cols.iter().map(|s| s.as_str()).collect();
-        stats::describe_selected(&df2, &col_refs)
+    let mut df = reader::read_file_with(file, read_opts)?;
+    let scaled = normalize::normalize(&mut df, method);
+    if scaled.is_empty() {
+        eprintln!("No numeric columns to scale");
     } else {
-        stats::describe_all(&df2)
+        eprintln!("Scaled {} columns: {}", scaled.len(), scaled.join(", "));
+    }
+
+    let indices: Vec<usize> = (0..df.nrows()).collect();
+    reader::write_csv_rows(&df, &indices, std::io::stdout().lock())
+        .context("Cannot write the scaled dataset")
+}
+
+fn cmd_transform(
+    file: &str,
+    var: &str,
+    method: &str,
+    window: Option<usize>,
+    na: Option<&str>,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    if window.is_some() && method != "rolling-mean" {
+        bail!("--window only applies to --method rolling-mean");
+    }
+    if na.is_some() && method != "cumsum" {
+        bail!("--na only applies to --method cumsum");
+    }
+    let transform = match method {
+        "rolling-mean" => transform::Transform::RollingMean {
+            window: window.context("--window is required for rolling-mean")?,
+        },
+        "cumsum" => {
+            let na = na.unwrap_or("zero");
+            let Some(na) = transform::MissingPolicy::parse(na) else {
+                bail!("Unknown --na policy '{}'. Use: zero, skip", na);
+            };
+            transform::Transform::CumSum { na }
+        }
+        "rank" => transform::Transform::Rank,
+        other => bail!(
+            "Unknown transform method '{}'. Use: rolling-mean, cumsum, rank",
+            other
+        ),
     };
 
+    let df = reader::read_file_with(file, read_opts)?;
+    let values = transform::transform_column(&df, var, transform)?;
+    let out = transform::to_frame(var, transform, &values);
+    let indices: Vec<usize> = (0..out.nrows()).collect();
+    reader::write_csv_rows(&out, &indices, std::io::stdout().lock())
+        .context("Cannot write the transformed column")
+}
+
+fn cmd_compare(
+    args: CompareArgs,
+    opts: &display::DisplayOptions,
+    read_opts: &reader::ReadOptions,
+) -> Result<()> {
+    let CompareArgs {
+        file1,
+        file2,
+        vars,
+        columns_regex,
+        detailed,
+        var,
+        plot: _,
+        output,
+    } = args;
+    let (file1, file2) = (file1.as_str(), file2.as_str());
+    let df1 = reader::read_file_with(file1, read_opts)?;
+    let df2 = reader::read_file_with(file2, read_opts)?;
+
     // Extract filename for labels
     let label1 = std::path::Path::new(file1)
         .file_name()
@@ -557,7 +3198,59 @@ fn cmd_compare(
         .and_then(|n| n.to_str())
         .unwrap_or(file2);
 
-    let mut result = display::format_comparison(&stats1, &stats2, label1, label2);
+    // --var is only accepted together with --plot
+    if let Some(col) = var {
+        let Some(first) = df1.valid_numeric_column(&col) else {
+            bail!("Column '{}' not found in {}", col, label1);
+        };
+        let Some(second) = df2.valid_numeric_column(&col) else {
+            bail!("Column '{}' not found in {}", col, label2);
+        };
+        let term_width = if output.is_some() {
+            None
+        } else {
+            utils::terminal_width()
+        };
+        // Three characters per bin: one bar per file and a gap
+        let max_bins = utils::plot_width(term_width, 50) / 3;
+        let n = first.len() + second.len();
+        let num_bins = ((n as f64).log2().ceil() as usize + 1).max(5).min(max_bins);
+        let chart = match plot::shared_edges(&first, &second, num_bins) {
+            Some(edges) => format!(
+                "{}: {} vs {}\n\n{}",
+                col,
+                label1,
+                label2,
+                plot::overlay_histogram(&first, &second, &edges, (label1, label2), 12)
+            ),
+            None => format!("{}: no spread to plot in either file", col),
+        };
+        return write_output(
+            &report::Report::from_text(&chart),
+            output.as_deref(),
+            opts.style,
+        );
+    }
+
+    let selected = resolve_vars(&vars, &columns_regex, &df1.headers)?;
+
+    // The wide layout only shows counts, means and standard deviations, so it
+    // skips the quartiles
+    let describe = |df: &reader::DataFrame| -> Vec<stats::DescriptiveStats> {
+        let columns = selected.clone().unwrap_or_else(|| types::numeric_columns(df));
+        columns
+            .iter()
+            .filter_map(|col| stats::describe_with(df, col, detailed))
+            .collect()
+    };
+    let stats1 = describe(&df1);
+    let stats2 = describe(&df2);
+
+    let mut result = if detailed {
+        display::format_comparison_detailed(&stats1, &stats2, label1, label2, opts)
+    } else {
+        display::format_comparison(&stats1, &stats2, label1, label2, opts)
+    };
 
     // Also compare missing data
     let missing1 = missing::analyze(&df1);
@@ -582,19 +3275,62 @@ fn cmd_compare(
             };
             builder.push_record([
                 m1.name.clone(),
-                format!("{} ({:.1}%)", m1.missing, m1.pct),
-                format!("{} ({:.1}%)", m2.missing, m2.pct),
+                format!("{} ({:.*}%)", m1.missing, opts.decimals(1), m1.pct),
+                format!("{} ({:.*}%)", m2.missing, opts.decimals(1), m2.pct),
                 diff_str,
             ]);
         }
     }
 
-    result.push_str(
-        &builder
-            .build()
-            .with(tabled::settings::Style::rounded())
-            .to_string(),
-    );
+    result.push_str(&opts.style.draw(builder.build()));
+
+    // Distribution shift between the two files, on the same columns as above
+    let columns = selected.unwrap_or_else(|| {
+        let mut columns = types::numeric_columns(&df1);
+        for col in types::numeric_columns(&df2) {
+            if !columns.contains(&col) {
+                columns.push(col);
+            }
+        }
+        columns
+    });
+    let mut ks_tests = Vec::new();
+    let mut skipped = Vec::new();
+    for col in &columns {
+        match (df1.valid_numeric_column(col), df2.valid_numeric_column(col)) {
+            (Some(a), Some(b)) => {
+                let (a, b) = (tests::ks_sample(a), tests::ks_sample(b));
+                ks_tests.push(tests::ks_two_sample(col, &a, &b));
+            }
+            (Some(_), None) => skipped.push(format!("{}: skipped, missing in {}", col, label2)),
+            _ => skipped.push(format!("{}: skipped, missing in {}", col, label1)),
+        }
+    }
+
+    result.push_str("\n\nDistribution Shift (Kolmogorov–Smirnov):\n");
+    result.push_str(&display::format_distribution_shift(&ks_tests, opts));
+    for note in &skipped {
+        result.push_str(&format!("\n{}", note));
+    }
+
+    write_output(
+        &report::Report::from_text(&result),
+        output.as_deref(),
+        opts.style,
+    )
+}
 
-    write_output(&result, output.as_deref())
+#[cfg(test)]
+mod cli_tests {
+    use super::*;
+
+    #[test]
+    fn test_plot_rejects_zero_bins() {
+        let cli = Cli::try_parse_from(["statsctl", "plot", "x.csv", "--var", "x", "--bins", "0"]);
+        let Commands::Plot(args) = cli.unwrap().command else {
+            panic!("expected the plot command");
+        };
+        let err = cmd_plot(args, &Default::default(), &Default::default()).unwrap_err();
+        assert_eq!(err.to_string(), "--bins must be at least 1");
+    }
 }
@@ -1,8 +1,10 @@
 use crate::reader::DataFrame;
 use crate::utils::is_missing;
+use serde::Serialize;
+use std::collections::HashMap;
 
 /// Missing data info for one column.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 #[allow(dead_code)]
 pub struct MissingInfo {
     pub name: String,
@@ -41,12 +43,51 @@ pub fn only_missing(infos: &[MissingInfo]) -> Vec<&MissingInfo> {
     infos.iter().filter(|info| info.missing > 0).collect()
 }
 
+/// Names of columns whose fraction of missing values exceeds `threshold`.
+///
+/// A threshold of 0 selects every column with at least one missing value.
+pub fn columns_over_threshold(infos: &[MissingInfo], threshold: f64) -> Vec<String> {
+    infos
+        .iter()
+        .filter(|info| info.total > 0 && info.missing as f64 / info.total as f64 > threshold)
+        .map(|info| info.name.clone())
+        .collect()
+}
+
+/// Indices of rows whose fraction of missing cells is at most `threshold`.
+pub fn rows_within_threshold(df: &DataFrame, threshold: f64) -> Vec<usize> {
+    let ncols = df.ncols();
+    (0..df.nrows())
+        .filter(|&i| {
+            let missing = df.rows[i].iter().filter(|v| is_missing(v)).count();
+            ncols == 0 || missing as f64 / ncols as f64 <= threshold
+        })
+        .collect()
+}
+
+/// Which missingness patterns a report lists.
+#[derive(Debug, Clone, Copy)]
+pub struct PatternLimits {
+    /// Most patterns to list, most common first.
+    pub top: usize,
+    /// Patterns seen in fewer rows than this are left out.
+    pub min_count: usize,
+}
+
+impl Default for PatternLimits {
+    fn default() -> Self {
+        PatternLimits {
+            top: 10,
+            min_count: 1,
+        }
+    }
+}
+
 /// Analyze missing data patterns (which rows have missing values in which columns).
-pub fn missing_patterns(df: &DataFrame) -> MissingPatternReport {
+pub fn missing_patterns(df: &DataFrame, limits: &PatternLimits) -> MissingPatternReport {
     let total = df.nrows();
     let mut rows_with_missing = 0usize;
-    let mut pattern_counts: std::collections::HashMap<String, usize> =
-        std::collections::HashMap::new();
+    let mut pattern_counts: HashMap<String, usize> = HashMap::new();
 
     for row in &df.rows {
         let pattern: String = row
@@ -60,20 +101,44 @@ pub fn missing_patterns(df: &DataFrame) -> MissingPatternReport {
         *pattern_counts.entry(pattern).or_insert(0) += 1;
     }
 
-    let mut patterns: Vec<(String, usize)> = pattern_counts.into_iter().collect();
-    patterns.sort_by(|a, b| b.1.cmp(&a.1));
+    pattern_report(
+        &df.headers,
+        total,
+        rows_with_missing,
+        pattern_counts,
+        limits,
+    )
+}
+
+/// Build the pattern report from counts of per-row missingness bit strings
+/// ('1' marks a missing cell), keeping the most common patterns within `limits`.
+///
+/// Ties in count are broken by the bit string so the listing is stable.
+pub fn pattern_report(
+    headers: &[String],
+    total: usize,
+    rows_with_missing: usize,
+    pattern_counts: HashMap<String, usize>,
+    limits: &PatternLimits,
+) -> MissingPatternReport {
+    let mut patterns: Vec<(String, usize)> = pattern_counts
+        .into_iter()
+        .filter(|(p, _)| p.contains('1'))
+        .collect();
+    patterns.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| b.0.cmp(&a.0)));
+    let distinct = patterns.len();
 
     // Convert bit patterns to column name patterns
     let named_patterns: Vec<(Vec<String>, usize)> = patterns
         .iter()
-        .filter(|(p, _)| p.contains('1'))
-        .take(10)
+        .filter(|(_, count)| *count >= limits.min_count)
+        .take(limits.top)
         .map(|(pattern, count)| {
             let cols: Vec<String> = pattern
                 .chars()
                 .enumerate()
                 .filter(|(_, c)| *c == '1')
-                .filter_map(|(i, _)| df.headers.get(i).cloned())
+                .filter_map(|(i, _)| headers.get(i).cloned())
                 .collect();
             (cols, *count)
         })
@@ -87,15 +152,101 @@ pub fn missing_patterns(df: &DataFrame) -> MissingPatternReport {
         } else {
             0.0
         },
+        hidden_patterns: distinct - named_patterns.len(),
         patterns: named_patterns,
     }
 }
 
 /// Report on missing data patterns.
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct MissingPatternReport {
     pub total_rows: usize,
     pub rows_with_missing: usize,
     pub pct_with_missing: f64,
     pub patterns: Vec<(Vec<String>, usize)>,
+    /// Distinct patterns left out by the limits.
+    pub hidden_patterns: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::read_file;
+
+    #[test]
+    fn test_columns_over_threshold() {
+        let df = read_file("tests/data/sample.csv").unwrap();
+        let infos = analyze(&df);
+        assert_eq!(columns_over_threshold(&infos, 0.5), Vec::<String>::new());
+        assert_eq!(columns_over_threshold(&infos, 0.05), vec!["income", "city"]);
+        assert_eq!(
+            columns_over_threshold(&infos, 0.0),
+            vec!["income", "score", "city"]
+        );
+    }
+
+    #[test]
+    fn test_rows_within_threshold() {
+        let df = read_file("tests/data/sample.csv").unwrap();
+        let complete = rows_within_threshold(&df, 0.0);
+        assert_eq!(
+            complete.len(),
+            df.nrows() - missing_patterns(&df, &PatternLimits::default()).rows_with_missing
+        );
+        assert_eq!(rows_within_threshold(&df, 1.0).len(), df.nrows());
+    }
+
+    #[test]
+    fn test_pattern_limits() {
+        // Row i misses column i; the last two rows share a pattern
+        let headers: Vec<String> = (0..12).map(|i| format!("c{}", i)).collect();
+        let mut rows: Vec<Vec<String>> = (0..12)
+            .map(|i| {
+                (0..12)
+                    .map(|j| if i == j { "NA" } else { "1" }.to_string())
+                    .collect()
+            })
+            .collect();
+        rows.push(rows[11].clone());
+        let df = DataFrame { headers, rows };
+
+        let default = missing_patterns(&df, &PatternLimits::default());
+        assert_eq!(default.patterns.len(), 10);
+        assert_eq!(default.hidden_patterns, 2);
+        assert_eq!(default.patterns[0], (vec!["c11".to_string()], 2));
+
+        let wider = PatternLimits {
+            top: 20,
+            ..PatternLimits::default()
+        };
+        let report = missing_patterns(&df, &wider);
+        assert_eq!(report.patterns.len(), 12);
+        assert_eq!(report.hidden_patterns, 0);
+        let hidden: Vec<&(Vec<String>, usize)> = report
+            .patterns
+            .iter()
+            .filter(|p| !default.patterns.contains(p))
+            .collect();
+        assert_eq!(hidden.len(), 2);
+
+        let common = PatternLimits {
+            min_count: 2,
+            ..PatternLimits::default()
+        };
+        let report = missing_patterns(&df, &common);
+        assert_eq!(report.patterns, vec![(vec!["c11".to_string()], 2)]);
+        assert_eq!(report.hidden_patterns, 11);
+    }
+
+    #[test]
+    fn test_pattern_limits_without_missing_data() {
+        let df = DataFrame::from_column("a", &["1", "2"]);
+        let limits = PatternLimits {
+            top: 1,
+            min_count: 5,
+        };
+        let report = missing_patterns(&df, &limits);
+        assert!(report.patterns.is_empty());
+        assert_eq!((report.rows_with_missing, report.hidden_patterns), (0, 0));
+    }
 }
@@ -0,0 +1,102 @@
+use crate::reader::DataFrame;
+use crate::stats::RunningStats;
+use crate::types;
+
+/// How numeric columns are rescaled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scaling {
+    /// Map the column's range onto [0, 1].
+    MinMax,
+    /// Subtract the mean and divide by the sample standard deviation.
+    ZScore,
+}
+
+impl Scaling {
+    /// Parse "minmax" or "zscore".
+    pub fn parse(value: &str) -> Option<Scaling> {
+        match value {
+            "minmax" => Some(Scaling::MinMax),
+            "zscore" => Some(Scaling::ZScore),
+            _ => None,
+        }
+    }
+}
+
+/// Rescale `x` given the column's statistics.
+///
+/// A column without spread maps every value to 0 instead of dividing by zero.
+fn scale(x: f64, stats: &RunningStats, method: Scaling) -> f64 {
+    let (offset, spread) = match method {
+        Scaling::MinMax => (stats.min(), stats.max() - stats.min()),
+        Scaling::ZScore => (stats.mean(), stats.std_dev()),
+    };
+    if spread == 0.0 {
+        0.0
+    } else {
+        (x - offset) / spread
+    }
+}
+
+/// Rescale every numeric column in place and return the names of the columns changed.
+///
+/// Missing and non-numeric cells are left as they are; other columns are untouched.
+pub fn normalize(df: &mut DataFrame, method: Scaling) -> Vec<String> {
+    let columns = types::numeric_columns(df);
+    for col in &columns {
+        let idx = df.col_index(col).expect("numeric column exists");
+        let mut stats = RunningStats::new();
+        for value in df.valid_numeric_column(col).unwrap_or_default() {
+            stats.push(value);
+        }
+        let parsed = df.numeric_column(col).unwrap_or_default();
+        for (row, value) in df.rows.iter_mut().zip(parsed) {
+            if let Some(x) = value {
+                row[idx] = scale(x, &stats, method).to_string();
+            }
+        }
+    }
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::read_file;
+
+    #[test]
+    fn test_minmax_scales_to_unit_range() {
+        let mut df = read_file("tests/data/sample.csv").unwrap();
+        let changed = normalize(&mut df, Scaling::MinMax);
+        assert_eq!(changed, ["id", "age", "income", "score"]);
+        let age = df.valid_numeric_column("age").unwrap();
+        assert_eq!(age.iter().copied().fold(f64::INFINITY, f64::min), 0.0);
+        assert_eq!(age.iter().copied().fold(f64::NEG_INFINITY, f64::max), 1.0);
+        assert_eq!(
+            df.numeric_column("income")
+                .unwrap()
+                .iter()
+                .flatten()
+                .count(),
+            27
+        );
+        assert_eq!(df.column("city").unwrap()[0], "Bogota");
+    }
+
+    #[test]
+    fn test_constant_column_maps_to_zero() {
+        let mut df = DataFrame::from_column("v", &["4", "4", "", "4"]);
+        normalize(&mut df, Scaling::MinMax);
+        assert_eq!(df.column("v").unwrap(), ["0", "0", "", "0"]);
+        normalize(&mut df, Scaling::ZScore);
+        assert_eq!(df.column("v").unwrap(), ["0", "0", "", "0"]);
+    }
+
+    #[test]
+    fn test_zscore_has_zero_mean_unit_std() {
+        let mut df = read_file("tests/data/sample.csv").unwrap();
+        normalize(&mut df, Scaling::ZScore);
+        let score = df.valid_numeric_column("score").unwrap();
+        assert!(crate::stats::mean(&score).abs() < 1e-9);
+        assert!((crate::stats::std_dev(&score) - 1.0).abs() < 1e-9);
+    }
+}
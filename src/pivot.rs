@@ -0,0 +1,177 @@
+use crate::reader::DataFrame;
+use crate::stats::{mean, percentile};
+use crate::types;
+use crate::utils::is_missing;
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+
+/// How the values falling into one cell are combined.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Agg {
+    Sum,
+    Mean,
+    Median,
+    Min,
+    Max,
+    Count,
+}
+
+impl Agg {
+    /// Parse "sum", "mean", "median", "min", "max" or "count".
+    pub fn parse(value: &str) -> Option<Agg> {
+        match value {
+            "sum" => Some(Agg::Sum),
+            "mean" => Some(Agg::Mean),
+            "median" => Some(Agg::Median),
+            "min" => Some(Agg::Min),
+            "max" => Some(Agg::Max),
+            "count" => Some(Agg::Count),
+            _ => None,
+        }
+    }
+
+    /// Combine the values of one cell; `values` is non-empty.
+    fn apply(&self, values: &mut [f64]) -> f64 {
+        match self {
+            Agg::Sum => values.iter().sum(),
+            Agg::Mean => mean(values),
+            Agg::Median => {
+                values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+                percentile(values, 50.0)
+            }
+            Agg::Min => values.iter().copied().fold(f64::INFINITY, f64::min),
+            Agg::Max => values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            Agg::Count => values.len() as f64,
+        }
+    }
+}
+
+/// Position of `value` in `levels`, appending it when new.
+fn level_index(levels: &mut Vec<String>, value: &str) -> usize {
+    match levels.iter().position(|l| l == value) {
+        Some(i) => i,
+        None => {
+            levels.push(value.to_string());
+            levels.len() - 1
+        }
+    }
+}
+
+/// Reshape long data wide: one row per `index` level, one column per `columns`
+/// level, each cell the `agg` of the matching `values`.
+///
+/// Levels keep their order of first appearance. Rows with a missing index or
+/// column value are skipped, as are missing values; cells with nothing to
+/// aggregate are left empty. `NaN` cells only count toward `Agg::Count`.
+pub fn pivot(
+    df: &DataFrame,
+    index: &str,
+    columns: &str,
+    values: &str,
+    agg: Agg,
+) -> Result<DataFrame> {
+    let mut idx = [0; 3];
+    for (slot, col) in idx.iter_mut().zip([index, columns, values]) {
+        let Some(i) = df.col_index(col) else {
+            bail!("Column '{}' not found", col);
+        };
+        *slot = i;
+    }
+    let [index_idx, columns_idx, values_idx] = idx;
+    if agg != Agg::Count && !types::numeric_columns(df).iter().any(|c| c == values) {
+        bail!(
+            "Column '{}' is not numeric; use --agg count to count its values",
+            values
+        );
+    }
+
+    let mut row_levels: Vec<String> = Vec::new();
+    let mut col_levels: Vec<String> = Vec::new();
+    let mut cells: HashMap<(usize, usize), Vec<f64>> = HashMap::new();
+    for row in &df.rows {
+        let (r, c, v) = (
+            row[index_idx].trim(),
+            row[columns_idx].trim(),
+            row[values_idx].trim(),
+        );
+        if is_missing(r) || is_missing(c) {
+            continue;
+        }
+        let i = level_index(&mut row_levels, r);
+        let j = level_index(&mut col_levels, c);
+        let cell = cells.entry((i, j)).or_default();
+        if is_missing(v) {
+            continue;
+        }
+        match v.parse::<f64>() {
+            Ok(x) if !x.is_nan() => cell.push(x),
+            // Count every present value, numeric or not; NaN cells are not aggregated
+            _ if agg == Agg::Count => cell.push(0.0),
+            _ => {}
+        }
+    }
+
+    let mut headers = vec![index.to_string()];
+    headers.extend(col_levels.iter().cloned());
+    let rows = row_levels
+        .iter()
+        .enumerate()
+        .map(|(i, level)| {
+            let mut row = vec![level.clone()];
+            row.extend((0..col_levels.len()).map(|j| match cells.get_mut(&(i, j)) {
+                Some(vals) if !vals.is_empty() => agg.apply(vals).to_string(),
+                _ => String::new(),
+            }));
+            row
+        })
+        .collect();
+
+    Ok(DataFrame { headers, rows })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::read_file;
+
+    #[test]
+    fn test_pivot_sum_to_wide_table() {
+        let df = read_file("tests/data/sales_long.csv").unwrap();
+        let wide = pivot(&df, "month", "product", "sales", Agg::Sum).unwrap();
+        assert_eq!(wide.headers, ["month", "A", "B", "C"]);
+        assert_eq!(
+            wide.rows,
+            [
+                ["Jan", "12", "5", ""],
+                ["Feb", "7", "", "3"],
+                ["Mar", "", "4", ""],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pivot_count_and_errors() {
+        let df = read_file("tests/data/sales_long.csv").unwrap();
+        let wide = pivot(&df, "month", "product", "sales", Agg::Count).unwrap();
+        assert_eq!(wide.rows[0], ["Jan", "2", "1", ""]);
+        assert!(pivot(&df, "month", "product", "nope", Agg::Sum).is_err());
+        assert!(pivot(&df, "month", "sales", "product", Agg::Mean).is_err());
+    }
+
+    #[test]
+    fn test_pivot_median_skips_nan_cells() {
+        let df = DataFrame::from_rows(
+            &["g", "c", "x"],
+            &[
+                ["a", "u", "3"],
+                ["a", "u", "NAN"],
+                ["a", "u", "1"],
+                ["a", "v", "NAN"],
+            ],
+        );
+        let median = pivot(&df, "g", "c", "x", Agg::Median).unwrap();
+        assert_eq!(median.rows, [["a", "2", ""]]);
+        let count = pivot(&df, "g", "c", "x", Agg::Count).unwrap();
+        assert_eq!(count.rows, [["a", "3", "1"]]);
+    }
+}
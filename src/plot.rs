@@ -1,56 +1,186 @@
 use crate::reader::DataFrame;
 use crate::stats;
+use crate::utils::is_missing;
 
-/// Generate an ASCII histogram for a numeric column.
-pub fn histogram(df: &DataFrame, col_name: &str, width: usize, height: usize) -> Option<String> {
-    let mut values = df.valid_numeric_column(col_name)?;
-    if values.is_empty() {
-        return Some(format!("{}: No valid numeric data", col_name));
+/// Upper bound on the drawing width of any plot, however wide the terminal.
+const MAX_PLOT_WIDTH: usize = 160;
+
+/// Binned counts of a numeric column, shared by the ASCII and image histograms.
+#[derive(Debug, Clone)]
+pub struct HistogramBins {
+    pub n: usize,
+    /// Bins are equal-width in log10 of the values; `min` and `bin_width` are log10 too.
+    pub log: bool,
+    /// Non-positive values left out of a log-scale histogram.
+    pub skipped: usize,
+    /// Lower edge of the first bin.
+    pub min: f64,
+    pub bin_width: f64,
+    pub counts: Vec<usize>,
+    /// Kernel density estimate at each bin center, scaled to expected counts per
+    /// bin. `Some(vec![])` when a curve was requested but the data has no spread.
+    pub density: Option<Vec<f64>>,
+    pub mean: f64,
+    pub median: f64,
+    pub std_dev: f64,
+}
+
+impl HistogramBins {
+    /// Lower edge of bin `i` (the upper edge of the last bin for `i == counts.len()`),
+    /// in the column's own units.
+    pub fn edge(&self, i: usize) -> f64 {
+        to_original_units(self.min + i as f64 * self.bin_width, self.log)
     }
+}
 
-    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+/// Undo the log10 transform of a log-scale histogram.
+pub fn to_original_units(x: f64, log: bool) -> f64 {
+    if log {
+        10f64.powf(x)
+    } else {
+        x
+    }
+}
 
-    let n = values.len();
-    let min_val = values[0];
-    let max_val = *values.last().unwrap();
-    let m = stats::mean(&values);
-    let med = stats::percentile(&values, 50.0);
-    let sd = stats::std_dev(&values);
+/// What a histogram of a column can show.
+#[derive(Debug, Clone)]
+pub enum HistogramData {
+    /// The column has no valid numeric values (no positive ones on a log scale).
+    Empty,
+    /// Every value is the same, so there is no range to bin.
+    Constant { value: f64, n: usize },
+    Binned(HistogramBins),
+}
+
+/// Bin a numeric column for a histogram.
+///
+/// When `bins` is `None` the bin count follows Sturges' rule. Either way it is
+/// capped at `max_bins`, and an explicit count is clamped to the number of
+/// distinct values. With `log`, non-positive values are skipped and the rest are
+/// binned on log10; the mean, median and std stay in the column's units.
+/// Returns `None` if the column does not exist.
+pub fn histogram_data(
+    df: &DataFrame,
+    col_name: &str,
+    max_bins: usize,
+    bins: Option<usize>,
+    show_density: bool,
+    log: bool,
+) -> Option<HistogramData> {
+    let mut originals = df.valid_numeric_column(col_name)?;
+    let total = originals.len();
+    if log {
+        originals.retain(|&v| v > 0.0);
+    }
+    let skipped = total - originals.len();
+    if originals.is_empty() {
+        return Some(HistogramData::Empty);
+    }
+
+    originals.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
-    // Number of bins using Sturges' rule
-    let num_bins = if n > 1 {
-        ((n as f64).log2().ceil() as usize + 1).max(5).min(width / 2)
+    let n = originals.len();
+    // A single distinct value has no range to bin; a lone bar would be misleading
+    if originals[n - 1] == originals[0] {
+        return Some(HistogramData::Constant {
+            value: originals[0],
+            n,
+        });
+    }
+    let values: Vec<f64> = if log {
+        originals.iter().map(|v| v.log10()).collect()
     } else {
-        1
+        originals.clone()
     };
+    let min_val = values[0];
 
-    let range = max_val - min_val;
-    let bin_width = if range > 0.0 {
-        range / num_bins as f64
-    } else {
-        1.0
+    let num_bins = match bins {
+        Some(requested) => {
+            let mut distinct = values.clone();
+            distinct.dedup();
+            requested.min(distinct.len()).min(max_bins).max(1)
+        }
+        // Number of bins using Sturges' rule
+        None if n > 1 => ((n as f64).log2().ceil() as usize + 1).max(5).min(max_bins),
+        None => 1,
     };
 
     // Count values per bin
-    let mut bins = vec![0usize; num_bins];
-    for &v in &values {
-        let mut idx = ((v - min_val) / bin_width).floor() as usize;
-        if idx >= num_bins {
-            idx = num_bins - 1;
+    let (bin_width, counts) = stats::bin_counts(&values, num_bins);
+    let curve = show_density.then(|| {
+        density(&values, num_bins)
+            .iter()
+            .map(|d| d * n as f64 * bin_width)
+            .collect()
+    });
+
+    Some(HistogramData::Binned(HistogramBins {
+        n,
+        log,
+        skipped,
+        min: min_val,
+        bin_width,
+        counts,
+        density: curve,
+        mean: stats::mean(&originals),
+        median: stats::percentile(&originals, 50.0),
+        std_dev: stats::std_dev(&originals),
+    }))
+}
+
+/// Generate an ASCII histogram for a numeric column.
+///
+/// Bins come from [`histogram_data`], with at most one bin per two columns of
+/// `width`. With `show_density`, a Gaussian KDE curve is drawn over the bars with `*`.
+/// With `log`, bins are equal-width in log10 but labeled in the column's units.
+pub fn histogram(
+    df: &DataFrame,
+    col_name: &str,
+    width: usize,
+    height: usize,
+    bins: Option<usize>,
+    show_density: bool,
+    log: bool,
+) -> Option<String> {
+    let data = match histogram_data(df, col_name, width / 2, bins, show_density, log)? {
+        HistogramData::Empty if log => {
+            return Some(format!(
+                "{}: No positive values to plot on a log scale",
+                col_name
+            ));
         }
-        bins[idx] += 1;
-    }
+        HistogramData::Empty => return Some(format!("{}: No valid numeric data", col_name)),
+        HistogramData::Constant { value, n } => {
+            return Some(format!("{}: all values are {} (n={})", col_name, value, n));
+        }
+        HistogramData::Binned(data) => data,
+    };
+    let bins = &data.counts;
+    let num_bins = bins.len();
 
     let max_count = *bins.iter().max().unwrap_or(&1);
+    let bar_height = height.min(15);
+
+    // Row of the density curve in each bin
+    let curve_rows: Vec<Option<usize>> = match &data.density {
+        Some(curve) if !curve.is_empty() => curve
+            .iter()
+            .map(|expected| {
+                let row = (expected / max_count as f64 * bar_height as f64).floor() as usize;
+                Some(row.min(bar_height - 1))
+            })
+            .collect(),
+        _ => vec![None; num_bins],
+    };
 
     let mut output = String::new();
+    let scale = if data.log { ", log10 scale" } else { "" };
     output.push_str(&format!(
-        "{}: Distribution (n={})\n\n",
-        col_name, n
+        "{}: Distribution (n={}{})\n\n",
+        col_name, data.n, scale
     ));
 
     // Draw histogram vertically
-    let bar_height = height.min(15);
     for row in (0..bar_height).rev() {
         let threshold = (row as f64 + 0.5) / bar_height as f64 * max_count as f64;
         let label = if row == bar_height - 1 {
@@ -65,8 +195,10 @@ pub fn histogram(df: &DataFrame, col_name: &str, width: usize, height: usize) ->
         output.push_str(&label);
         output.push('|');
 
-        for &count in &bins {
-            if count as f64 >= threshold {
+        for (&count, curve_row) in bins.iter().zip(&curve_rows) {
+            if *curve_row == Some(row) {
+                output.push_str("**");
+            } else if count as f64 >= threshold {
                 output.push_str("██");
             } else if count as f64 >= threshold - (max_count as f64 / bar_height as f64 / 2.0) {
                 output.push_str("▄▄");
@@ -89,8 +221,7 @@ pub fn histogram(df: &DataFrame, col_name: &str, width: usize, height: usize) ->
     let label_step = (num_bins / 5).max(1);
     for i in 0..num_bins {
         if i % label_step == 0 {
-            let val = min_val + i as f64 * bin_width;
-            let label = format_number_short(val);
+            let label = format_number_short(data.edge(i));
             output.push_str(&label);
             // Pad to align
             let pad = 2usize.saturating_sub(label.len().saturating_sub(2));
@@ -106,65 +237,320 @@ pub fn histogram(df: &DataFrame, col_name: &str, width: usize, height: usize) ->
     output.push('\n');
     output.push_str(&format!(
         "Mean: {:.2} | Median: {:.2} | Std: {:.2}",
-        m, med, sd
+        data.mean, data.median, data.std_dev
     ));
+    if data.density.as_ref().is_some_and(|curve| curve.is_empty()) {
+        output.push_str("\nDensity curve skipped: column has no spread");
+    }
+    if data.skipped > 0 {
+        output.push_str(&format!(
+            "\nSkipped {} non-positive value(s) that have no logarithm",
+            data.skipped
+        ));
+    }
 
     Some(output)
 }
 
-/// Generate an ASCII boxplot for a numeric column.
-pub fn boxplot(df: &DataFrame, col_name: &str, width: usize) -> Option<String> {
-    let mut values = df.valid_numeric_column(col_name)?;
-    if values.is_empty() {
-        return Some(format!("{}: No valid numeric data", col_name));
+/// `num_bins + 1` equal-width bin edges spanning the combined range of two samples.
+///
+/// Returns `None` if both samples are empty or every value is the same.
+pub fn shared_edges(first: &[f64], second: &[f64], num_bins: usize) -> Option<Vec<f64>> {
+    let values = first.iter().chain(second);
+    let min_val = values.clone().copied().fold(f64::INFINITY, f64::min);
+    let max_val = values.copied().fold(f64::NEG_INFINITY, f64::max);
+    // Empty samples leave min at infinity and max below it
+    if max_val <= min_val || num_bins == 0 {
+        return None;
     }
+    let bin_width = (max_val - min_val) / num_bins as f64;
+    let mut edges: Vec<f64> = (0..num_bins)
+        .map(|i| min_val + i as f64 * bin_width)
+        .collect();
+    edges.push(max_val);
+    Some(edges)
+}
 
-    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+/// Count `values` into the bins delimited by `edges`; the top edge belongs to the
+/// last bin and values outside the edges are ignored.
+fn count_in_bins(values: &[f64], edges: &[f64]) -> Vec<usize> {
+    let num_bins = edges.len().saturating_sub(1);
+    let mut counts = vec![0usize; num_bins];
+    if num_bins == 0 {
+        return counts;
+    }
+    let (lo, hi) = (edges[0], edges[num_bins]);
+    let bin_width = (hi - lo) / num_bins as f64;
+    for &v in values {
+        if (lo..=hi).contains(&v) {
+            let idx = ((v - lo) / bin_width).floor() as usize;
+            counts[idx.min(num_bins - 1)] += 1;
+        }
+    }
+    counts
+}
 
-    let min_val = values[0];
-    let max_val = *values.last().unwrap();
-    let q1 = stats::percentile(&values, 25.0);
-    let med = stats::percentile(&values, 50.0);
-    let q3 = stats::percentile(&values, 75.0);
-    let iqr = q3 - q1;
-
-    // Whiskers (capped at 1.5 * IQR)
-    let lower_whisker = values
-        .iter()
-        .copied()
-        .find(|&v| v >= q1 - 1.5 * iqr)
-        .unwrap_or(min_val);
-    let upper_whisker = values
+/// ASCII histograms of two samples drawn in one chart on shared bin edges.
+///
+/// Each bin shows the first sample's bar with `█` next to the second's with `▒`.
+/// Bars are each sample's share of its values, so files of different sizes
+/// stay comparable.
+pub fn overlay_histogram(
+    first: &[f64],
+    second: &[f64],
+    edges: &[f64],
+    labels: (&str, &str),
+    height: usize,
+) -> String {
+    let share = |values: &[f64]| -> Vec<f64> {
+        let n = values.len().max(1) as f64;
+        count_in_bins(values, edges)
+            .into_iter()
+            .map(|c| c as f64 / n)
+            .collect()
+    };
+    let (shares1, shares2) = (share(first), share(second));
+    let num_bins = shares1.len();
+    let max_share = shares1
         .iter()
-        .rev()
-        .copied()
-        .find(|&v| v <= q3 + 1.5 * iqr)
-        .unwrap_or(max_val);
+        .chain(&shares2)
+        .fold(0.0_f64, |acc, &s| acc.max(s));
+    let bar_height = height.clamp(1, 15);
 
-    // Outliers
-    let outliers: Vec<f64> = values
-        .iter()
-        .copied()
-        .filter(|&v| v < lower_whisker || v > upper_whisker)
-        .collect();
+    let mut output = format!(
+        "█ {} (n={})   ▒ {} (n={})   bars: share of each file's values\n\n",
+        labels.0,
+        first.len(),
+        labels.1,
+        second.len()
+    );
+
+    for row in (0..bar_height).rev() {
+        let threshold = (row as f64 + 0.5) / bar_height as f64 * max_share;
+        let bar = |share: f64, glyph: char| {
+            if share > 0.0 && share >= threshold {
+                glyph
+            } else {
+                ' '
+            }
+        };
+        let label = if row == bar_height - 1 {
+            format!("{:.0}%", max_share * 100.0)
+        } else if row == 0 {
+            "0%".to_string()
+        } else {
+            String::new()
+        };
+        output.push_str(&format!("{:>4}|", label));
+        for (s1, s2) in shares1.iter().zip(&shares2) {
+            output.push(bar(*s1, '█'));
+            output.push(bar(*s2, '▒'));
+            output.push(' ');
+        }
+        output.push('\n');
+    }
 
+    output.push_str("    └");
+    for _ in 0..num_bins {
+        output.push_str("───");
+    }
+    output.push('\n');
+
+    // Label every few bins with its lower edge
+    let mut axis = "     ".to_string();
+    let label_step = (num_bins / 5).max(1);
+    for (i, edge) in edges.iter().take(num_bins).enumerate() {
+        if i % label_step == 0 {
+            let target = 5 + i * 3;
+            while axis.chars().count() < target {
+                axis.push(' ');
+            }
+            if axis.chars().count() == target {
+                axis.push_str(&format_number_short(*edge));
+            }
+        }
+    }
+    output.push_str(axis.trim_end());
+    output
+}
+
+/// Gaussian kernel density estimate evaluated at `width` evenly spaced points.
+///
+/// The points are the centers of `width` equal cells spanning the data range, so
+/// they line up with histogram bins of the same count. The bandwidth follows
+/// Silverman's rule of thumb. Returns an empty vector when the data has no spread.
+pub fn density(values: &[f64], width: usize) -> Vec<f64> {
+    if values.len() < 2 || width == 0 {
+        return Vec::new();
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let n = sorted.len() as f64;
+    let min_val = sorted[0];
+    let max_val = sorted[sorted.len() - 1];
     let range = max_val - min_val;
-    let plot_width = width.min(60).max(20);
+    let sd = stats::std_dev(&sorted);
+    if range <= 0.0 || sd <= 0.0 {
+        return Vec::new();
+    }
+
+    // Silverman: h = 0.9 * min(sd, IQR / 1.34) * n^(-1/5)
+    let iqr = stats::percentile(&sorted, 75.0) - stats::percentile(&sorted, 25.0);
+    let spread = if iqr > 0.0 { sd.min(iqr / 1.34) } else { sd };
+    let bandwidth = 0.9 * spread * n.powf(-0.2);
+
+    let norm = 1.0 / (n * bandwidth * (2.0 * std::f64::consts::PI).sqrt());
+    let step = range / width as f64;
+    (0..width)
+        .map(|i| {
+            let x = min_val + (i as f64 + 0.5) * step;
+            let sum: f64 = sorted
+                .iter()
+                .map(|v| {
+                    let u = (x - v) / bandwidth;
+                    (-0.5 * u * u).exp()
+                })
+                .sum();
+            sum * norm
+        })
+        .collect()
+}
+
+/// Most outliers a boxplot lists by value; the rest are only counted.
+const MAX_LISTED_OUTLIERS: usize = 5;
+
+/// Five-number summary with Tukey whiskers for a sorted slice.
+#[derive(Debug, Clone)]
+struct BoxStats {
+    min: f64,
+    q1: f64,
+    median: f64,
+    q3: f64,
+    max: f64,
+    lower_whisker: f64,
+    upper_whisker: f64,
+    outliers: Vec<f64>,
+}
+
+impl BoxStats {
+    /// Compute box statistics from sorted, non-empty values.
+    fn from_sorted(values: &[f64]) -> BoxStats {
+        let min = values[0];
+        let max = values[values.len() - 1];
+        let q1 = stats::percentile(values, 25.0);
+        let median = stats::percentile(values, 50.0);
+        let q3 = stats::percentile(values, 75.0);
+        let iqr = q3 - q1;
+
+        // Whiskers (capped at 1.5 * IQR)
+        let lower_whisker = values
+            .iter()
+            .copied()
+            .find(|&v| v >= q1 - 1.5 * iqr)
+            .unwrap_or(min);
+        let upper_whisker = values
+            .iter()
+            .rev()
+            .copied()
+            .find(|&v| v <= q3 + 1.5 * iqr)
+            .unwrap_or(max);
+
+        let outliers: Vec<f64> = values
+            .iter()
+            .copied()
+            .filter(|&v| v < lower_whisker || v > upper_whisker)
+            .collect();
+
+        BoxStats {
+            min,
+            q1,
+            median,
+            q3,
+            max,
+            lower_whisker,
+            upper_whisker,
+            outliers,
+        }
+    }
+
+    /// Up to `n` outliers, furthest from the median first.
+    fn most_extreme_outliers(&self, n: usize) -> Vec<f64> {
+        let mut outliers = self.outliers.clone();
+        outliers.sort_by(|a, b| {
+            (b - self.median)
+                .abs()
+                .partial_cmp(&(a - self.median).abs())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        outliers.truncate(n);
+        outliers
+    }
+}
+
+/// Draw the whisker/box/median line of a boxplot using the given x-scale.
+fn box_line(b: &BoxStats, plot_width: usize, scale: &dyn Fn(f64) -> usize) -> Vec<char> {
+    let lw = scale(b.lower_whisker);
+    let uq1 = scale(b.q1);
+    let um = scale(b.median);
+    let uq3 = scale(b.q3);
+    let uw = scale(b.upper_whisker);
+
+    let mut line = vec![' '; plot_width];
+    // Whisker lines
+    for cell in line.iter_mut().take(uw + 1).skip(lw) {
+        *cell = '─';
+    }
+    // Box
+    for cell in line.iter_mut().take(uq3 + 1).skip(uq1) {
+        *cell = '█';
+    }
+    // Median
+    if um < plot_width {
+        line[um] = '│';
+    }
+    // Whisker ends
+    if lw < plot_width {
+        line[lw] = '├';
+    }
+    if uw < plot_width {
+        line[uw] = '┤';
+    }
+    line
+}
 
-    let scale = |v: f64| -> usize {
+/// Linear mapping from data values onto `0..plot_width` character positions.
+fn x_scale(min_val: f64, max_val: f64, plot_width: usize) -> impl Fn(f64) -> usize {
+    let range = max_val - min_val;
+    move |v: f64| -> usize {
         if range == 0.0 {
             plot_width / 2
         } else {
             ((v - min_val) / range * (plot_width - 1) as f64).round() as usize
         }
-    };
+    }
+}
+
+/// Generate an ASCII boxplot for a numeric column.
+pub fn boxplot(df: &DataFrame, col_name: &str, width: usize) -> Option<String> {
+    let mut values = df.valid_numeric_column(col_name)?;
+    if values.is_empty() {
+        return Some(format!("{}: No valid numeric data", col_name));
+    }
+
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let b = BoxStats::from_sorted(&values);
+    let plot_width = width.clamp(20, MAX_PLOT_WIDTH);
+    let scale = x_scale(b.min, b.max, plot_width);
 
     let mut output = String::new();
     output.push_str(&format!("{}: Boxplot (n={})\n\n", col_name, values.len()));
 
     // Top line with outliers
     let mut line1 = vec![' '; plot_width];
-    for &o in &outliers {
+    for &o in &b.outliers {
         let pos = scale(o);
         if pos < plot_width {
             line1[pos] = 'o';
@@ -175,39 +561,8 @@ pub fn boxplot(df: &DataFrame, col_name: &str, width: usize) -> Option<String> {
     output.push('\n');
 
     // Boxplot line
-    let lw = scale(lower_whisker);
-    let uq1 = scale(q1);
-    let um = scale(med);
-    let uq3 = scale(q3);
-    let uw = scale(upper_whisker);
-
-    let mut line2 = vec![' '; plot_width];
-    // Whisker lines
-    for i in lw..=uw {
-        if i < plot_width {
-            line2[i] = '─';
-        }
-    }
-    // Box
-    for i in uq1..=uq3 {
-        if i < plot_width {
-            line2[i] = '█';
-        }
-    }
-    // Median
-    if um < plot_width {
-        line2[um] = '│';
-    }
-    // Whisker ends
-    if lw < plot_width {
-        line2[lw] = '├';
-    }
-    if uw < plot_width {
-        line2[uw] = '┤';
-    }
-
     output.push_str("  ");
-    output.extend(line2.iter());
+    output.extend(box_line(&b, plot_width, &scale).iter());
     output.push('\n');
 
     // Scale line
@@ -216,31 +571,170 @@ pub fn boxplot(df: &DataFrame, col_name: &str, width: usize) -> Option<String> {
     output.extend(scale_line.iter());
     output.push('\n');
 
-    // Labels
+    // Labels; long numbers can outgrow the plot, so keep at least one space between them
+    let min_label = format_number_short(b.min);
+    let max_label = format_number_short(b.max);
     output.push_str(&format!(
         "  {:<width$}{}\n",
-        format_number_short(min_val),
-        format_number_short(max_val),
-        width = plot_width - format_number_short(max_val).len()
+        min_label,
+        max_label,
+        width = plot_width
+            .saturating_sub(max_label.len())
+            .max(min_label.len() + 1)
     ));
 
     output.push('\n');
     output.push_str(&format!(
         "Min: {:.2} | Q1: {:.2} | Median: {:.2} | Q3: {:.2} | Max: {:.2}",
-        min_val, q1, med, q3, max_val
+        b.min, b.q1, b.median, b.q3, b.max
     ));
 
-    if !outliers.is_empty() {
-        output.push_str(&format!("\nOutliers: {} values", outliers.len()));
+    if !b.outliers.is_empty() {
+        output.push_str(&format!("\nOutliers: {} values", b.outliers.len()));
+        let extreme: Vec<String> = b
+            .most_extreme_outliers(MAX_LISTED_OUTLIERS)
+            .iter()
+            .map(|v| format!("{:.2}", v))
+            .collect();
+        output.push_str(&format!("\nMost extreme: {}", extreme.join(", ")));
+        if b.outliers.len() > extreme.len() {
+            output.push_str(&format!(" ({} of {})", extreme.len(), b.outliers.len()));
+        }
     }
 
     Some(output)
 }
 
-/// Generate an ASCII scatter plot for two numeric columns.
+/// Minimum group size for drawing a full box in grouped boxplots.
+const MIN_BOX_GROUP: usize = 5;
+
+/// Generate one ASCII boxplot per level of a categorical column.
 ///
-/// Uses a pre-computed density map (HashMap) for O(n) point placement instead of
-/// the naive O(n*m) approach of recounting density per cell for every point.
+/// Boxes are stacked vertically on a shared x-scale. Groups with fewer than five
+/// values are drawn as a point range (`·` per value joined by `-`). Rows with a
+/// missing group value are collected under `(missing)`.
+pub fn grouped_boxplot(
+    df: &DataFrame,
+    col_name: &str,
+    by: &str,
+    width: usize,
+) -> Option<String> {
+    let values = df.numeric_column(col_name)?;
+    let groups_col = df.column(by)?;
+
+    let mut groups: std::collections::BTreeMap<String, Vec<f64>> =
+        std::collections::BTreeMap::new();
+    for (value, group) in values.iter().zip(groups_col.iter()) {
+        if let Some(v) = value {
+            let key = if is_missing(group) {
+                "(missing)".to_string()
+            } else {
+                group.trim().to_string()
+            };
+            groups.entry(key).or_default().push(*v);
+        }
+    }
+
+    if groups.is_empty() {
+        return Some(format!("{}: No valid numeric data", col_name));
+    }
+
+    for vals in groups.values_mut() {
+        vals.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    }
+
+    let min_val = groups.values().map(|v| v[0]).fold(f64::INFINITY, f64::min);
+    let max_val = groups
+        .values()
+        .map(|v| v[v.len() - 1])
+        .fold(f64::NEG_INFINITY, f64::max);
+    let plot_width = width.clamp(20, MAX_PLOT_WIDTH);
+    let scale = x_scale(min_val, max_val, plot_width);
+
+    let label_width = groups.keys().map(|k| k.chars().count()).max().unwrap_or(0).min(12);
+    let total: usize = groups.values().map(|v| v.len()).sum();
+
+    let mut output = String::new();
+    output.push_str(&format!(
+        "{} by {}: Boxplots (n={}, {} groups)\n\n",
+        col_name,
+        by,
+        total,
+        groups.len()
+    ));
+
+    let mut summary = String::new();
+    for (name, vals) in &groups {
+        let b = BoxStats::from_sorted(vals);
+        let label: String = name.chars().take(label_width).collect();
+        let line = if vals.len() < MIN_BOX_GROUP {
+            let lo = scale(vals[0]);
+            let hi = scale(vals[vals.len() - 1]);
+            let mut line = vec![' '; plot_width];
+            for cell in line.iter_mut().take(hi + 1).skip(lo) {
+                *cell = '-';
+            }
+            for &v in vals {
+                let pos = scale(v);
+                if pos < plot_width {
+                    line[pos] = '·';
+                }
+            }
+            line
+        } else {
+            let mut line = box_line(&b, plot_width, &scale);
+            for &o in &b.outliers {
+                let pos = scale(o);
+                if pos < plot_width {
+                    line[pos] = 'o';
+                }
+            }
+            line
+        };
+
+        output.push_str(&format!("{:>width$} ", label, width = label_width));
+        output.extend(line.iter());
+        output.push('\n');
+
+        if vals.len() < MIN_BOX_GROUP {
+            summary.push_str(&format!(
+                "{}: n={} (too few for a box) | Min: {:.2} | Max: {:.2}\n",
+                name,
+                vals.len(),
+                b.min,
+                b.max
+            ));
+        } else {
+            summary.push_str(&format!(
+                "{}: n={} | Q1: {:.2} | Median: {:.2} | Q3: {:.2}\n",
+                name,
+                vals.len(),
+                b.q1,
+                b.median,
+                b.q3
+            ));
+        }
+    }
+
+    // Shared scale line and labels
+    output.push_str(&" ".repeat(label_width + 1));
+    output.push_str(&"─".repeat(plot_width));
+    output.push('\n');
+    output.push_str(&format!(
+        "{}{:<width$}{}\n",
+        " ".repeat(label_width + 1),
+        format_number_short(min_val),
+        format_number_short(max_val),
+        width = plot_width.saturating_sub(format_number_short(max_val).len())
+    ));
+
+    output.push('\n');
+    output.push_str(summary.trim_end());
+
+    Some(output)
+}
+
+/// Generate an ASCII scatter plot for two numeric columns.
 pub fn scatter(
     df: &DataFrame,
     x_name: &str,
@@ -248,53 +742,522 @@ pub fn scatter(
     width: usize,
     height: usize,
 ) -> Option<String> {
+    let pairs = complete_pairs(df, x_name, y_name)?;
+
+    if pairs.is_empty() {
+        return Some(format!(
+            "{} vs {}: No complete pairs of data",
+            x_name, y_name
+        ));
+    }
+
+    let title = format!("{} vs {} (n={})", y_name, x_name, pairs.len());
+    Some(render_scatter(&pairs, &title, x_name, width, height, None))
+}
+
+/// Rows where both columns hold a number, as (x, y) pairs.
+fn complete_pairs(df: &DataFrame, x_name: &str, y_name: &str) -> Option<Vec<(f64, f64)>> {
     let x_all = df.numeric_column(x_name)?;
     let y_all = df.numeric_column(y_name)?;
+    Some(
+        x_all
+            .iter()
+            .zip(y_all.iter())
+            .filter_map(|(a, b)| match (a, b) {
+                (Some(x), Some(y)) => Some((*x, *y)),
+                _ => None,
+            })
+            .collect(),
+    )
+}
+
+/// Most columns a scatter plot matrix will show before the panels get too small.
+pub const SPLOM_MAX_VARS: usize = 6;
+
+/// Generate an ASCII scatter plot matrix for several numeric columns.
+///
+/// Each off-diagonal panel is a small scatter of the row's column (y) against the
+/// panel column's (x) over their complete pairs; the diagonal carries the names.
+/// Returns `None` if a column does not exist.
+pub fn splom(df: &DataFrame, columns: &[String], width: usize) -> Option<String> {
+    let k = columns.len();
+    let panel_w = (width.clamp(20, MAX_PLOT_WIDTH).saturating_sub(k + 1) / k.max(1)).max(8);
+    let panel_h = 6;
+
+    let mut panels: Vec<Vec<Vec<String>>> = Vec::with_capacity(k);
+    for y_name in columns {
+        let mut row = Vec::with_capacity(k);
+        for x_name in columns {
+            let lines = if x_name == y_name {
+                df.col_index(x_name)?;
+                label_panel(x_name, panel_w, panel_h)
+            } else {
+                let pairs = complete_pairs(df, x_name, y_name)?;
+                if pairs.is_empty() {
+                    label_panel("n=0", panel_w, panel_h)
+                } else {
+                    scatter_cells(&pairs, panel_w, panel_h)
+                        .into_iter()
+                        .map(|cells| cells.into_iter().collect())
+                        .collect()
+                }
+            };
+            row.push(lines);
+        }
+        panels.push(row);
+    }
+
+    let rule = |left: &str, mid: &str, right: &str| {
+        let segments = vec!["─".repeat(panel_w); k];
+        format!("{}{}{}\n", left, segments.join(mid), right)
+    };
+
+    let mut output = format!(
+        "Scatter matrix of {} (y: row, x: column)\n\n",
+        columns.join(", ")
+    );
+    output.push_str(&rule("┌", "┬", "┐"));
+    for (i, row) in panels.iter().enumerate() {
+        if i > 0 {
+            output.push_str(&rule("├", "┼", "┤"));
+        }
+        for line in 0..panel_h {
+            let cells: Vec<&str> = row.iter().map(|panel| panel[line].as_str()).collect();
+            output.push('│');
+            output.push_str(&cells.join("│"));
+            output.push_str("│\n");
+        }
+    }
+    output.push_str(&rule("└", "┴", "┘"));
+
+    Some(output)
+}
+
+/// A blank panel with `text` centered on its middle line, truncated to fit.
+fn label_panel(text: &str, width: usize, height: usize) -> Vec<String> {
+    let text: String = text.chars().take(width).collect();
+    (0..height)
+        .map(|line| {
+            if line == (height - 1) / 2 {
+                format!("{:^width$}", text, width = width)
+            } else {
+                " ".repeat(width)
+            }
+        })
+        .collect()
+}
+
+/// Generate an ASCII normal QQ plot for a numeric column.
+///
+/// Sample values are standardized and plotted against theoretical standard normal
+/// quantiles, so normally distributed data falls along the `/` reference line y = x.
+pub fn qqplot(df: &DataFrame, col_name: &str, width: usize, height: usize) -> Option<String> {
+    let mut values = df.valid_numeric_column(col_name)?;
+    if values.len() < 3 {
+        return Some(format!(
+            "{}: QQ plot needs at least 3 valid values (found {})",
+            col_name,
+            values.len()
+        ));
+    }
+
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let m = stats::mean(&values);
+    let sd = stats::std_dev(&values);
+    if sd == 0.0 {
+        return Some(format!("{}: All values are identical, QQ plot is undefined", col_name));
+    }
 
-    // Use only complete pairs
-    let pairs: Vec<(f64, f64)> = x_all
+    // Blom plotting positions: p_i = (i - 3/8) / (n + 1/4)
+    let n = values.len() as f64;
+    let pairs: Vec<(f64, f64)> = values
         .iter()
-        .zip(y_all.iter())
-        .filter_map(|(a, b)| match (a, b) {
-            (Some(x), Some(y)) => Some((*x, *y)),
-            _ => None,
+        .enumerate()
+        .map(|(i, v)| {
+            let p = (i as f64 + 1.0 - 0.375) / (n + 0.25);
+            (stats::normal_quantile(p), (v - m) / sd)
         })
         .collect();
 
-    if pairs.is_empty() {
-        return Some(format!(
-            "{} vs {}: No complete pairs of data",
-            x_name, y_name
+    let title = format!(
+        "{}: Normal QQ plot (n={}, standardized sample vs theoretical)",
+        col_name,
+        values.len()
+    );
+    Some(render_scatter(
+        &pairs,
+        &title,
+        "Theoretical quantiles",
+        width,
+        height,
+        Some(|x| x),
+    ))
+}
+
+/// Empirical CDF of sorted values: each distinct value with the proportion of
+/// values at or below it. The last point always has proportion 1.
+pub fn ecdf_points(sorted: &[f64]) -> Vec<(f64, f64)> {
+    let n = sorted.len() as f64;
+    let mut points: Vec<(f64, f64)> = Vec::new();
+    for (i, &v) in sorted.iter().enumerate() {
+        let p = (i + 1) as f64 / n;
+        match points.last_mut() {
+            Some(last) if last.0 == v => last.1 = p,
+            _ => points.push((v, p)),
+        }
+    }
+    points
+}
+
+/// Generate an ASCII empirical CDF (cumulative proportion against value).
+///
+/// The step function is drawn with `─` for flat stretches and `│` for jumps. The
+/// median and 95th percentile are marked with `^` below the x axis.
+pub fn ecdf(df: &DataFrame, col_name: &str, width: usize, height: usize) -> Option<String> {
+    let mut values = df.valid_numeric_column(col_name)?;
+    if values.is_empty() {
+        return Some(format!("{}: No valid numeric data", col_name));
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let n = values.len();
+    let points = ecdf_points(&values);
+    let min_val = values[0];
+    let max_val = values[n - 1];
+    let range = if max_val > min_val {
+        max_val - min_val
+    } else {
+        1.0
+    };
+    let plot_w = width.clamp(20, MAX_PLOT_WIDTH);
+    let plot_h = height.clamp(5, 20);
+
+    // Row of the cumulative proportion at the right edge of each plot column
+    let mut next = 0;
+    let mut proportion = 0.0;
+    let levels: Vec<usize> = (0..plot_w)
+        .map(|col| {
+            let x = if col == plot_w - 1 {
+                f64::INFINITY
+            } else {
+                min_val + (col + 1) as f64 / plot_w as f64 * range
+            };
+            while next < points.len() && points[next].0 <= x {
+                proportion = points[next].1;
+                next += 1;
+            }
+            (proportion * (plot_h - 1) as f64).round() as usize
+        })
+        .collect();
+
+    let mut grid = vec![vec![' '; plot_w]; plot_h];
+    let mut previous = 0;
+    for (col, &level) in levels.iter().enumerate() {
+        for row in grid.iter_mut().take(level).skip(previous + 1) {
+            row[col] = '│';
+        }
+        grid[level][col] = '─';
+        previous = level;
+    }
+
+    let mut output = format!("{}: Empirical CDF (n={})\n\n", col_name, n);
+    for row in (0..plot_h).rev() {
+        if row == plot_h - 1 || row == 0 || row == (plot_h - 1) / 2 {
+            output.push_str(&format!("{:>4.2}|", row as f64 / (plot_h - 1) as f64));
+        } else {
+            output.push_str("    |");
+        }
+        output.extend(grid[row].iter());
+        output.push('\n');
+    }
+
+    output.push_str("    └");
+    output.push_str(&"─".repeat(plot_w));
+    output.push('\n');
+    let min_label = format_number_short(min_val);
+    let max_label = format_number_short(max_val);
+    output.push_str(&format!(
+        "     {:<width$}{}\n",
+        min_label,
+        max_label,
+        width = plot_w.saturating_sub(max_label.len())
+    ));
+
+    let p50 = stats::percentile(&values, 50.0);
+    let p95 = stats::percentile(&values, 95.0);
+    let mut markers = vec![' '; plot_w];
+    for p in [p50, p95] {
+        let col = ((p - min_val) / range * (plot_w - 1) as f64).round() as usize;
+        markers[col.min(plot_w - 1)] = '^';
+    }
+    output.push_str("     ");
+    output.extend(markers.iter());
+    output.push('\n');
+    output.push_str(&format!("^ p50: {:.2} | p95: {:.2}", p50, p95));
+
+    Some(output)
+}
+
+/// Most columns a line plot draws at once, one glyph each from `SERIES_GLYPHS`.
+pub const MAX_LINE_SERIES: usize = 4;
+
+/// Glyphs marking each series when a line plot shows more than one column.
+const SERIES_GLYPHS: [char; MAX_LINE_SERIES] = ['*', '+', 'o', 'x'];
+
+/// Generate an ASCII line plot of numeric columns against their row index.
+///
+/// When there are more rows than plot columns, rows are bucketed and each bucket is
+/// drawn at the mean of its valid values. Missing values (or buckets with no valid
+/// values) leave gaps in the line instead of being interpolated across.
+///
+/// Several columns (up to [`MAX_LINE_SERIES`]) share one y-scale, each drawn with
+/// its own glyph and listed in a legend; each series keeps its own gaps.
+/// Returns `None` if a column does not exist.
+pub fn lineplot(df: &DataFrame, columns: &[&str], width: usize, height: usize) -> Option<String> {
+    let series: Vec<Vec<Option<f64>>> = columns
+        .iter()
+        .map(|col| df.numeric_column(col))
+        .collect::<Option<_>>()?;
+    let valid: Vec<usize> = series.iter().map(|s| s.iter().flatten().count()).collect();
+    if valid.iter().all(|&v| v == 0) {
+        return Some(format!("{}: No valid numeric data", columns.join(", ")));
+    }
+
+    let n = df.nrows();
+    let plot_w = width.clamp(20, MAX_PLOT_WIDTH);
+    let plot_h = height.clamp(8, 20);
+
+    let all_values = || series.iter().flatten().flatten().copied();
+    let y_min = all_values().fold(f64::INFINITY, f64::min);
+    let y_max = all_values().fold(f64::NEG_INFINITY, f64::max);
+    let y_range = if y_max == y_min { 1.0 } else { y_max - y_min };
+    let to_row = |v: f64| (y_max - v) / y_range * (plot_h - 1) as f64;
+
+    let mut grid = vec![vec![' '; plot_w]; plot_h];
+    if let [values] = series.as_slice() {
+        draw_line(&mut grid, &line_samples(values, plot_w), &to_row);
+    } else {
+        // Draw each series on its own grid, then stamp it with the series glyph
+        for (values, glyph) in series.iter().zip(SERIES_GLYPHS) {
+            let mut layer = vec![vec![' '; plot_w]; plot_h];
+            draw_line(&mut layer, &line_samples(values, plot_w), &to_row);
+            for (row, layer_row) in grid.iter_mut().zip(&layer) {
+                for (cell, layer_cell) in row.iter_mut().zip(layer_row) {
+                    if *layer_cell != ' ' {
+                        *cell = glyph;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut output = String::new();
+    if let [col_name] = columns {
+        output.push_str(&format!(
+            "{}: Line plot (n={}, {} missing)\n\n",
+            col_name,
+            n,
+            n - valid[0]
         ));
+    } else {
+        output.push_str(&format!("Line plot: {} (n={})\n\n", columns.join(", "), n));
+    }
+    for (i, row) in grid.iter().enumerate() {
+        let y_val = y_max - (i as f64 / (plot_h - 1) as f64) * y_range;
+        if i == 0 || i == plot_h - 1 || i == plot_h / 2 {
+            output.push_str(&format!("{:>8.1}│", y_val));
+        } else {
+            output.push_str("        │");
+        }
+        output.extend(row.iter());
+        output.push('\n');
+    }
+    output.push_str("        └");
+    output.push_str(&"─".repeat(plot_w));
+    output.push('\n');
+    let last = n.saturating_sub(1).to_string();
+    output.push_str(&format!(
+        "         {:<width$}{}\n",
+        0,
+        last,
+        width = plot_w.saturating_sub(last.len())
+    ));
+    output.push_str(&format!(
+        "         {:^width$}\n",
+        "Row index",
+        width = plot_w
+    ));
+
+    if columns.len() > 1 {
+        output.push('\n');
+        for ((col_name, glyph), valid) in columns.iter().zip(SERIES_GLYPHS).zip(&valid) {
+            output.push_str(&format!(
+                "  {} {} ({} missing)\n",
+                glyph,
+                col_name,
+                n - valid
+            ));
+        }
+        // With one shared scale, a series with a much smaller range looks flat
+        let ranges: Vec<f64> = series
+            .iter()
+            .filter_map(|values| {
+                let lo = values
+                    .iter()
+                    .flatten()
+                    .copied()
+                    .fold(f64::INFINITY, f64::min);
+                let hi = values
+                    .iter()
+                    .flatten()
+                    .copied()
+                    .fold(f64::NEG_INFINITY, f64::max);
+                (hi >= lo).then_some(hi - lo)
+            })
+            .collect();
+        let widest = ranges.iter().copied().fold(0.0, f64::max);
+        let narrowest = ranges.iter().copied().fold(f64::INFINITY, f64::min);
+        if narrowest * 10.0 < widest {
+            output.push_str(
+                "\nNote: the series' ranges differ more than tenfold on this shared y-scale; \
+                 plot them one at a time to see the flatter ones in detail\n",
+            );
+        }
+    }
+
+    Some(output)
+}
+
+/// Place a series along `plot_w` columns: one sample per row, or per bucket of
+/// rows drawn at the bucket's mean when there are more rows than columns.
+fn line_samples(values: &[Option<f64>], plot_w: usize) -> Vec<(usize, Option<f64>)> {
+    let n = values.len();
+    if n <= plot_w {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let col = if n > 1 {
+                    (i as f64 / (n - 1) as f64 * (plot_w - 1) as f64).round() as usize
+                } else {
+                    0
+                };
+                (col, *v)
+            })
+            .collect()
+    } else {
+        (0..plot_w)
+            .map(|col| {
+                let start = col * n / plot_w;
+                let end = ((col + 1) * n / plot_w).max(start + 1);
+                let bucket: Vec<f64> = values[start..end].iter().flatten().copied().collect();
+                let value = if bucket.is_empty() {
+                    None
+                } else {
+                    Some(stats::mean(&bucket))
+                };
+                (col, value)
+            })
+            .collect()
+    }
+}
+
+/// Connect consecutive samples with segments, leaving gaps at missing ones.
+fn draw_line(
+    grid: &mut [Vec<char>],
+    samples: &[(usize, Option<f64>)],
+    to_row: &dyn Fn(f64) -> f64,
+) {
+    let plot_h = grid.len();
+    for (k, &(col, value)) in samples.iter().enumerate() {
+        let Some(v) = value else { continue };
+        let next = samples.get(k + 1).and_then(|&(c, nv)| nv.map(|nv| (c, nv)));
+        match next {
+            Some((next_col, next_v)) => {
+                draw_segment(grid, (col, to_row(v)), (next_col, to_row(next_v)))
+            }
+            None => {
+                let row = to_row(v).round() as usize;
+                let prev_connected = k > 0 && samples[k - 1].1.is_some();
+                if !prev_connected {
+                    // Isolated point between gaps
+                    grid[row.min(plot_h - 1)][col] = '·';
+                } else if grid[row.min(plot_h - 1)][col] == ' ' {
+                    grid[row.min(plot_h - 1)][col] = '-';
+                }
+            }
+        }
+    }
+}
+
+/// Draw a line segment between two (column, fractional row) points.
+///
+/// Each column gets `/`, `\` or `-` depending on the local slope; steep segments
+/// also fill the vertical run within a column so the line stays connected.
+fn draw_segment(grid: &mut [Vec<char>], from: (usize, f64), to: (usize, f64)) {
+    let (c1, r1) = from;
+    let (c2, r2) = to;
+    let max_row = grid.len() - 1;
+    let span = c2.saturating_sub(c1).max(1) as f64;
+    let row_at = |c: usize| r1 + (r2 - r1) * (c - c1) as f64 / span;
+
+    for c in c1..c2.max(c1 + 1) {
+        let start = row_at(c);
+        let end = row_at((c + 1).min(c2.max(c1 + 1)));
+        let glyph = if end - start < -0.5 {
+            '/'
+        } else if end - start > 0.5 {
+            '\\'
+        } else {
+            '-'
+        };
+        let (lo, hi) = if start <= end { (start, end) } else { (end, start) };
+        let first = lo.round() as usize;
+        // Stop short of the next column's starting row unless the segment is flat
+        let last = if glyph == '-' {
+            first
+        } else {
+            (hi.round() as usize).saturating_sub(1).max(first)
+        };
+        for line in grid.iter_mut().take(last.min(max_row) + 1).skip(first) {
+            if c < line.len() {
+                line[c] = glyph;
+            }
+        }
+    }
+
+    // End point of the segment
+    let end_row = (r2.round() as usize).min(max_row);
+    if c2 < grid[end_row].len() && grid[end_row][c2] == ' ' {
+        grid[end_row][c2] = '-';
     }
+}
 
+/// Place points on a `width` x `height` grid spanning their range, with heavier
+/// glyphs where several points share a cell.
+fn scatter_cells(pairs: &[(f64, f64)], width: usize, height: usize) -> Vec<Vec<char>> {
     let x_vals: Vec<f64> = pairs.iter().map(|(x, _)| *x).collect();
     let y_vals: Vec<f64> = pairs.iter().map(|(_, y)| *y).collect();
-
     let x_min = x_vals.iter().copied().fold(f64::INFINITY, f64::min);
     let x_max = x_vals.iter().copied().fold(f64::NEG_INFINITY, f64::max);
     let y_min = y_vals.iter().copied().fold(f64::INFINITY, f64::min);
     let y_max = y_vals.iter().copied().fold(f64::NEG_INFINITY, f64::max);
-
-    let plot_w = width.min(60).max(20);
-    let plot_h = height.min(20).max(8);
-
     let x_range = if x_max == x_min { 1.0 } else { x_max - x_min };
     let y_range = if y_max == y_min { 1.0 } else { y_max - y_min };
 
     // Pre-compute density map in a single O(n) pass
     let mut density: std::collections::HashMap<(usize, usize), usize> =
         std::collections::HashMap::new();
-    for (x, y) in &pairs {
-        let col = ((*x - x_min) / x_range * (plot_w - 1) as f64).round() as usize;
-        let row = ((y_max - *y) / y_range * (plot_h - 1) as f64).round() as usize;
-        let col = col.min(plot_w - 1);
-        let row = row.min(plot_h - 1);
+    for (x, y) in pairs {
+        let col = ((*x - x_min) / x_range * (width - 1) as f64).round() as usize;
+        let row = ((y_max - *y) / y_range * (height - 1) as f64).round() as usize;
+        let col = col.min(width - 1);
+        let row = row.min(height - 1);
         *density.entry((row, col)).or_insert(0) += 1;
     }
 
-    // Build grid from density map
-    let mut grid = vec![vec![' '; plot_w]; plot_h];
+    let mut grid = vec![vec![' '; width]; height];
     for (&(row, col), &count) in &density {
         grid[row][col] = if count > 3 {
             '●'
@@ -304,14 +1267,58 @@ pub fn scatter(
             '·'
         };
     }
+    grid
+}
+
+/// Render (x, y) pairs as a density-map scatter grid with labeled axes.
+///
+/// Uses a pre-computed density map (HashMap) for O(n) point placement. When
+/// `reference` is given, the line y = f(x) is drawn with `/` underneath the points.
+fn render_scatter(
+    pairs: &[(f64, f64)],
+    title: &str,
+    x_label: &str,
+    width: usize,
+    height: usize,
+    reference: Option<fn(f64) -> f64>,
+) -> String {
+    let x_vals: Vec<f64> = pairs.iter().map(|(x, _)| *x).collect();
+    let y_vals: Vec<f64> = pairs.iter().map(|(_, y)| *y).collect();
+
+    let x_min = x_vals.iter().copied().fold(f64::INFINITY, f64::min);
+    let x_max = x_vals.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let y_min = y_vals.iter().copied().fold(f64::INFINITY, f64::min);
+    let y_max = y_vals.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    let plot_w = width.clamp(20, MAX_PLOT_WIDTH);
+    let plot_h = height.clamp(8, 20);
+
+    let x_range = if x_max == x_min { 1.0 } else { x_max - x_min };
+    let y_range = if y_max == y_min { 1.0 } else { y_max - y_min };
+
+    // Points go on top of the optional reference line
+    let mut grid = scatter_cells(pairs, plot_w, plot_h);
+    if let Some(f) = reference {
+        let line: Vec<(usize, usize)> = (0..plot_w)
+            .filter_map(|col| {
+                let y = f(x_min + col as f64 / (plot_w - 1) as f64 * x_range);
+                if y < y_min || y > y_max {
+                    return None;
+                }
+                let row = ((y_max - y) / y_range * (plot_h - 1) as f64).round() as usize;
+                Some((row.min(plot_h - 1), col))
+            })
+            .collect();
+        for (row, col) in line {
+            if grid[row][col] == ' ' {
+                grid[row][col] = '/';
+            }
+        }
+    }
 
     let mut output = String::new();
-    output.push_str(&format!(
-        "{} vs {} (n={})\n\n",
-        y_name,
-        x_name,
-        pairs.len()
-    ));
+    output.push_str(title);
+    output.push_str("\n\n");
 
     for (i, row) in grid.iter().enumerate() {
         let y_val = y_max - (i as f64 / (plot_h - 1) as f64) * y_range;
@@ -335,20 +1342,413 @@ pub fn scatter(
         x_max,
         width = plot_w - format!("{:.1}", x_max).len()
     ));
-    output.push_str(&format!("         {:^width$}\n", x_name, width = plot_w));
+    output.push_str(&format!("         {:^width$}\n", x_label, width = plot_w));
 
-    Some(output)
+    output
+}
+
+/// Generate a missing-data map: one character column per variable, one line per row.
+///
+/// Missing cells are drawn as `█` and present cells as a space. With more rows than
+/// `height`, consecutive rows are grouped into one line each; a line shows `▒` for
+/// a variable that is missing in only some rows of its group. Each variable gets an
+/// equal share of `width`, and its label along the top is truncated to that share.
+pub fn missing_heatmap(df: &DataFrame, width: usize, height: usize) -> String {
+    let nrows = df.nrows();
+    if nrows == 0 || df.ncols() == 0 {
+        return "Missing data map: No data".to_string();
+    }
+
+    let plot_width = width.clamp(20, MAX_PLOT_WIDTH);
+    let ncols = df.ncols().min(plot_width);
+    let cell = (plot_width / ncols).clamp(1, 12);
+    let lines = nrows.min(height.max(1));
+    let row_label_width = nrows.to_string().len();
+
+    let mut output = format!(
+        "Missing data map ({} rows x {} columns{})\n\n",
+        nrows,
+        df.ncols(),
+        if lines < nrows {
+            format!(", ~{:.0} rows per line", nrows as f64 / lines as f64)
+        } else {
+            String::new()
+        }
+    );
+
+    // Column labels, each truncated to its cell (leaving a gap when there is room)
+    output.push_str(&" ".repeat(row_label_width + 1));
+    for header in df.headers.iter().take(ncols) {
+        let label_width = if cell > 1 { cell - 1 } else { 1 };
+        let label: String = header.chars().take(label_width).collect();
+        output.push_str(&format!("{:<width$}", label, width = cell));
+    }
+    output.push('\n');
+
+    for line in 0..lines {
+        let start = line * nrows / lines;
+        let end = ((line + 1) * nrows / lines).max(start + 1);
+        output.push_str(&format!("{:>width$} ", start + 1, width = row_label_width));
+        for col in 0..ncols {
+            let missing = df.rows[start..end]
+                .iter()
+                .filter(|row| is_missing(&row[col]))
+                .count();
+            let glyph = if missing == 0 {
+                ' '
+            } else if missing == end - start {
+                '█'
+            } else {
+                '▒'
+            };
+            let fill = if cell > 1 { cell - 1 } else { 1 };
+            output.push_str(&glyph.to_string().repeat(fill));
+            output.push_str(&" ".repeat(cell - fill));
+        }
+        output.push('\n');
+    }
+
+    output.push_str("\n█ missing  ▒ partly missing in the grouped rows");
+    if ncols < df.ncols() {
+        output.push_str(&format!(
+            "\n({} of {} columns shown; use --vars to select columns)",
+            ncols,
+            df.ncols()
+        ));
+    }
+    output
 }
 
 /// Format a number in short form.
-fn format_number_short(val: f64) -> String {
+pub fn format_number_short(val: f64) -> String {
     if val.abs() >= 1_000_000.0 {
         format!("{:.1}M", val / 1_000_000.0)
     } else if val.abs() >= 1_000.0 {
         format!("{:.1}k", val / 1_000.0)
     } else if val.fract() == 0.0 && val.abs() < 10000.0 {
         format!("{:.0}", val)
+    } else if val.abs() < 0.1 {
+        // Small edges of log-scale histograms would all round to 0.0
+        format!("{:.0e}", val)
     } else {
         format!("{:.1}", val)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_edges_span_both_files() {
+        let edges = shared_edges(&[2.0, 3.0, 4.0], &[0.0, 10.0], 5).unwrap();
+        assert_eq!(edges, [0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+        assert!(shared_edges(&[1.0, 1.0], &[1.0], 5).is_none());
+        assert!(shared_edges(&[], &[], 5).is_none());
+        assert_eq!(
+            count_in_bins(&[0.0, 1.9, 2.0, 10.0], &edges),
+            [2, 1, 0, 0, 1]
+        );
+    }
+
+    #[test]
+    fn test_overlay_histogram_marks_each_file() {
+        let first = [1.0, 1.0, 1.0, 2.0];
+        let second = [4.0, 4.0];
+        let edges = shared_edges(&first, &second, 3).unwrap();
+        let out = overlay_histogram(&first, &second, &edges, ("a.csv", "b.csv"), 4);
+        let lines: Vec<&str> = out.lines().collect();
+        assert!(lines[0].contains("█ a.csv (n=4)") && lines[0].contains("▒ b.csv (n=2)"));
+        // The second file has all its values in the last bin, the tallest bar
+        assert!(lines[2].starts_with("100%|"));
+        assert_eq!(lines[2].trim_end(), "100%|       ▒");
+        assert!(lines[5].starts_with("  0%|█  ") && lines[5].contains('▒'));
+    }
+
+    #[test]
+    fn test_ecdf_points_end_at_one() {
+        let points = ecdf_points(&[1.0, 2.0, 2.0, 5.0]);
+        assert_eq!(points, vec![(1.0, 0.25), (2.0, 0.75), (5.0, 1.0)]);
+        assert!(ecdf_points(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_ecdf_curve_reaches_top_row() {
+        let df = crate::reader::read_file("tests/data/sample.csv").unwrap();
+        let out = ecdf(&df, "income", 40, 11).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert!(lines[0].starts_with("income: Empirical CDF (n=27)"));
+        assert!(lines[2].starts_with("1.00|"));
+        assert!(lines[2].ends_with('─'));
+        assert!(lines[12].starts_with("0.00|"));
+        assert!(lines[12].trim_end().len() < lines[2].len());
+        assert!(out.contains("p50: 46000.00"));
+    }
+
+    #[test]
+    fn test_splom_tiles_panels_with_names_on_diagonal() {
+        let df = crate::reader::read_file("tests/data/sample.csv").unwrap();
+        let cols: Vec<String> = ["age", "income", "score"].map(String::from).to_vec();
+        let out = splom(&df, &cols, 72).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        // Title, blank, top rule, then 3 panels of 6 lines with rules between
+        assert_eq!(lines.len(), 3 + 3 * 6 + 2 + 1);
+        assert!(lines[2].starts_with('┌') && lines[2].matches('┬').count() == 2);
+        assert!(lines[5].contains("age"));
+        assert!(lines[12].contains("income"));
+        assert!(lines[19].contains("score"));
+        let width = lines[2].chars().count();
+        assert!(lines.iter().skip(2).all(|l| l.chars().count() == width));
+        assert!(splom(&df, &["age".to_string(), "nope".to_string()], 72).is_none());
+    }
+
+    #[test]
+    fn test_splom_panel_without_complete_pairs() {
+        let df = DataFrame::from_rows(&["alpha", "b"], &[["1", ""], ["", "2"], ["3", ""]]);
+        let cols = vec!["alpha".to_string(), "b".to_string()];
+        let out = splom(&df, &cols, 40).unwrap();
+        assert!(out.contains("n=0"));
+    }
+
+    #[test]
+    fn test_missing_heatmap_marks_missing_cells() {
+        let df = DataFrame::from_rows(&["alpha", "b"], &[["1", "x"], ["", "y"], ["3", "NA"]]);
+        let out = missing_heatmap(&df, 20, 10);
+        let lines: Vec<&str> = out.lines().collect();
+        assert!(lines[2].starts_with("  alpha"));
+        assert_eq!(lines[3].trim_end(), "1");
+        assert!(lines[4].starts_with("2 █"));
+        assert!(lines[5].trim_end().ends_with('█'));
+    }
+
+    #[test]
+    fn test_missing_heatmap_downsamples_rows() {
+        let rows: Vec<[&str; 2]> = (0..1000)
+            .map(|i| if i % 2 == 0 { ["1", ""] } else { ["1", "2"] })
+            .collect();
+        let out = missing_heatmap(&DataFrame::from_rows(&["alpha", "b"], &rows), 20, 25);
+        assert!(out.contains("~40 rows per line"));
+        let grid: Vec<&str> = out.lines().skip(3).take_while(|l| !l.is_empty()).collect();
+        assert_eq!(grid.len(), 25);
+        assert!(grid.iter().all(|l| l.contains('▒') && !l.contains('█')));
+    }
+
+    #[test]
+    fn test_density_integrates_to_about_one() {
+        let values: Vec<f64> = (0..200).map(|i| (i % 20) as f64).collect();
+        let curve = density(&values, 40);
+        assert_eq!(curve.len(), 40);
+        assert!(curve.iter().all(|d| *d >= 0.0));
+        // Riemann sum over the data range; tails outside it are lost
+        let area: f64 = curve.iter().sum::<f64>() * (19.0 / 40.0);
+        assert!(area > 0.8 && area <= 1.0);
+    }
+
+    #[test]
+    fn test_lineplot_leaves_gaps_for_missing_values() {
+        let df = DataFrame::from_column("v", &["1", "2", "NA", "NA", "5", "6"]);
+        let out = lineplot(&df, &["v"], 20, 10).unwrap();
+        // Rows 0-1 map to columns 0-4 and rows 4-5 to columns 15-19; the missing
+        // rows in between must not be bridged.
+        let grid_rows: Vec<Vec<char>> = out
+            .lines()
+            .filter_map(|l| l.split_once('│').map(|(_, g)| g.chars().collect()))
+            .collect();
+        assert_eq!(grid_rows.len(), 10);
+        for row in &grid_rows {
+            assert!(row[5..15].iter().all(|c| *c == ' '), "gap was filled: {:?}", row);
+        }
+        assert!(grid_rows.iter().any(|r| r[..5].iter().any(|c| *c != ' ')));
+        assert!(grid_rows.iter().any(|r| r[15..].iter().any(|c| *c != ' ')));
+    }
+
+    #[test]
+    fn test_lineplot_series_keep_their_own_gaps() {
+        let df = DataFrame::from_rows(
+            &["a", "b"],
+            &[
+                ["1", "10"],
+                ["2", "9"],
+                ["NA", "8"],
+                ["NA", "7"],
+                ["5", "6"],
+                ["6", "5"],
+            ],
+        );
+        let out = lineplot(&df, &["a", "b"], 20, 10).unwrap();
+        assert!(out.starts_with("Line plot: a, b (n=6)"));
+        assert!(out.contains("  * a (2 missing)\n"));
+        assert!(out.contains("  + b (0 missing)\n"));
+
+        let grid_rows: Vec<Vec<char>> = out
+            .lines()
+            .filter_map(|l| l.split_once('│').map(|(_, g)| g.chars().collect()))
+            .collect();
+        // Only `a` is missing in rows 2-3, so `b` still runs through columns 5-14
+        for row in &grid_rows {
+            assert!(!row[5..15].contains(&'*'), "gap in a was filled: {:?}", row);
+        }
+        assert!(grid_rows.iter().any(|r| r[5..15].contains(&'+')));
+        assert!(lineplot(&df, &["a", "nope"], 20, 10).is_none());
+    }
+
+    #[test]
+    fn test_grouped_boxplot_small_groups_use_point_range() {
+        let df = DataFrame::from_rows(
+            &["v", "g"],
+            &[
+                ["1", "a"],
+                ["2", "a"],
+                ["3", "a"],
+                ["4", "a"],
+                ["5", "a"],
+                ["6", "a"],
+                ["10", "b"],
+                ["12", "b"],
+                ["7", "NA"],
+            ],
+        );
+        let out = grouped_boxplot(&df, "v", "g", 40).unwrap();
+        let line_a = out.lines().find(|l| l.trim_start().starts_with("a ")).unwrap();
+        let line_b = out.lines().find(|l| l.trim_start().starts_with("b ")).unwrap();
+        assert!(line_a.contains('█'));
+        assert!(!line_b.contains('█'));
+        assert!(line_b.contains('·'));
+        assert!(out.contains("(missing)"));
+        assert!(out.contains("b: n=2 (too few for a box)"));
+    }
+
+    #[test]
+    fn test_histogram_constant_column_reports_value() {
+        let df = DataFrame::from_column("v", &["5.0"; 6]);
+        let out = histogram(&df, "v", 60, 10, None, false, false).unwrap();
+        assert_eq!(out, "v: all values are 5 (n=6)");
+        assert!(!out.contains('█'));
+    }
+
+    #[test]
+    fn test_histogram_data_bins_and_density() {
+        let df = crate::reader::read_file("tests/data/sample.csv").unwrap();
+        let data = match histogram_data(&df, "income", 25, Some(4), true, false).unwrap() {
+            HistogramData::Binned(data) => data,
+            other => panic!("expected bins, got {:?}", other),
+        };
+        assert_eq!(data.counts.len(), 4);
+        assert_eq!(data.counts.iter().sum::<usize>(), data.n);
+        assert_eq!(data.density.as_ref().map(Vec::len), Some(4));
+
+        // The cap wins over an explicit request
+        match histogram_data(&df, "income", 3, Some(10), false, false).unwrap() {
+            HistogramData::Binned(data) => {
+                assert_eq!(data.counts.len(), 3);
+                assert!(data.density.is_none());
+            }
+            other => panic!("expected bins, got {:?}", other),
+        }
+        assert!(histogram_data(&df, "nope", 25, None, false, false).is_none());
+    }
+
+    #[test]
+    fn test_qqplot_draws_reference_line() {
+        let values: Vec<String> = (1..=30).map(|v| v.to_string()).collect();
+        let out = qqplot(&DataFrame::from_column("v", &values), "v", 40, 12).unwrap();
+        assert!(out.starts_with("v: Normal QQ plot (n=30,"));
+        assert!(out.contains('/'));
+
+        let constant = DataFrame::from_column("v", &["4", "4", "4"]);
+        let out = qqplot(&constant, "v", 40, 12).unwrap();
+        assert_eq!(out, "v: All values are identical, QQ plot is undefined");
+
+        let short = DataFrame::from_column("v", &["1", "2", "NA"]);
+        let out = qqplot(&short, "v", 40, 12).unwrap();
+        assert_eq!(out, "v: QQ plot needs at least 3 valid values (found 2)");
+        assert!(qqplot(&short, "nope", 40, 12).is_none());
+    }
+
+    #[test]
+    fn test_histogram_bins_clamped_to_distinct_values_and_width() {
+        let df = DataFrame::from_column("v", &["1", "2", "2", "3", "3", "3"]);
+        let bins = |max_bins, requested| {
+            let data = histogram_data(&df, "v", max_bins, Some(requested), false, false);
+            match data.unwrap() {
+                HistogramData::Binned(data) => data.counts.len(),
+                other => panic!("expected bins, got {:?}", other),
+            }
+        };
+        // Never more bins than distinct values...
+        assert_eq!(bins(25, 10), 3);
+        // ...or than fit in the plot width
+        assert_eq!(bins(2, 10), 2);
+        assert_eq!(bins(25, 2), 2);
+
+        // histogram() draws one two-column bar per bin, so at most width / 2 bins
+        let drawn = |width| {
+            let out = histogram(&df, "v", width, 10, Some(10), false, false).unwrap();
+            let axis = out.lines().find(|l| l.contains('└')).unwrap().to_string();
+            axis.matches("──").count()
+        };
+        assert_eq!(drawn(4), 2);
+        assert_eq!(drawn(60), 3);
+    }
+
+    #[test]
+    fn test_histogram_log_scale_balances_skewed_data() {
+        // Powers spread evenly over four orders of magnitude, plus values log cannot take
+        let mut values: Vec<String> = (0..40)
+            .map(|i| 10f64.powf(i as f64 / 10.0).to_string())
+            .collect();
+        values.extend(["0", "-5"].map(String::from));
+        let df = DataFrame::from_column("v", &values);
+        let bins = |log| match histogram_data(&df, "v", 25, Some(4), false, log).unwrap() {
+            HistogramData::Binned(data) => data,
+            other => panic!("expected bins, got {:?}", other),
+        };
+
+        let linear = bins(false);
+        assert_eq!(linear.skipped, 0);
+        assert!(linear.counts[0] > 30);
+
+        let log = bins(true);
+        assert_eq!(log.skipped, 2);
+        assert_eq!(log.counts, vec![10, 10, 10, 10]);
+        assert!((log.edge(0) - 1.0).abs() < 1e-9);
+        // Summary statistics stay in the column's units
+        assert!((log.median - (10f64.powf(1.9) + 100.0) / 2.0).abs() < 1e-9);
+
+        let out = histogram(&df, "v", 60, 10, Some(4), false, true).unwrap();
+        assert!(out.contains("log10 scale"));
+        assert!(out.contains("Skipped 2 non-positive value(s)"));
+    }
+
+    #[test]
+    fn test_boxplot_lists_most_extreme_outliers() {
+        let mut values: Vec<String> = (1..=20).map(|v| v.to_string()).collect();
+        values.extend(["-40", "45", "50", "60", "70", "500"].map(String::from));
+        let df = DataFrame::from_column("v", &values);
+        let out = boxplot(&df, "v", 60).unwrap();
+        assert!(out.contains("Outliers: 6 values"));
+        let listed = out.lines().last().unwrap();
+        assert_eq!(
+            listed,
+            "Most extreme: 500.00, 70.00, -40.00, 60.00, 50.00 (5 of 6)"
+        );
+    }
+
+    #[test]
+    fn test_boxplot_labels_wider_than_plot() {
+        let df = DataFrame::from_column("v", &["1000000", "2000000"]);
+        let out = boxplot(&df, "v", 5).unwrap();
+        assert!(out.contains("1.0M"));
+        assert!(out.contains("2.0M"));
+
+        let df = DataFrame::from_column("v", &["1e300", "2e300"]);
+        let out = boxplot(&df, "v", 5).unwrap();
+        let labels = out.lines().nth(5).unwrap();
+        assert!(labels.trim_start().contains(' '));
+    }
+
+    #[test]
+    fn test_density_constant_column_is_empty() {
+        assert!(density(&[5.0, 5.0, 5.0, 5.0], 10).is_empty());
+    }
+}
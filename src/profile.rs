@@ -0,0 +1,71 @@
+use crate::missing;
+use crate::reader::DataFrame;
+use crate::types;
+use crate::utils::is_missing;
+use serde::Serialize;
+
+/// Overview of one column: type, cardinality, missingness and an example value.
+#[derive(Debug, Clone, Serialize)]
+pub struct ColumnProfile {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_label: String,
+    pub unique: usize,
+    pub missing: usize,
+    pub missing_pct: f64,
+    /// First non-missing cell, or `None` if the column is entirely missing.
+    pub example: Option<String>,
+}
+
+/// Profile every column, combining type inference, missing counts and an example.
+pub fn profile(df: &DataFrame) -> Vec<ColumnProfile> {
+    types::infer_types(df)
+        .into_iter()
+        .zip(missing::analyze(df))
+        .map(|(info, missing)| {
+            let example = df.column(&info.name).and_then(|values| {
+                values
+                    .into_iter()
+                    .find(|v| !is_missing(v))
+                    .map(|v| v.trim().to_string())
+            });
+            ColumnProfile {
+                type_label: info.type_label(),
+                name: info.name,
+                unique: info.unique_count,
+                missing: missing.missing,
+                missing_pct: missing.pct,
+                example,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::read_file;
+
+    #[test]
+    fn test_profile_sample_columns() {
+        let df = read_file("tests/data/sample.csv").unwrap();
+        let profiles = profile(&df);
+        assert_eq!(profiles.len(), df.ncols());
+
+        let income = profiles.iter().find(|p| p.name == "income").unwrap();
+        assert_eq!(income.type_label, "Integer");
+        assert_eq!(income.missing, 3);
+        assert!((income.missing_pct - 10.0).abs() < 1e-10);
+        assert_eq!(income.example.as_deref(), Some("38000"));
+    }
+
+    #[test]
+    fn test_profile_all_missing_column_has_no_example() {
+        let df = DataFrame::from_rows(&["a", "b"], &[["NA", "x"], ["", "y"]]);
+        let profiles = profile(&df);
+        assert_eq!(profiles[0].example, None);
+        assert_eq!(profiles[0].missing, 2);
+        assert_eq!(profiles[1].example.as_deref(), Some("x"));
+        assert_eq!(profiles[1].unique, 2);
+    }
+}
@@ -1,6 +1,9 @@
 use crate::utils::is_missing;
 use anyhow::{bail, Context, Result};
-use std::io::{self, Read};
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+/// Non-missing cells that failed to parse as numbers, as (row index, trimmed value).
+pub type ParseFailures = Vec<(usize, String)>;
 
 /// Represents a parsed dataset with headers and rows of string values.
 #[derive(Debug, Clone)]
@@ -35,20 +38,31 @@ impl DataFrame {
 
     /// Extracts a column as numeric values, skipping non-parseable entries.
     pub fn numeric_column(&self, name: &str) -> Option<Vec<Option<f64>>> {
+        self.numeric_column_checked(name).map(|(values, _)| values)
+    }
+
+    /// Like `numeric_column`, but also returns the non-missing entries that failed
+    /// to parse.
+    pub fn numeric_column_checked(&self, name: &str) -> Option<(Vec<Option<f64>>, ParseFailures)> {
         let idx = self.col_index(name)?;
-        Some(
-            self.rows
-                .iter()
-                .map(|row| {
-                    let val = row[idx].trim();
-                    if is_missing(val) {
-                        None
-                    } else {
-                        val.parse::<f64>().ok()
-                    }
-                })
-                .collect(),
-        )
+        let mut failures = Vec::new();
+        let values = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let val = row[idx].trim();
+                if is_missing(val) {
+                    return None;
+                }
+                let parsed = val.parse::<f64>().ok();
+                if parsed.is_none() {
+                    failures.push((i, val.to_string()));
+                }
+                parsed
+            })
+            .collect();
+        Some((values, failures))
     }
 
     /// Returns only the non-None numeric values for a column.
@@ -57,6 +71,16 @@ impl DataFrame {
             .map(|col| col.into_iter().flatten().collect())
     }
 
+    /// Indices of the first `n` rows, or of every row if there are fewer.
+    pub fn head_indices(&self, n: usize) -> Vec<usize> {
+        (0..n.min(self.nrows())).collect()
+    }
+
+    /// Indices of the last `n` rows, or of every row if there are fewer.
+    pub fn tail_indices(&self, n: usize) -> Vec<usize> {
+        (self.nrows().saturating_sub(n)..self.nrows()).collect()
+    }
+
     /// Filter to only specific columns.
     pub fn select_columns(&self, names: &[&str]) -> DataFrame {
         let indices: Vec<usize> = names
@@ -71,12 +95,124 @@ impl DataFrame {
             .collect();
         DataFrame { headers, rows }
     }
+
+    /// Split the rows by the values of column `name`, groups in label order.
+    ///
+    /// Rows with a missing key are collected in a final `(missing)` group. Returns
+    /// `None` if the column does not exist.
+    pub fn group_by(&self, name: &str) -> Option<Vec<(String, DataFrame)>> {
+        let idx = self.col_index(name)?;
+        let mut groups: std::collections::BTreeMap<String, Vec<Vec<String>>> =
+            std::collections::BTreeMap::new();
+        let mut missing = Vec::new();
+        for row in &self.rows {
+            let key = row[idx].trim();
+            if is_missing(key) {
+                missing.push(row.clone());
+            } else {
+                groups.entry(key.to_string()).or_default().push(row.clone());
+            }
+        }
+        let frame = |rows| DataFrame {
+            headers: self.headers.clone(),
+            rows,
+        };
+        let mut result: Vec<(String, DataFrame)> = groups
+            .into_iter()
+            .map(|(key, rows)| (key, frame(rows)))
+            .collect();
+        if !missing.is_empty() {
+            result.push(("(missing)".to_string(), frame(missing)));
+        }
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+impl DataFrame {
+    /// Build a frame from literal cells, for tests.
+    pub fn from_rows<S: AsRef<str>, R: AsRef<[S]>>(headers: &[&str], rows: &[R]) -> DataFrame {
+        DataFrame {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: rows
+                .iter()
+                .map(|row| row.as_ref().iter().map(|c| c.as_ref().into()).collect())
+                .collect(),
+        }
+    }
+
+    /// Build a one-column frame, for tests.
+    pub fn from_column<S: AsRef<str>>(name: &str, values: &[S]) -> DataFrame {
+        let rows: Vec<[&str; 1]> = values.iter().map(|v| [v.as_ref()]).collect();
+        DataFrame::from_rows(&[name], &rows)
+    }
+}
+
+/// Options controlling how input files are parsed.
+#[derive(Debug, Clone, Default)]
+pub struct ReadOptions {
+    /// Field delimiter; `None` auto-detects comma vs tab from the first line.
+    pub delimiter: Option<u8>,
+    /// Worksheet to read from Excel files; `None` reads the first one.
+    pub sheet: Option<String>,
+    /// Number of leading lines (e.g. metadata preamble) to drop before the header.
+    pub skip_rows: usize,
+    /// Lines whose first non-whitespace character is this byte are ignored.
+    pub comment: Option<u8>,
+    /// Treat the first line as data and name columns `col1`, `col2`, ...
+    pub no_header: bool,
+    /// Reject rows whose field count differs from the header instead of padding
+    /// or truncating them.
+    pub strict_rows: bool,
+}
+
+/// Parse a user-supplied delimiter: any single ASCII character, or `\t`/`tab` for tab.
+pub fn parse_delimiter(value: &str) -> std::result::Result<u8, String> {
+    match value {
+        "\\t" | "\t" | "tab" | "TAB" => Ok(b'\t'),
+        _ => {
+            let mut chars = value.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii() && !matches!(c, '\n' | '\r' | '"') => {
+                    Ok(c as u8)
+                }
+                _ => Err(format!(
+                    "invalid delimiter '{}': expected a single ASCII character such as , ; | or \\t",
+                    value
+                )),
+            }
+        }
+    }
+}
+
+/// Parse a comment prefix: a single non-whitespace ASCII character such as `#`.
+pub fn parse_comment(value: &str) -> std::result::Result<u8, String> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii() && !c.is_ascii_whitespace() && c != '"' => Ok(c as u8),
+        _ => Err(format!(
+            "invalid comment prefix '{}': expected a single character such as #",
+            value
+        )),
+    }
 }
 
 /// Detects the delimiter (comma or tab) by inspecting the first line.
+///
+/// Characters inside double quotes are not counted, so a quoted field such as
+/// `"last, first"` does not sway the choice.
 fn detect_delimiter(first_line: &str) -> u8 {
-    let tab_count = first_line.chars().filter(|&c| c == '\t').count();
-    let comma_count = first_line.chars().filter(|&c| c == ',').count();
+    let mut in_quotes = false;
+    let (mut tab_count, mut comma_count) = (0, 0);
+    for c in first_line.chars() {
+        match c {
+            // A doubled "" escape toggles twice and leaves the state unchanged
+            '"' => in_quotes = !in_quotes,
+            '\t' if !in_quotes => tab_count += 1,
+            ',' if !in_quotes => comma_count += 1,
+            _ => {}
+        }
+    }
     if tab_count > comma_count {
         b'\t'
     } else {
@@ -84,36 +220,193 @@ fn detect_delimiter(first_line: &str) -> u8 {
     }
 }
 
+/// Rename repeated column names to `name.1`, `name.2`, ... so every column can be
+/// addressed, warning on stderr for each rename.
+fn disambiguate_headers(headers: Vec<String>) -> Vec<String> {
+    let mut seen: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let mut result = Vec::with_capacity(headers.len());
+    for header in &headers {
+        let mut name = header.clone();
+        let mut suffix = 0;
+        // Skip suffixed names that already appear later in the header row
+        while seen.contains(&name) || (suffix > 0 && headers.contains(&name)) {
+            suffix += 1;
+            name = format!("{}.{}", header, suffix);
+        }
+        if suffix > 0 {
+            eprintln!(
+                "Warning: duplicate column '{}' renamed to '{}'",
+                header, name
+            );
+        }
+        seen.insert(name.clone());
+        result.push(name);
+    }
+    result
+}
+
+/// Rows whose field count differed from the header and were padded or truncated.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct RaggedRows {
+    padded: usize,
+    truncated: usize,
+}
+
+impl RaggedRows {
+    /// Record data row `row` (1-based) with `fields` fields against `width` headers.
+    ///
+    /// With `strict`, a mismatch is an error naming the row and its field count.
+    fn check(&mut self, row: usize, fields: usize, width: usize, strict: bool) -> Result<()> {
+        if fields == width {
+            return Ok(());
+        }
+        if strict {
+            bail!(
+                "Row {} has {} field(s) but the header has {}",
+                row,
+                fields,
+                width
+            );
+        }
+        if fields < width {
+            self.padded += 1;
+        } else {
+            self.truncated += 1;
+        }
+        Ok(())
+    }
+
+    /// One-line summary such as "12 rows padded, 3 rows truncated", if any row was fixed up.
+    fn summary(&self) -> Option<String> {
+        let rows = |n: usize| if n == 1 { "row" } else { "rows" };
+        let mut parts = Vec::new();
+        if self.padded > 0 {
+            parts.push(format!("{} {} padded", self.padded, rows(self.padded)));
+        }
+        if self.truncated > 0 {
+            parts.push(format!(
+                "{} {} truncated",
+                self.truncated,
+                rows(self.truncated)
+            ));
+        }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    /// Warn on stderr once about every row that did not match the header.
+    fn warn(&self) {
+        if let Some(summary) = self.summary() {
+            eprintln!(
+                "Warning: {} to match the header (use --strict-rows to reject them)",
+                summary
+            );
+        }
+    }
+}
+
 /// Parse CSV/TSV content from a string buffer into a DataFrame.
+#[allow(dead_code)]
 fn parse_csv(content: &str) -> Result<DataFrame> {
+    parse_csv_with(content, &ReadOptions::default())
+}
+
+/// Drop the first `n` lines of `content`.
+fn skip_lines(content: &str, n: usize) -> &str {
+    let mut rest = content;
+    for _ in 0..n {
+        match rest.find('\n') {
+            Some(pos) => rest = &rest[pos + 1..],
+            None => return "",
+        }
+    }
+    rest
+}
+
+/// Whether `line` opens or closes a quoted field: escaped quotes come in pairs,
+/// so only an odd number of quotes changes whether the next line starts a record.
+fn flips_quoting(line: &[u8]) -> bool {
+    line.iter().filter(|&&b| b == b'"').count() % 2 == 1
+}
+
+/// Parse delimited content using the given options.
+///
+/// Rows with too few fields are padded with empty strings and longer ones are
+/// truncated, with one warning for the whole file; `strict_rows` rejects them.
+fn parse_csv_with(content: &str, opts: &ReadOptions) -> Result<DataFrame> {
+    let (df, ragged) = parse_csv_counted(content, opts)?;
+    ragged.warn();
+    Ok(df)
+}
+
+/// Parse delimited content, also returning the rows that had to be fixed up.
+fn parse_csv_counted(content: &str, opts: &ReadOptions) -> Result<(DataFrame, RaggedRows)> {
+    // Excel prepends a UTF-8 byte order mark that would stick to the first header
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let content = skip_lines(content, opts.skip_rows);
+
+    // Drop comment lines up front so indented comments and ones before the header
+    // are ignored too; the csv reader's own comment handling only sees column 0.
+    // Lines continuing a quoted multi-line field are data, whatever they start with.
+    let uncommented;
+    let content = match opts.comment {
+        Some(prefix) => {
+            let mut quoted = false;
+            uncommented = content
+                .split_inclusive('\n')
+                .filter(|line| {
+                    let keep = quoted || !line.trim_start().starts_with(prefix as char);
+                    if keep {
+                        quoted ^= flips_quoting(line.as_bytes());
+                    }
+                    keep
+                })
+                .collect::<String>();
+            uncommented.as_str()
+        }
+        None => content,
+    };
+
     let first_line = content.lines().next().unwrap_or("");
     if first_line.trim().is_empty() {
         bail!("Input data is empty");
     }
 
-    let delimiter = detect_delimiter(first_line);
+    let delimiter = opts
+        .delimiter
+        .unwrap_or_else(|| detect_delimiter(first_line));
 
     let mut rdr = csv::ReaderBuilder::new()
         .delimiter(delimiter)
         .flexible(true)
-        .has_headers(true)
+        .has_headers(!opts.no_header)
+        .comment(opts.comment)
         .from_reader(content.as_bytes());
 
-    let headers: Vec<String> = rdr
-        .headers()
-        .context("Cannot read headers")?
-        .iter()
-        .map(|h| h.trim().to_string())
-        .collect();
+    // Without a header row, `headers()` peeks at the first record, which is still
+    // yielded as data below; only its field count is used.
+    let first = rdr.headers().context("Cannot read headers")?;
+    let headers: Vec<String> = if opts.no_header {
+        (1..=first.len()).map(|i| format!("col{}", i)).collect()
+    } else {
+        first.iter().map(|h| h.trim().to_string()).collect()
+    };
 
     if headers.is_empty() {
         bail!("No columns found in input");
     }
+    let headers = disambiguate_headers(headers);
 
     let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut ragged = RaggedRows::default();
     for result in rdr.records() {
         let record = result
             .with_context(|| format!("Error reading row {}", rows.len() + 1))?;
+        ragged.check(
+            rows.len() + 1,
+            record.len(),
+            headers.len(),
+            opts.strict_rows,
+        )?;
         let mut row: Vec<String> = record.iter().map(|f| f.trim().to_string()).collect();
         // Pad short rows with empty strings
         while row.len() < headers.len() {
@@ -124,13 +417,31 @@ fn parse_csv(content: &str) -> Result<DataFrame> {
         rows.push(row);
     }
 
-    Ok(DataFrame { headers, rows })
+    Ok((DataFrame { headers, rows }, ragged))
+}
+
+/// Reads a CSV/TSV (or `.json`) file into a DataFrame, auto-detecting the delimiter.
+#[allow(dead_code)]
+pub fn read_file(path: &str) -> Result<DataFrame> {
+    read_file_with(path, &ReadOptions::default())
 }
 
-/// Reads a CSV/TSV file into a DataFrame.
+/// Reads a delimited file into a DataFrame using the given options.
 ///
+/// Files with a `.json` extension are read as records-style JSON instead, and
+/// `.parquet` files with `read_parquet`.
 /// The file is read once into memory and then parsed, avoiding a double file open.
-pub fn read_file(path: &str) -> Result<DataFrame> {
+pub fn read_file_with(path: &str, opts: &ReadOptions) -> Result<DataFrame> {
+    if has_extension(path, &["json"]) {
+        return read_json(path);
+    }
+    if has_extension(path, &["xlsx", "xls"]) {
+        return read_xlsx(path, opts.sheet.as_deref());
+    }
+    if has_extension(path, &["parquet"]) {
+        return read_parquet(path);
+    }
+
     let content = std::fs::read_to_string(path)
         .with_context(|| format!("Cannot open file '{}'", path))?;
 
@@ -138,12 +449,216 @@ pub fn read_file(path: &str) -> Result<DataFrame> {
         bail!("File '{}' is empty", path);
     }
 
-    parse_csv(&content)
+    parse_csv_with(&content, opts)
         .with_context(|| format!("Failed to parse '{}'", path))
 }
 
+/// Stacks the rows of several frames into one, in order.
+///
+/// Every frame must have the same headers in the same order as the first.
+pub fn concat(frames: Vec<DataFrame>) -> Result<DataFrame> {
+    let mut frames = frames.into_iter();
+    let Some(mut combined) = frames.next() else {
+        bail!("No datasets to concatenate");
+    };
+    for (i, frame) in frames.enumerate() {
+        if frame.headers != combined.headers {
+            bail!(
+                "Dataset {} has columns [{}] but dataset 1 has [{}]",
+                i + 2,
+                frame.headers.join(", "),
+                combined.headers.join(", ")
+            );
+        }
+        combined.rows.extend(frame.rows);
+    }
+    Ok(combined)
+}
+
+/// Returns true if `path` ends with one of the given extensions (case-insensitive).
+fn has_extension(path: &str, extensions: &[&str]) -> bool {
+    std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| extensions.iter().any(|x| e.eq_ignore_ascii_case(x)))
+}
+
+/// Reads a worksheet of an Excel file (the first one unless `sheet` is given).
+///
+/// The first row holds the headers. Cells are converted to their string form,
+/// dates to ISO 8601, and empty or error cells to empty strings.
+pub fn read_xlsx(path: &str, sheet: Option<&str>) -> Result<DataFrame> {
+    use calamine::Reader;
+
+    let mut workbook = calamine::open_workbook_auto(path)
+        .with_context(|| format!("Cannot open workbook '{}'", path))?;
+    let names = workbook.sheet_names();
+    let name = match sheet {
+        Some(name) if names.iter().any(|n| n == name) => name.to_string(),
+        Some(name) => bail!(
+            "Sheet '{}' not found in '{}'. Available: {}",
+            name,
+            path,
+            names.join(", ")
+        ),
+        None => match names.first() {
+            Some(first) => first.clone(),
+            None => bail!("Workbook '{}' has no worksheets", path),
+        },
+    };
+    let range = workbook
+        .worksheet_range(&name)
+        .with_context(|| format!("Cannot read sheet '{}' of '{}'", name, path))?;
+
+    let mut rows_iter = range.rows();
+    let headers: Vec<String> = match rows_iter.next() {
+        Some(row) => disambiguate_headers(row.iter().map(cell_to_string).collect()),
+        None => bail!("Sheet '{}' of '{}' is empty", name, path),
+    };
+
+    let rows = rows_iter
+        .map(|row| {
+            let mut values: Vec<String> = row.iter().map(cell_to_string).collect();
+            values.resize(headers.len(), String::new());
+            values
+        })
+        .collect();
+
+    Ok(DataFrame { headers, rows })
+}
+
+/// Reads a Parquet file into a DataFrame.
+///
+/// Each value is converted to its display form (dates as ISO 8601) so type
+/// inference works as it does for CSV; nulls become empty strings.
+#[cfg(feature = "parquet")]
+pub fn read_parquet(path: &str) -> Result<DataFrame> {
+    use arrow_array::RecordBatchReader;
+    use arrow_cast::display::{ArrayFormatter, FormatOptions};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let file = std::fs::File::open(path).with_context(|| format!("Cannot open file '{}'", path))?;
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+        .and_then(|builder| builder.build())
+        .with_context(|| format!("Failed to read Parquet file '{}'", path))?;
+    let headers = disambiguate_headers(
+        reader
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect(),
+    );
+
+    let options = FormatOptions::default().with_null("");
+    let mut rows = Vec::new();
+    for batch in reader {
+        let batch = batch.with_context(|| format!("Failed to read Parquet file '{}'", path))?;
+        let formatters = batch
+            .columns()
+            .iter()
+            .map(|column| ArrayFormatter::try_new(column.as_ref(), &options))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        for i in 0..batch.num_rows() {
+            rows.push(formatters.iter().map(|f| f.value(i).to_string()).collect());
+        }
+    }
+
+    Ok(DataFrame { headers, rows })
+}
+
+#[cfg(not(feature = "parquet"))]
+pub fn read_parquet(path: &str) -> Result<DataFrame> {
+    bail!(
+        "Cannot read '{}': Parquet input requires building with --features parquet",
+        path
+    )
+}
+
+/// String form of a spreadsheet cell, with dates as ISO 8601.
+fn cell_to_string(cell: &calamine::Data) -> String {
+    use calamine::Data;
+
+    match cell {
+        Data::Empty | Data::Error(_) => String::new(),
+        Data::String(s) => s.trim().to_string(),
+        Data::Int(i) => i.to_string(),
+        Data::Float(f) => f.to_string(),
+        Data::Bool(b) => b.to_string(),
+        Data::DateTime(dt) if dt.is_datetime() => match dt.as_datetime() {
+            // Displayed as "YYYY-MM-DD HH:MM:SS"; plain dates drop the midnight time
+            Some(value) => {
+                let text = value.to_string();
+                match text.strip_suffix(" 00:00:00") {
+                    Some(date) => date.to_string(),
+                    None => text.replacen(' ', "T", 1),
+                }
+            }
+            None => dt.as_f64().to_string(),
+        },
+        Data::DateTime(dt) => dt.as_f64().to_string(),
+        Data::DateTimeIso(s) | Data::DurationIso(s) => s.clone(),
+    }
+}
+
+/// Reads a JSON file holding an array of flat objects into a DataFrame.
+pub fn read_json(path: &str) -> Result<DataFrame> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Cannot open file '{}'", path))?;
+    if content.trim().is_empty() {
+        bail!("File '{}' is empty", path);
+    }
+    parse_json(&content).with_context(|| format!("Failed to parse '{}'", path))
+}
+
+/// Parse a records-style JSON array (`[{"a": 1, "b": "x"}, ...]`) into a DataFrame.
+///
+/// Headers are the union of keys in first-seen order; absent keys and `null` become
+/// empty strings (treated as missing) and nested values are kept as JSON text.
+fn parse_json(content: &str) -> Result<DataFrame> {
+    let value: serde_json::Value = serde_json::from_str(content).context("Invalid JSON")?;
+    let records = match value {
+        serde_json::Value::Array(items) => items,
+        _ => bail!("Expected a JSON array of objects"),
+    };
+
+    let mut headers: Vec<String> = Vec::new();
+    let mut objects = Vec::with_capacity(records.len());
+    for (i, record) in records.into_iter().enumerate() {
+        let serde_json::Value::Object(map) = record else {
+            bail!("Record {} is not a JSON object", i + 1);
+        };
+        for key in map.keys() {
+            if !headers.contains(key) {
+                headers.push(key.clone());
+            }
+        }
+        objects.push(map);
+    }
+
+    if headers.is_empty() {
+        bail!("No columns found in input");
+    }
+
+    let rows = objects
+        .iter()
+        .map(|map| {
+            headers
+                .iter()
+                .map(|h| match map.get(h) {
+                    None | Some(serde_json::Value::Null) => String::new(),
+                    Some(serde_json::Value::String(s)) => s.trim().to_string(),
+                    Some(other) => other.to_string(),
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(DataFrame { headers, rows })
+}
+
 /// Reads from stdin into a DataFrame.
-pub fn read_stdin() -> Result<DataFrame> {
+pub fn read_stdin(opts: &ReadOptions) -> Result<DataFrame> {
     let stdin = io::stdin();
     let mut input = String::new();
     stdin
@@ -155,13 +670,217 @@ pub fn read_stdin() -> Result<DataFrame> {
         bail!("No data received from stdin");
     }
 
-    parse_csv(&input).context("Failed to parse stdin input")
+    parse_csv_with(&input, opts).context("Failed to parse stdin input")
+}
+
+/// Receives the rows of a file one at a time, see `stream_file_with`.
+pub trait RowVisitor {
+    /// Called once with the column names before any row.
+    fn headers(&mut self, headers: &[String]);
+    /// Called for each data row, padded or truncated to the number of headers.
+    fn row(&mut self, row: &[String]);
+}
+
+/// Reads a file row by row into `visitor` without keeping the rows in memory.
+///
+/// Delimited files are parsed from a buffered handle with the same options as
+/// `read_file_with`. JSON, Excel and Parquet inputs have no streaming parser, so
+/// they are loaded whole and then replayed.
+pub fn stream_file_with(
+    path: &str,
+    opts: &ReadOptions,
+    visitor: &mut impl RowVisitor,
+) -> Result<()> {
+    if has_extension(path, &["json", "xlsx", "xls", "parquet"]) {
+        let df = read_file_with(path, opts)?;
+        visitor.headers(&df.headers);
+        df.rows.iter().for_each(|row| visitor.row(row));
+        return Ok(());
+    }
+
+    let file = std::fs::File::open(path).with_context(|| format!("Cannot open file '{}'", path))?;
+    stream_csv(BufReader::new(file), opts, visitor)
+        .with_context(|| format!("Failed to parse '{}'", path))
+}
+
+/// Reads stdin row by row into `visitor`, see `stream_file_with`.
+pub fn stream_stdin(opts: &ReadOptions, visitor: &mut impl RowVisitor) -> Result<()> {
+    stream_csv(io::stdin().lock(), opts, visitor).context("Failed to parse stdin input")
+}
+
+/// Drops lines whose first non-whitespace character is `prefix` from a reader,
+/// except inside quoted multi-line fields.
+struct CommentFilter<R> {
+    inner: R,
+    prefix: Option<u8>,
+    line: Vec<u8>,
+    pos: usize,
+    quoted: bool,
+}
+
+impl<R: BufRead> CommentFilter<R> {
+    /// Load the next kept line into the buffer; false at end of input.
+    fn next_line(&mut self) -> io::Result<bool> {
+        loop {
+            self.line.clear();
+            self.pos = 0;
+            if self.inner.read_until(b'\n', &mut self.line)? == 0 {
+                return Ok(false);
+            }
+            let first = self.line.iter().find(|b| !b.is_ascii_whitespace());
+            if self.quoted || self.prefix.is_none() || first != self.prefix.as_ref() {
+                self.quoted ^= flips_quoting(&self.line);
+                return Ok(true);
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Read for CommentFilter<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos == self.line.len() && !self.next_line()? {
+            return Ok(0);
+        }
+        let n = buf.len().min(self.line.len() - self.pos);
+        buf[..n].copy_from_slice(&self.line[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+/// Parse delimited input incrementally, mirroring `parse_csv_with`.
+fn stream_csv(
+    mut input: impl BufRead,
+    opts: &ReadOptions,
+    visitor: &mut impl RowVisitor,
+) -> Result<()> {
+    if input.fill_buf()?.starts_with(b"\xef\xbb\xbf") {
+        input.consume(3);
+    }
+
+    let mut skipped = Vec::new();
+    for _ in 0..opts.skip_rows {
+        skipped.clear();
+        if input.read_until(b'\n', &mut skipped)? == 0 {
+            break;
+        }
+    }
+
+    let mut input = CommentFilter {
+        inner: input,
+        prefix: opts.comment,
+        line: Vec::new(),
+        pos: 0,
+        quoted: false,
+    };
+
+    // The first kept line stays buffered, so the csv reader still sees it
+    input.next_line()?;
+    let first = String::from_utf8_lossy(&input.line).into_owned();
+    if first.trim().is_empty() {
+        bail!("Input data is empty");
+    }
+
+    let delimiter = opts
+        .delimiter
+        .unwrap_or_else(|| detect_delimiter(first.trim_end()));
+
+    let mut rdr = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .has_headers(!opts.no_header)
+        .comment(opts.comment)
+        .from_reader(input);
+
+    let first = rdr.headers().context("Cannot read headers")?;
+    let headers: Vec<String> = if opts.no_header {
+        (1..=first.len()).map(|i| format!("col{}", i)).collect()
+    } else {
+        first.iter().map(|h| h.trim().to_string()).collect()
+    };
+
+    if headers.is_empty() {
+        bail!("No columns found in input");
+    }
+    let headers = disambiguate_headers(headers);
+    visitor.headers(&headers);
+
+    let mut record = csv::StringRecord::new();
+    let mut row: Vec<String> = Vec::with_capacity(headers.len());
+    let mut line = 0usize;
+    let mut ragged = RaggedRows::default();
+    while rdr
+        .read_record(&mut record)
+        .with_context(|| format!("Error reading row {}", line + 1))?
+    {
+        line += 1;
+        ragged.check(line, record.len(), headers.len(), opts.strict_rows)?;
+        row.clear();
+        row.extend(
+            record
+                .iter()
+                .take(headers.len())
+                .map(|f| f.trim().to_string()),
+        );
+        row.resize(headers.len(), String::new());
+        visitor.row(&row);
+    }
+    ragged.warn();
+
+    Ok(())
+}
+
+/// Writes the header and the rows at `indices` as comma-separated CSV.
+pub fn write_csv_rows<W: Write>(df: &DataFrame, indices: &[usize], out: W) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(&df.headers)?;
+    for &i in indices {
+        writer.write_record(&df.rows[i])?;
+    }
+    writer.flush()?;
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_write_csv_rows_round_trip() {
+        let df = read_file("tests/data/sample.csv").unwrap();
+        let mut buf = Vec::new();
+        write_csv_rows(&df, &[0, 29], &mut buf).unwrap();
+        let back = parse_csv(&String::from_utf8(buf).unwrap()).unwrap();
+        assert_eq!(back.headers, df.headers);
+        assert_eq!(back.rows, vec![df.rows[0].clone(), df.rows[29].clone()]);
+    }
+
+    #[test]
+    fn test_group_by_puts_missing_keys_last() {
+        let df = parse_csv("g,x\nb,1\na,2\n,3\nb,4\nNA,5").unwrap();
+        let groups = df.group_by("g").unwrap();
+        let labels: Vec<&str> = groups.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels, ["a", "b", "(missing)"]);
+        assert_eq!(groups[1].1.column("x").unwrap(), ["1", "4"]);
+        assert_eq!(groups[2].1.nrows(), 2);
+        assert_eq!(groups[0].1.headers, df.headers);
+        assert!(df.group_by("nope").is_none());
+    }
+
+    #[test]
+    fn test_head_tail_indices() {
+        let df = parse_csv("x\n1\n2\n3\n4\n5").unwrap();
+        assert_eq!(df.head_indices(2), vec![0, 1]);
+        assert_eq!(df.tail_indices(2), vec![3, 4]);
+    }
+
+    #[test]
+    fn test_head_tail_shorter_than_n() {
+        let df = read_file("tests/data/sample.csv").unwrap();
+        assert_eq!(df.head_indices(100).len(), df.nrows());
+        assert_eq!(df.tail_indices(100), (0..df.nrows()).collect::<Vec<_>>());
+    }
+
     #[test]
     fn test_detect_delimiter_comma() {
         assert_eq!(detect_delimiter("a,b,c"), b',');
@@ -177,11 +896,283 @@ mod tests {
         assert_eq!(detect_delimiter("a,b\tc\td\te"), b'\t');
     }
 
+    #[test]
+    fn test_detect_delimiter_ignores_quoted() {
+        assert_eq!(detect_delimiter("\"a, b, c\"\tx\ty"), b'\t');
+
+        let df = parse_csv("\"last, first\",age\n\"Doe, Jane\",41\n").unwrap();
+        assert_eq!(df.headers, ["last, first", "age"]);
+        assert_eq!(df.rows, [["Doe, Jane", "41"]]);
+    }
+
     #[test]
     fn test_detect_delimiter_empty() {
         assert_eq!(detect_delimiter(""), b',');
     }
 
+    #[test]
+    fn test_parse_delimiter() {
+        assert_eq!(parse_delimiter(";"), Ok(b';'));
+        assert_eq!(parse_delimiter("|"), Ok(b'|'));
+        assert_eq!(parse_delimiter("\\t"), Ok(b'\t'));
+        assert_eq!(parse_delimiter("\t"), Ok(b'\t'));
+        assert!(parse_delimiter(";;").is_err());
+        assert!(parse_delimiter("").is_err());
+        assert!(parse_delimiter("é").is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_forced_delimiter() {
+        let data = "name;income\nAna;1,5\nLuis;2,0\n";
+        // Auto-detection only knows comma and tab, so the header stays one column
+        assert_eq!(parse_csv(data).unwrap().headers, ["name;income"]);
+
+        let opts = ReadOptions {
+            delimiter: Some(b';'),
+            ..Default::default()
+        };
+        let df = parse_csv_with(data, &opts).unwrap();
+        assert_eq!(df.headers, ["name", "income"]);
+        assert_eq!(df.rows[0], ["Ana", "1,5"]);
+
+        let piped = parse_csv_with(
+            "a|b\n1|2\n",
+            &ReadOptions {
+                delimiter: Some(b'|'),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(piped.rows[0], ["1", "2"]);
+    }
+
+    #[test]
+    fn test_parse_json_records() {
+        let data = r#"[
+            {"id": 1, "name": "Ana", "score": 9.5, "active": true},
+            {"id": 2, "name": null, "tags": ["a", "b"]},
+            {"id": 3, "meta": {"k": 1}}
+        ]"#;
+        let df = parse_json(data).unwrap();
+        assert_eq!(df.headers, ["id", "name", "score", "active", "tags", "meta"]);
+        assert_eq!(df.nrows(), 3);
+        assert_eq!(df.rows[0], ["1", "Ana", "9.5", "true", "", ""]);
+        assert_eq!(df.rows[1][4], r#"["a","b"]"#);
+        assert_eq!(df.rows[2][5], r#"{"k":1}"#);
+        assert_eq!(df.valid_numeric_column("score").unwrap(), [9.5]);
+    }
+
+    #[test]
+    fn test_parse_json_rejects_non_records() {
+        assert!(parse_json(r#"{"a": 1}"#).is_err());
+        assert!(parse_json("[1, 2]").is_err());
+        assert!(parse_json("[]").is_err());
+    }
+
+    #[test]
+    fn test_read_file_detects_json() {
+        let df = read_file("tests/data/sample.json").unwrap();
+        assert_eq!(df.headers, ["id", "age", "income", "city"]);
+        assert_eq!(df.nrows(), 4);
+        assert!(is_missing(&df.rows[2][2]));
+    }
+
+    #[test]
+    fn test_read_file_detects_xlsx() {
+        let df = read_file("tests/data/sample.xlsx").unwrap();
+        assert_eq!(df.headers, ["id", "name", "score", "joined"]);
+        assert_eq!(df.nrows(), 3);
+        assert_eq!(df.rows[0], ["1", "Ana", "9.5", "2024-01-15"]);
+        assert_eq!(df.rows[1][3], "2023-12-01");
+        assert!(is_missing(&df.rows[1][2]));
+        assert!(is_missing(&df.rows[2][3]));
+    }
+
+    #[test]
+    fn test_read_xlsx_named_sheet() {
+        let df = read_xlsx("tests/data/sample.xlsx", Some("other")).unwrap();
+        assert_eq!(df.headers, ["a", "b"]);
+        assert_eq!(df.rows, [["1", "2"]]);
+        assert!(read_xlsx("tests/data/sample.xlsx", Some("missing")).is_err());
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_read_parquet_maps_nulls_to_empty() {
+        use arrow_array::{ArrayRef, Float64Array, RecordBatch, StringArray};
+        use std::sync::Arc;
+
+        let batch = RecordBatch::try_from_iter([
+            (
+                "city",
+                Arc::new(StringArray::from(vec![Some("Cali"), None, Some("Lima")])) as ArrayRef,
+            ),
+            (
+                "income",
+                Arc::new(Float64Array::from(vec![Some(1.5), Some(2.0), None])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+        let path = std::env::temp_dir().join(format!("statsctl_{}.parquet", std::process::id()));
+        let file = std::fs::File::create(&path).unwrap();
+        let mut writer = parquet::arrow::ArrowWriter::try_new(file, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let df = read_file(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(df.headers, ["city", "income"]);
+        assert_eq!(df.rows, [["Cali", "1.5"], ["", "2.0"], ["Lima", ""]]);
+        assert!(is_missing(&df.rows[1][0]));
+        let income = df.valid_numeric_column("income").unwrap();
+        assert_eq!(income, [1.5, 2.0]);
+    }
+
+    #[test]
+    fn test_parse_csv_skip_rows() {
+        let data = "Instrument: XR-200\nExported 2024-01-01\ntime\tvalue\n1\t0.5\n2\t0.7\n";
+        let opts = ReadOptions {
+            skip_rows: 2,
+            ..Default::default()
+        };
+        let df = parse_csv_with(data, &opts).unwrap();
+        assert_eq!(df.headers, ["time", "value"]);
+        assert_eq!(df.nrows(), 2);
+
+        let too_many = ReadOptions {
+            skip_rows: 10,
+            ..Default::default()
+        };
+        assert!(parse_csv_with(data, &too_many).is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_comment_lines() {
+        let data = "# generated by sim v2\nx,y\n1,2\n  # checkpoint\n3,4\n#5,6\n";
+        let opts = ReadOptions {
+            comment: Some(b'#'),
+            ..Default::default()
+        };
+        let df = parse_csv_with(data, &opts).unwrap();
+        assert_eq!(df.headers, ["x", "y"]);
+        assert_eq!(df.rows, [["1", "2"], ["3", "4"]]);
+
+        assert_eq!(parse_comment("#"), Ok(b'#'));
+        assert!(parse_comment("//").is_err());
+        assert!(parse_comment(" ").is_err());
+    }
+
+    /// Keeps everything it is sent, to compare streaming with `parse_csv_with`.
+    #[derive(Default)]
+    struct Collect {
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+    }
+
+    impl RowVisitor for Collect {
+        fn headers(&mut self, headers: &[String]) {
+            self.headers = headers.to_vec();
+        }
+
+        fn row(&mut self, row: &[String]) {
+            self.rows.push(row.to_vec());
+        }
+    }
+
+    #[test]
+    fn test_stream_csv_matches_parse() {
+        let data = "Exported 2024-01-01\n# generated by sim v2\nx,y\n1,2\n  # checkpoint\n3\n#5,6\n";
+        let opts = ReadOptions {
+            skip_rows: 1,
+            comment: Some(b'#'),
+            ..Default::default()
+        };
+        let mut streamed = Collect::default();
+        stream_csv(data.as_bytes(), &opts, &mut streamed).unwrap();
+        let df = parse_csv_with(data, &opts).unwrap();
+        assert_eq!(streamed.headers, df.headers);
+        assert_eq!(streamed.rows, df.rows);
+        assert_eq!(df.rows, [["1", "2"], ["3", ""]]);
+
+        assert!(stream_csv("# only\n".as_bytes(), &opts, &mut Collect::default()).is_err());
+    }
+
+    #[test]
+    fn test_comment_prefix_inside_quoted_field_is_data() {
+        let data =
+            "id,note\n1,\"first line\n# not a comment\nlast\"\n# comment\n2,\"say \"\"hi\"\"\"\n";
+        let opts = ReadOptions {
+            comment: Some(b'#'),
+            ..Default::default()
+        };
+        let df = parse_csv_with(data, &opts).unwrap();
+        assert_eq!(
+            df.rows,
+            [
+                ["1", "first line\n# not a comment\nlast"],
+                ["2", "say \"hi\""],
+            ]
+        );
+
+        let mut streamed = Collect::default();
+        stream_csv(data.as_bytes(), &opts, &mut streamed).unwrap();
+        assert_eq!(streamed.rows, df.rows);
+    }
+
+    #[test]
+    fn test_parse_csv_strips_bom() {
+        let data = "\u{feff}name,age\nAna,31\n";
+        let df = parse_csv(data).unwrap();
+        assert_eq!(df.headers[0], "name");
+        assert_eq!(df.col_index("name"), Some(0));
+
+        let mut streamed = Collect::default();
+        stream_csv(data.as_bytes(), &ReadOptions::default(), &mut streamed).unwrap();
+        assert_eq!(streamed.headers, ["name", "age"]);
+    }
+
+    #[test]
+    fn test_parse_csv_duplicate_headers() {
+        let df = parse_csv("id,value,value,value.1\n1,10,20,30\n").unwrap();
+        assert_eq!(df.headers, ["id", "value", "value.2", "value.1"]);
+        assert_eq!(df.column("value").unwrap(), ["10"]);
+        assert_eq!(df.column("value.2").unwrap(), ["20"]);
+        assert_eq!(df.column("value.1").unwrap(), ["30"]);
+    }
+
+    #[test]
+    fn test_concat_stacks_rows() {
+        let first = parse_csv("a,b\n1,2\n3,4\n").unwrap();
+        let second = parse_csv("a,b\n5,6\n").unwrap();
+        let combined = concat(vec![first, second]).unwrap();
+        assert_eq!(combined.nrows(), 3);
+        assert_eq!(combined.rows[2], vec!["5", "6"]);
+    }
+
+    #[test]
+    fn test_concat_rejects_mismatched_headers() {
+        let first = parse_csv("a,b\n1,2\n").unwrap();
+        let second = parse_csv("b,a\n2,1\n").unwrap();
+        let err = concat(vec![first, second]).unwrap_err().to_string();
+        assert!(err.contains("Dataset 2"), "{}", err);
+        assert!(concat(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_read_file_no_header() {
+        let opts = ReadOptions {
+            no_header: true,
+            ..Default::default()
+        };
+        let df = read_file_with("tests/data/headerless.csv", &opts).unwrap();
+        assert_eq!(df.headers, ["col1", "col2", "col3"]);
+        assert_eq!(df.nrows(), 3);
+        assert_eq!(df.rows[0], ["1", "34", "52000"]);
+
+        // By default the first row is consumed as the header
+        assert_eq!(read_file("tests/data/headerless.csv").unwrap().nrows(), 2);
+    }
+
     #[test]
     fn test_parse_csv_basic() {
         let data = "name,age,score\nAlice,25,85\nBob,34,72\n";
@@ -263,4 +1254,34 @@ mod tests {
         assert_eq!(df.rows[0].len(), 3);
         assert_eq!(df.rows[0][2], "");
     }
+    #[test]
+    fn test_ragged_rows_counted_by_default() {
+        let data = "a,b,c\n1,2\n4,5,6\n7\n8,9,10,11\n";
+        let (df, ragged) = parse_csv_counted(data, &ReadOptions::default()).unwrap();
+        assert_eq!(df.nrows(), 4);
+        assert_eq!(df.rows[3], ["8", "9", "10"]);
+        assert_eq!(
+            ragged,
+            RaggedRows {
+                padded: 2,
+                truncated: 1
+            }
+        );
+        assert_eq!(
+            ragged.summary().as_deref(),
+            Some("2 rows padded, 1 row truncated")
+        );
+        assert_eq!(RaggedRows::default().summary(), None);
+    }
+
+    #[test]
+    fn test_strict_rows_rejects_mismatched_row() {
+        let opts = ReadOptions {
+            strict_rows: true,
+            ..ReadOptions::default()
+        };
+        let err = parse_csv_with("a,b,c\n1,2,3\n4,5\n", &opts).unwrap_err();
+        assert_eq!(err.to_string(), "Row 2 has 2 field(s) but the header has 3");
+        assert!(parse_csv_with("a,b\n1,2\n", &opts).is_ok());
+    }
 }
@@ -0,0 +1,551 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use tabled::{builder::Builder, settings::Style};
+
+/// Minimal inline stylesheet for HTML exports.
+const HTML_STYLE: &str = "\
+body { font-family: sans-serif; margin: 1.5em; }
+table { border-collapse: collapse; margin: 1em 0; }
+caption { font-weight: bold; text-align: left; padding-bottom: 0.3em; }
+th, td { border: 1px solid #ccc; padding: 0.25em 0.6em; text-align: right; }
+th { background: #f3f3f3; }
+td:first-child, th:first-child { text-align: left; }
+pre { background: #f8f8f8; padding: 0.5em; }
+.strong { color: #c00; font-weight: bold; }
+.moderate { color: #b8860b; }";
+
+/// Border style of tables rendered for the terminal.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum TableStyle {
+    /// Rounded Unicode box-drawing borders.
+    #[default]
+    Rounded,
+    /// psql-like ASCII separators (`|`, `-`, `+`) that paste cleanly anywhere.
+    Plain,
+}
+
+impl TableStyle {
+    /// Parse `table` (rounded borders) or `plain`, for use as a clap value parser.
+    pub fn parse(value: &str) -> std::result::Result<TableStyle, String> {
+        match value {
+            "table" => Ok(TableStyle::Rounded),
+            "plain" => Ok(TableStyle::Plain),
+            other => Err(format!("unknown style '{}'; use table or plain", other)),
+        }
+    }
+
+    /// Render a built `tabled` table with this style's borders.
+    pub fn draw(self, mut table: tabled::Table) -> String {
+        match self {
+            TableStyle::Rounded => table.with(Style::rounded()).to_string(),
+            TableStyle::Plain => table.with(Style::psql()).to_string(),
+        }
+    }
+}
+
+/// A table of pre-formatted cells that can be rendered to any output format.
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    pub title: Option<String>,
+    /// File name stem used when the table is written to its own file.
+    pub name: Option<String>,
+    pub header: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// CSS classes for individual cells, keyed by (row, column); used by HTML export.
+    pub classes: HashMap<(usize, usize), String>,
+}
+
+impl Table {
+    /// Create an empty table with the given header.
+    pub fn new<S: ToString>(header: &[S]) -> Table {
+        Table {
+            title: None,
+            name: None,
+            header: header.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+            classes: HashMap::new(),
+        }
+    }
+
+    /// Set the title shown above the table.
+    pub fn with_title(mut self, title: &str) -> Table {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Set the file name stem used when the table is written to its own file.
+    pub fn with_name(mut self, name: &str) -> Table {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Append a row of cells.
+    pub fn push_row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    /// Render the table for the terminal with the borders of `style`.
+    pub fn render(&self, style: TableStyle) -> String {
+        let mut builder = Builder::new();
+        builder.push_record(self.header.clone());
+        for row in &self.rows {
+            builder.push_record(row.clone());
+        }
+
+        let mut output = String::new();
+        if let Some(title) = &self.title {
+            output.push_str(title);
+            output.push_str(":\n");
+        }
+        output.push_str(&style.draw(builder.build()));
+        output
+    }
+
+    /// Render the table as a GitHub-flavored Markdown pipe table.
+    pub fn to_markdown(&self) -> String {
+        let mut output = String::new();
+        if let Some(title) = &self.title {
+            output.push_str(&format!("### {}\n\n", title));
+        }
+        output.push_str(&markdown_row(&self.header));
+        output.push_str(&markdown_row(
+            &self.header.iter().map(|_| "---".to_string()).collect::<Vec<_>>(),
+        ));
+        for row in &self.rows {
+            output.push_str(&markdown_row(row));
+        }
+        output
+    }
+
+    /// Tag a cell with a CSS class for HTML export.
+    pub fn set_class(&mut self, row: usize, col: usize, class: &str) {
+        self.classes.insert((row, col), class.to_string());
+    }
+
+    /// Render the table as an HTML `<table>` element.
+    pub fn to_html(&self) -> String {
+        let mut output = "<table>\n".to_string();
+        if let Some(title) = &self.title {
+            output.push_str(&format!("<caption>{}</caption>\n", html_escape(title)));
+        }
+        output.push_str("<tr>");
+        for cell in &self.header {
+            output.push_str(&format!("<th>{}</th>", html_escape(cell)));
+        }
+        output.push_str("</tr>\n");
+        for (i, row) in self.rows.iter().enumerate() {
+            output.push_str("<tr>");
+            for (j, cell) in row.iter().enumerate() {
+                match self.classes.get(&(i, j)) {
+                    Some(class) => output.push_str(&format!(
+                        "<td class=\"{}\">{}</td>",
+                        html_escape(class),
+                        html_escape(cell)
+                    )),
+                    None => output.push_str(&format!("<td>{}</td>", html_escape(cell))),
+                }
+            }
+            output.push_str("</tr>\n");
+        }
+        output.push_str("</table>\n");
+        output
+    }
+
+    /// Render the header and rows as CSV, quoting cells as needed.
+    pub fn to_csv(&self) -> String {
+        let mut writer = csv::WriterBuilder::new()
+            .flexible(true)
+            .from_writer(Vec::new());
+        // Writing to an in-memory buffer cannot fail.
+        let _ = writer.write_record(&self.header);
+        for row in &self.rows {
+            let _ = writer.write_record(row);
+        }
+        let bytes = writer.into_inner().unwrap_or_default();
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+}
+
+/// Format one Markdown table row, escaping pipes inside cells.
+fn markdown_row(cells: &[String]) -> String {
+    let escaped: Vec<String> = cells.iter().map(|c| c.replace('|', "\\|")).collect();
+    format!("| {} |\n", escaped.join(" | "))
+}
+
+/// Lowercase `text`, keeping letters and digits and joining the words with underscores.
+fn file_stem(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Escape text for inclusion in HTML.
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// One piece of a report: free text or a table.
+#[derive(Debug, Clone)]
+pub enum Block {
+    Text(String),
+    Table(Table),
+    /// A table with its own terminal rendering (e.g. a colored matrix); exports use the table.
+    Styled { terminal: String, table: Table },
+}
+
+/// Output of a command, kept structured so it can be exported faithfully.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub blocks: Vec<Block>,
+    /// Machine-readable form of the results, used for JSON export when present.
+    pub json: Option<serde_json::Value>,
+}
+
+impl Report {
+    /// Create an empty report.
+    pub fn new() -> Report {
+        Report::default()
+    }
+
+    /// Create a report holding only free text (e.g. an ASCII plot).
+    pub fn from_text(text: &str) -> Report {
+        let mut report = Report::new();
+        report.push_text(text);
+        report
+    }
+
+    /// Append free text, shown verbatim in the terminal.
+    pub fn push_text(&mut self, text: &str) {
+        self.blocks.push(Block::Text(text.to_string()));
+    }
+
+    /// Append a table.
+    pub fn push_table(&mut self, table: Table) {
+        self.blocks.push(Block::Table(table));
+    }
+
+    /// Append a table that is shown as `terminal` on screen but exported as `table`.
+    pub fn push_styled(&mut self, terminal: &str, table: Table) {
+        self.blocks.push(Block::Styled {
+            terminal: terminal.to_string(),
+            table,
+        });
+    }
+
+    /// Attach the structured data behind the report for JSON export.
+    pub fn set_json(&mut self, value: serde_json::Value) {
+        self.json = Some(value);
+    }
+
+    /// Append all blocks of another report.
+    pub fn append(&mut self, other: Report) {
+        self.blocks.extend(other.blocks);
+    }
+
+    /// Returns true if the report has no blocks.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Render the report for the terminal, drawing tables in `style`.
+    pub fn to_terminal(&self, style: TableStyle) -> String {
+        self.blocks
+            .iter()
+            .map(|block| match block {
+                Block::Text(text) => text.clone(),
+                Block::Table(table) => table.render(style),
+                Block::Styled { terminal, .. } => terminal.clone(),
+            })
+            .collect()
+    }
+
+    /// Tables of the report in order, including the export form of styled blocks.
+    fn tables(&self) -> impl Iterator<Item = &Table> {
+        self.blocks.iter().filter_map(|block| match block {
+            Block::Text(_) => None,
+            Block::Table(table) | Block::Styled { table, .. } => Some(table),
+        })
+    }
+
+    /// One CSV file per table, as `(file name, contents)` pairs in report order.
+    ///
+    /// Files are named after the table's name, else its title, else its position;
+    /// repeated names get a numeric suffix so no file overwrites another.
+    pub fn csv_files(&self) -> Vec<(String, String)> {
+        let mut files: Vec<(String, String)> = Vec::new();
+        for (i, table) in self.tables().enumerate() {
+            let stem = table
+                .name
+                .as_deref()
+                .or(table.title.as_deref())
+                .map(file_stem)
+                .filter(|stem| !stem.is_empty())
+                .unwrap_or_else(|| format!("table{}", i + 1));
+            let mut file_name = format!("{}.csv", stem);
+            let mut n = 1;
+            while files.iter().any(|(existing, _)| *existing == file_name) {
+                n += 1;
+                file_name = format!("{}_{}.csv", stem, n);
+            }
+            files.push((file_name, table.to_csv()));
+        }
+        files
+    }
+
+    /// Render the report's tables as CSV, separated by blank lines.
+    ///
+    /// Free text is dropped; a report without tables falls back to its terminal text.
+    pub fn to_csv(&self) -> String {
+        let sections: Vec<String> = self.tables().map(Table::to_csv).collect();
+        if sections.is_empty() {
+            return self.to_terminal(TableStyle::default());
+        }
+        sections.join("\n")
+    }
+
+    /// Render the report as Markdown: tables become pipe tables, text is kept as-is.
+    pub fn to_markdown(&self) -> String {
+        let sections: Vec<String> = self
+            .blocks
+            .iter()
+            .filter_map(|block| match block {
+                Block::Text(text) if text.trim().is_empty() => None,
+                Block::Text(text) => Some(format!("{}\n", text.trim())),
+                Block::Table(table) | Block::Styled { table, .. } => Some(table.to_markdown()),
+            })
+            .collect();
+        sections.join("\n")
+    }
+
+    /// Render the report as a standalone HTML document with an inline stylesheet.
+    pub fn to_html(&self) -> String {
+        let mut output = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>statsctl report</title>\n<style>\n{}\n</style>\n</head>\n<body>\n",
+            HTML_STYLE
+        );
+        for block in &self.blocks {
+            match block {
+                Block::Text(text) if text.trim().is_empty() => {}
+                Block::Text(text) => {
+                    output.push_str(&format!("<pre>{}</pre>\n", html_escape(text.trim())))
+                }
+                Block::Table(table) | Block::Styled { table, .. } => {
+                    output.push_str(&table.to_html())
+                }
+            }
+        }
+        output.push_str("</body>\n</html>\n");
+        output
+    }
+}
+
+/// Convert a report to a specific format for export.
+pub fn export(report: &Report, format: &str) -> String {
+    match format {
+        "json" => match &report.json {
+            Some(value) => serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string()),
+            // No structured data (e.g. plots): wrap the text as a simple JSON object
+            None => {
+                let text = report.to_terminal(TableStyle::default());
+                serde_json::json!({ "output": text }).to_string()
+            }
+        },
+        "csv" => report.to_csv(),
+        "html" => report.to_html(),
+        _ => report.to_markdown(),
+    }
+}
+
+/// Serialize each item as compact JSON on its own line (JSON Lines).
+///
+/// Non-finite floats become `null`, so every line is valid JSON.
+pub fn json_lines<T: Serialize>(items: &[T]) -> serde_json::Result<String> {
+    let mut output = String::new();
+    for item in items {
+        output.push_str(&serde_json::to_string(item)?);
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_table() -> Table {
+        let mut table = Table::new(&["Variable", "Missing"]).with_title("Missing Data Report");
+        table.push_row(vec!["age".to_string(), "0".to_string()]);
+        table.push_row(vec!["a|b".to_string(), "2".to_string()]);
+        table
+    }
+
+    #[test]
+    fn test_markdown_table_structure() {
+        let md = sample_table().to_markdown();
+        let lines: Vec<&str> = md.lines().collect();
+        assert_eq!(lines[0], "### Missing Data Report");
+        assert_eq!(lines[2], "| Variable | Missing |");
+        assert_eq!(lines[3], "| --- | --- |");
+        assert_eq!(lines[4], "| age | 0 |");
+        assert_eq!(lines[5], "| a\\|b | 2 |");
+    }
+
+    #[test]
+    fn test_terminal_render_keeps_title() {
+        let out = sample_table().render(TableStyle::Rounded);
+        assert!(out.starts_with("Missing Data Report:\n"));
+        assert!(out.contains('╭'));
+    }
+
+    #[test]
+    fn test_plain_style_has_no_box_drawing() {
+        let out = sample_table().render(TableStyle::Plain);
+        assert!(out.contains(" age      | 0 "));
+        // Box-drawing glyphs live in U+2500..U+257F
+        assert!(!out.chars().any(|c| ('\u{2500}'..='\u{257F}').contains(&c)));
+    }
+
+    #[test]
+    fn test_json_export_uses_structured_data() {
+        let mut report = Report::new();
+        report.push_table(sample_table());
+        report.set_json(serde_json::json!([{ "name": "age", "mean": f64::NAN }]));
+        let parsed: serde_json::Value = serde_json::from_str(&export(&report, "json")).unwrap();
+        assert_eq!(parsed[0]["name"], "age");
+        assert!(parsed[0]["mean"].is_null());
+    }
+
+    #[test]
+    fn test_json_lines_one_object_per_line_with_null_nan() {
+        #[derive(Serialize)]
+        struct Row {
+            name: &'static str,
+            mean: f64,
+        }
+        let items = [
+            Row {
+                name: "age",
+                mean: 34.5,
+            },
+            Row {
+                name: "empty",
+                mean: f64::NAN,
+            },
+        ];
+        let out = json_lines(&items).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], r#"{"name":"age","mean":34.5}"#);
+        assert_eq!(lines[1], r#"{"name":"empty","mean":null}"#);
+        for line in lines {
+            assert!(serde_json::from_str::<serde_json::Value>(line).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_json_export_falls_back_to_text() {
+        let report = Report::from_text("plot");
+        let parsed: serde_json::Value = serde_json::from_str(&export(&report, "json")).unwrap();
+        assert_eq!(parsed["output"], "plot");
+    }
+
+    #[test]
+    fn test_csv_export_quotes_cells_and_skips_text() {
+        let mut report = Report::new();
+        report.push_text("Intro\n");
+        let mut table = Table::new(&["Variable", "Top"]);
+        table.push_row(vec!["city".to_string(), "Bogota (3), Cali (2)".to_string()]);
+        report.push_table(table);
+        let csv = export(&report, "csv");
+        assert_eq!(csv, "Variable,Top\ncity,\"Bogota (3), Cali (2)\"\n");
+    }
+
+    #[test]
+    fn test_summary_csv_round_trip() {
+        let df = crate::reader::read_file("tests/data/sample.csv").unwrap();
+        let stats = crate::stats::describe_selected(&df, &["age", "income"]);
+        let mut report = Report::new();
+        report.push_table(crate::display::format_summary(
+            &stats,
+            crate::stats::BIMODALITY_THRESHOLD,
+            false,
+            false,
+            &crate::display::DisplayOptions::default(),
+        ));
+
+        let path = std::env::temp_dir().join(format!("statsctl_summary_{}.csv", std::process::id()));
+        std::fs::write(&path, export(&report, "csv")).unwrap();
+        let back = crate::reader::read_file(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(back.headers[0], "Variable");
+        assert_eq!(back.headers.len(), 10);
+        assert_eq!(back.nrows(), 2);
+        assert_eq!(back.rows[0][0], "age");
+        assert_eq!(back.rows[0][1], "30");
+    }
+
+    #[test]
+    fn test_csv_files_one_per_table() {
+        let mut report = Report::new();
+        report.push_table(Table::new(&["Variable"]).with_name("summary"));
+        report.push_text("\n\n");
+        report.push_table(sample_table());
+        report.push_table(sample_table());
+        report.push_table(Table::new(&["Value"]));
+
+        let files = report.csv_files();
+        let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "summary.csv",
+                "missing_data_report.csv",
+                "missing_data_report_2.csv",
+                "table4.csv"
+            ]
+        );
+        assert_eq!(files[0].1, "Variable\n");
+        assert!(files[1].1.starts_with("Variable,Missing\n"));
+    }
+
+    #[test]
+    fn test_styled_block_exports_table() {
+        let mut report = Report::new();
+        report.push_styled("\x1b[31mcolored\x1b[0m", sample_table());
+        assert!(report.to_terminal(TableStyle::Rounded).contains("colored"));
+        assert!(report.to_csv().starts_with("Variable,Missing\n"));
+        assert!(!report.to_markdown().contains("colored"));
+    }
+
+    #[test]
+    fn test_html_export_rows_and_classes() {
+        let mut table = sample_table();
+        table.set_class(1, 1, "strong");
+        let mut report = Report::new();
+        report.push_text("Note: <n> & more");
+        report.push_table(table);
+        let html = export(&report, "html");
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert_eq!(html.matches("<tr>").count(), 3);
+        assert_eq!(html.matches("<tr>").count(), html.matches("</tr>").count());
+        assert!(html.contains("<td class=\"strong\">2</td>"));
+        assert!(html.contains("<caption>Missing Data Report</caption>"));
+        assert!(html.contains("<pre>Note: &lt;n&gt; &amp; more</pre>"));
+    }
+
+    #[test]
+    fn test_report_markdown_mixes_text_and_tables() {
+        let mut report = Report::new();
+        report.push_table(sample_table());
+        report.push_text("\n12.50% of observations have at least one missing value");
+        let md = report.to_markdown();
+        assert!(md.contains("| --- | --- |"));
+        assert!(md.ends_with("12.50% of observations have at least one missing value\n"));
+        assert!(!md.contains('╭'));
+    }
+}
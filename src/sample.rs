@@ -0,0 +1,59 @@
+use anyhow::{bail, Result};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Resolve the number of rows to draw from `--n` or `--frac`.
+///
+/// Exactly one of the two must be given; `n` larger than the data is capped at `nrows`.
+pub fn sample_size(nrows: usize, n: Option<usize>, frac: Option<f64>) -> Result<usize> {
+    match (n, frac) {
+        (Some(_), Some(_)) => bail!("Use either --n or --frac, not both"),
+        (None, None) => bail!("Specify the sample size with --n or --frac"),
+        (Some(n), None) => Ok(n.min(nrows)),
+        (None, Some(frac)) => {
+            if !(frac > 0.0 && frac <= 1.0) {
+                bail!("--frac must be greater than 0 and at most 1");
+            }
+            Ok(((nrows as f64 * frac).round() as usize).min(nrows))
+        }
+    }
+}
+
+/// Draw `n` distinct row indices out of `nrows`, returned in their original order.
+///
+/// The same `seed` always selects the same rows; without one the RNG is seeded
+/// from the operating system.
+pub fn sample_indices(nrows: usize, n: usize, seed: Option<u64>) -> Vec<usize> {
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let mut indices = rand::seq::index::sample(&mut rng, nrows, n.min(nrows)).into_vec();
+    indices.sort_unstable();
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_size() {
+        assert_eq!(sample_size(30, Some(10), None).unwrap(), 10);
+        assert_eq!(sample_size(30, Some(100), None).unwrap(), 30);
+        assert_eq!(sample_size(30, None, Some(0.5)).unwrap(), 15);
+        assert!(sample_size(30, Some(10), Some(0.5)).is_err());
+        assert!(sample_size(30, None, None).is_err());
+        assert!(sample_size(30, None, Some(1.5)).is_err());
+    }
+
+    #[test]
+    fn test_sample_indices_reproducible() {
+        let a = sample_indices(1000, 50, Some(42));
+        let b = sample_indices(1000, 50, Some(42));
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 50);
+        assert!(a.windows(2).all(|w| w[0] < w[1]));
+        assert_ne!(a, sample_indices(1000, 50, Some(7)));
+    }
+}
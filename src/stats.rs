@@ -1,21 +1,61 @@
 use crate::reader::DataFrame;
+use crate::sketch::{self, HyperLogLog};
 use crate::types;
 use crate::utils::is_missing;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
 
 /// Descriptive statistics for a single numeric column.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 #[allow(dead_code)]
 pub struct DescriptiveStats {
     pub name: String,
     pub count: usize,
     pub missing: usize,
+    /// Values equal to zero.
+    pub zeros: usize,
+    /// Values below zero.
+    pub negatives: usize,
     pub mean: f64,
+    #[serde(rename = "std")]
     pub std_dev: f64,
     pub min: f64,
     pub q1: f64,
     pub median: f64,
     pub q3: f64,
     pub max: f64,
+    /// Interquartile range, Q3 - Q1 (`NaN` when quartiles are skipped).
+    pub iqr: f64,
+    /// Max - min.
+    pub range: f64,
+    /// Sample skewness (`NaN` when quartiles are skipped).
+    pub skewness: f64,
+    /// Sample excess kurtosis (`NaN` when quartiles are skipped).
+    pub kurtosis: f64,
+    /// Symmetric trimmed mean, when requested with `--trim`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trimmed_mean: Option<f64>,
+    /// Bootstrap confidence interval for the median, when requested with
+    /// `--bootstrap-median`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub median_ci: Option<(f64, f64)>,
+    /// Geometric mean, when requested with `--means`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geometric_mean: Option<f64>,
+    /// Harmonic mean, when requested with `--means`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub harmonic_mean: Option<f64>,
+    /// Jarque–Bera normality p-value, when requested with `--normality`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jb_p: Option<f64>,
+}
+
+impl DescriptiveStats {
+    /// Bimodality coefficient from this column's skewness and kurtosis.
+    pub fn bimodality(&self) -> f64 {
+        bimodality_coefficient(self.count, self.skewness, self.kurtosis)
+    }
 }
 
 /// Compute the mean of a slice.
@@ -36,6 +76,166 @@ pub fn std_dev(data: &[f64]) -> f64 {
     variance.sqrt()
 }
 
+/// Median of unsorted data (`NaN` for an empty slice).
+pub fn median(data: &[f64]) -> f64 {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    percentile(&sorted, 50.0)
+}
+
+/// Number of values dropped from each end when trimming `proportion` of `n` values.
+pub fn trim_count(n: usize, proportion: f64) -> usize {
+    (n as f64 * proportion).floor() as usize
+}
+
+/// Mean of `sorted` after dropping the lowest and highest `proportion` of values.
+///
+/// `floor(n * proportion)` values are dropped from each end. Returns `NaN` when
+/// that leaves nothing to average (including `proportion >= 0.5`).
+pub fn trimmed_mean(sorted: &[f64], proportion: f64) -> f64 {
+    let k = trim_count(sorted.len(), proportion);
+    if 2 * k >= sorted.len() {
+        return f64::NAN;
+    }
+    mean(&sorted[k..sorted.len() - k])
+}
+
+/// Clip values below the `proportion` percentile and above the `1 - proportion`
+/// percentile to those bounds, keeping their original order.
+pub fn winsorize(values: &[f64], proportion: f64) -> Vec<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let lower = percentile(&sorted, proportion * 100.0);
+    let upper = percentile(&sorted, (1.0 - proportion) * 100.0);
+    values.iter().map(|v| v.max(lower).min(upper)).collect()
+}
+
+/// Winsorize the numeric cells of `col` in place, leaving missing and unparseable
+/// cells untouched. Returns `false` if the column does not exist.
+pub fn winsorize_column(df: &mut DataFrame, col: &str, proportion: f64) -> bool {
+    let (Some(idx), Some(values)) = (df.col_index(col), df.numeric_column(col)) else {
+        return false;
+    };
+    let present: Vec<f64> = values.iter().flatten().copied().collect();
+    let mut clipped = winsorize(&present, proportion).into_iter();
+    for (row, value) in df.rows.iter_mut().zip(&values) {
+        if value.is_some() {
+            if let Some(x) = clipped.next() {
+                row[idx] = x.to_string();
+            }
+        }
+    }
+    true
+}
+
+/// Alternative means for positive-valued data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MeanKind {
+    Geometric,
+    Harmonic,
+}
+
+impl MeanKind {
+    /// Parse "geometric" or "harmonic".
+    pub fn parse(value: &str) -> Option<MeanKind> {
+        match value {
+            "geometric" => Some(MeanKind::Geometric),
+            "harmonic" => Some(MeanKind::Harmonic),
+            _ => None,
+        }
+    }
+
+    /// This kind of mean of `data`.
+    pub fn compute(self, data: &[f64]) -> f64 {
+        match self {
+            MeanKind::Geometric => geometric_mean(data),
+            MeanKind::Harmonic => harmonic_mean(data),
+        }
+    }
+}
+
+/// Geometric mean, computed in log space.
+///
+/// Returns `NaN` for an empty slice or when any value is zero or negative.
+pub fn geometric_mean(data: &[f64]) -> f64 {
+    if data.is_empty() || data.iter().any(|&x| x <= 0.0) {
+        return f64::NAN;
+    }
+    (data.iter().map(|x| x.ln()).sum::<f64>() / data.len() as f64).exp()
+}
+
+/// Harmonic mean.
+///
+/// Returns `NaN` for an empty slice or when any value is zero or negative.
+pub fn harmonic_mean(data: &[f64]) -> f64 {
+    if data.is_empty() || data.iter().any(|&x| x <= 0.0) {
+        return f64::NAN;
+    }
+    data.len() as f64 / data.iter().map(|x| 1.0 / x).sum::<f64>()
+}
+
+/// Second, third and fourth central moments (divided by n).
+pub fn central_moments(data: &[f64]) -> (f64, f64, f64) {
+    let m = mean(data);
+    let n = data.len() as f64;
+    let (mut m2, mut m3, mut m4) = (0.0, 0.0, 0.0);
+    for x in data {
+        let d = x - m;
+        m2 += d * d;
+        m3 += d * d * d;
+        m4 += d * d * d * d;
+    }
+    (m2 / n, m3 / n, m4 / n)
+}
+
+/// Sample skewness with the small-sample adjustment (as in Excel's SKEW).
+///
+/// Returns `NaN` for fewer than 3 values or a constant column.
+pub fn skewness(data: &[f64]) -> f64 {
+    let n = data.len() as f64;
+    if data.len() < 3 {
+        return f64::NAN;
+    }
+    let (m2, m3, _) = central_moments(data);
+    if m2 == 0.0 {
+        return f64::NAN;
+    }
+    (n * (n - 1.0)).sqrt() / (n - 2.0) * m3 / m2.powf(1.5)
+}
+
+/// Sample excess kurtosis with the small-sample adjustment (as in Excel's KURT).
+///
+/// Returns `NaN` for fewer than 4 values or a constant column.
+pub fn kurtosis(data: &[f64]) -> f64 {
+    let n = data.len() as f64;
+    if data.len() < 4 {
+        return f64::NAN;
+    }
+    let (m2, _, m4) = central_moments(data);
+    if m2 == 0.0 {
+        return f64::NAN;
+    }
+    let g2 = m4 / (m2 * m2) - 3.0;
+    ((n + 1.0) * g2 + 6.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0))
+}
+
+/// Rule-of-thumb cutoff for the bimodality coefficient: the value for a uniform
+/// distribution. Larger values hint at a mix of populations.
+pub const BIMODALITY_THRESHOLD: f64 = 0.555;
+
+/// Sarle's bimodality coefficient from sample skewness and excess kurtosis.
+///
+/// Ranges over (0, 1]; a normal distribution gives 1/3. Returns `NaN` for
+/// fewer than 4 values.
+pub fn bimodality_coefficient(n: usize, skewness: f64, kurtosis: f64) -> f64 {
+    if n < 4 {
+        return f64::NAN;
+    }
+    let n = n as f64;
+    let correction = 3.0 * (n - 1.0).powi(2) / ((n - 2.0) * (n - 3.0));
+    (skewness * skewness + 1.0) / (kurtosis + correction)
+}
+
 /// Compute a percentile using linear interpolation.
 pub fn percentile(sorted: &[f64], p: f64) -> f64 {
     if sorted.is_empty() {
@@ -56,8 +256,476 @@ pub fn percentile(sorted: &[f64], p: f64) -> f64 {
     }
 }
 
+/// 1-based ranks of `values` in their original order, with tied values sharing
+/// the average of the ranks they span: `[10, 20, 20, 30]` ranks as `[1, 2.5, 2.5, 4]`.
+///
+/// This is the ranking behind rank-based statistics such as Spearman's rho.
+pub fn average_ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| {
+        values[a]
+            .partial_cmp(&values[b])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        // Sorted positions start..end would hold ranks start + 1 ..= end
+        let rank = (start + 1 + end) as f64 / 2.0;
+        for &i in &order[start..end] {
+            ranks[i] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// Percentile rank of `value`: the percentage of values at or below it.
+///
+/// Values below the minimum rank 0 and values at or above the maximum rank 100.
+/// Returns `NaN` for an empty slice.
+pub fn percentile_rank(sorted: &[f64], value: f64) -> f64 {
+    if sorted.is_empty() || value.is_nan() {
+        return f64::NAN;
+    }
+    let at_or_below = sorted.partition_point(|&v| v <= value);
+    at_or_below as f64 / sorted.len() as f64 * 100.0
+}
+
+/// Confidence level of the bootstrap interval reported by `summary --bootstrap-median`.
+pub const BOOTSTRAP_LEVEL: f64 = 0.95;
+
+/// Percentile bootstrap confidence interval for `statistic` of `values`.
+///
+/// Draws `iterations` resamples of `values` with replacement and returns the
+/// `(1 - level) / 2` and `(1 + level) / 2` percentiles of the statistic across
+/// them. The same `seed` always gives the same interval; without one the RNG is
+/// seeded from the operating system. Returns `NaN` bounds for empty input or zero
+/// iterations.
+pub fn bootstrap_ci<F>(
+    values: &[f64],
+    statistic: F,
+    iterations: usize,
+    level: f64,
+    seed: Option<u64>,
+) -> (f64, f64)
+where
+    F: Fn(&[f64]) -> f64,
+{
+    if values.is_empty() || iterations == 0 {
+        return (f64::NAN, f64::NAN);
+    }
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let n = values.len();
+    let mut resample = vec![0.0; n];
+    let mut estimates: Vec<f64> = (0..iterations)
+        .map(|_| {
+            for slot in resample.iter_mut() {
+                *slot = values[rng.gen_range(0..n)];
+            }
+            statistic(&resample)
+        })
+        .collect();
+    estimates.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let tail = (1.0 - level) / 2.0 * 100.0;
+    (
+        percentile(&estimates, tail),
+        percentile(&estimates, 100.0 - tail),
+    )
+}
+
+/// Inverse of the standard normal CDF (quantile function).
+///
+/// Uses Acklam's rational approximation (relative error below 1.2e-9).
+/// Returns `NaN` outside the open interval (0, 1).
+pub fn normal_quantile(p: f64) -> f64 {
+    if p.is_nan() || p <= 0.0 || p >= 1.0 {
+        return f64::NAN;
+    }
+
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239e0,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838e0,
+        -2.549732539343734e0,
+        4.374664141464968e0,
+        2.938163982698783e0,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996e0,
+        3.754408661907416e0,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    if p < P_LOW {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -normal_quantile(1.0 - p)
+    }
+}
+
+/// Natural log of the gamma function for `x > 0` (Lanczos approximation).
+fn ln_gamma(x: f64) -> f64 {
+    const G: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.1208650973866179e-2,
+        -0.5395239384953e-5,
+    ];
+    let tmp = x + 5.5 - (x + 0.5) * (x + 5.5).ln();
+    let mut ser = 1.000000000190015;
+    for (k, g) in G.iter().enumerate() {
+        ser += g / (x + 1.0 + k as f64);
+    }
+    -tmp + (2.5066282746310005 * ser / x).ln()
+}
+
+/// Continued fraction for the regularized incomplete beta function.
+fn beta_cf(a: f64, b: f64, x: f64) -> f64 {
+    const MAX_ITER: usize = 200;
+    const EPS: f64 = 1e-14;
+    const TINY: f64 = 1e-300;
+
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+
+    for m in 1..=MAX_ITER {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+
+        let aa = m * (b - m) * x / ((a + m2 - 1.0) * (a + m2));
+        d = 1.0 + aa * d;
+        d = if d.abs() < TINY { TINY } else { d };
+        c = 1.0 + aa / c;
+        c = if c.abs() < TINY { TINY } else { c };
+        d = 1.0 / d;
+        h *= d * c;
+
+        let aa = -(a + m) * (a + b + m) * x / ((a + m2) * (a + m2 + 1.0));
+        d = 1.0 + aa * d;
+        d = if d.abs() < TINY { TINY } else { d };
+        c = 1.0 + aa / c;
+        c = if c.abs() < TINY { TINY } else { c };
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < EPS {
+            break;
+        }
+    }
+    h
+}
+
+/// Regularized incomplete beta function I_x(a, b).
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_cf(a, b, x) / a
+    } else {
+        1.0 - front * beta_cf(b, a, 1.0 - x) / b
+    }
+}
+
+/// Two-sided p-value of a Student's t statistic with `df` degrees of freedom.
+pub fn t_test_p_value(t: f64, df: f64) -> f64 {
+    if t.is_nan() || df <= 0.0 {
+        return f64::NAN;
+    }
+    if t.is_infinite() {
+        return 0.0;
+    }
+    incomplete_beta(df / 2.0, 0.5, df / (df + t * t))
+}
+
+/// Upper-tail p-value of an F statistic with `df1` and `df2` degrees of freedom.
+pub fn f_test_p_value(f: f64, df1: f64, df2: f64) -> f64 {
+    if f.is_nan() || df1 <= 0.0 || df2 <= 0.0 {
+        return f64::NAN;
+    }
+    if f.is_infinite() {
+        return 0.0;
+    }
+    incomplete_beta(df2 / 2.0, df1 / 2.0, df2 / (df2 + df1 * f.max(0.0)))
+}
+
+/// Two-sided p-value for a Pearson correlation `r` over `n` complete pairs.
+///
+/// Tests r against zero with `t = r * sqrt((n - 2) / (1 - r^2))`, which follows a
+/// t-distribution with n - 2 degrees of freedom. Returns `NaN` for fewer than 3 pairs.
+pub fn correlation_p_value(r: f64, n: usize) -> f64 {
+    if n < 3 || r.is_nan() {
+        return f64::NAN;
+    }
+    let df = (n - 2) as f64;
+    let denom = 1.0 - r * r;
+    if denom <= 0.0 {
+        return 0.0;
+    }
+    t_test_p_value(r * (df / denom).sqrt(), df)
+}
+
+/// Streaming mean, variance, min and max using Welford's online algorithm.
+///
+/// Needs constant memory and stays accurate for values with a large common offset,
+/// where summing squares in two passes loses precision.
+#[derive(Debug, Clone, Copy)]
+pub struct RunningStats {
+    count: usize,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+    zeros: usize,
+    negatives: usize,
+}
+
+impl Default for RunningStats {
+    fn default() -> Self {
+        RunningStats {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            zeros: 0,
+            negatives: 0,
+        }
+    }
+}
+
+impl RunningStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add one observation.
+    pub fn push(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+        if x == 0.0 {
+            self.zeros += 1;
+        } else if x < 0.0 {
+            self.negatives += 1;
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Number of observations equal to zero.
+    pub fn zeros(&self) -> usize {
+        self.zeros
+    }
+
+    /// Number of observations below zero.
+    pub fn negatives(&self) -> usize {
+        self.negatives
+    }
+
+    /// Mean of the observations (`NaN` if there are none).
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 {
+            f64::NAN
+        } else {
+            self.mean
+        }
+    }
+
+    /// Sample variance; 0 for fewer than two observations, like `std_dev`.
+    pub fn variance(&self) -> f64 {
+        if self.count < 2 {
+            0.0
+        } else {
+            self.m2 / (self.count - 1) as f64
+        }
+    }
+
+    /// Sample standard deviation.
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// Smallest observation (`NaN` if there are none).
+    pub fn min(&self) -> f64 {
+        if self.count == 0 {
+            f64::NAN
+        } else {
+            self.min
+        }
+    }
+
+    /// Largest observation (`NaN` if there are none).
+    pub fn max(&self) -> f64 {
+        if self.count == 0 {
+            f64::NAN
+        } else {
+            self.max
+        }
+    }
+}
+
+/// Values kept per column by `summary --approx` unless `--reservoir-size` is given.
+pub const DEFAULT_RESERVOIR_SIZE: usize = 10_000;
+
+/// Seed of the reservoir's RNG, fixed so repeated runs report the same estimates.
+const RESERVOIR_SEED: u64 = 0x5eed;
+
+/// Uniform random sample of a stream of values (Vitter's algorithm R).
+///
+/// Keeps at most `capacity` values in constant memory. Until more than that have
+/// been pushed it holds every value, so quantiles of short streams are exact.
+#[derive(Debug, Clone)]
+pub struct Reservoir {
+    capacity: usize,
+    seen: usize,
+    sample: Vec<f64>,
+    rng: StdRng,
+}
+
+impl Reservoir {
+    pub fn new(capacity: usize) -> Self {
+        Reservoir {
+            capacity,
+            seen: 0,
+            sample: Vec::new(),
+            rng: StdRng::seed_from_u64(RESERVOIR_SEED),
+        }
+    }
+
+    /// Offer one value; once the reservoir is full it replaces a random kept value
+    /// with probability `capacity / seen`.
+    pub fn push(&mut self, x: f64) {
+        self.seen += 1;
+        if self.sample.len() < self.capacity {
+            self.sample.push(x);
+        } else {
+            let slot = self.rng.gen_range(0..self.seen);
+            if slot < self.capacity {
+                self.sample[slot] = x;
+            }
+        }
+    }
+
+    /// True while every value pushed so far is kept.
+    pub fn is_exact(&self) -> bool {
+        self.seen <= self.capacity
+    }
+
+    /// Q1, median and Q3 of the kept values, estimating those of the whole stream.
+    pub fn quartiles(&self) -> (f64, f64, f64) {
+        let mut sorted = self.sample.clone();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        (
+            percentile(&sorted, 25.0),
+            percentile(&sorted, 50.0),
+            percentile(&sorted, 75.0),
+        )
+    }
+}
+
 /// Compute descriptive statistics for a column.
 pub fn describe(df: &DataFrame, col_name: &str) -> Option<DescriptiveStats> {
+    describe_with(df, col_name, true)
+}
+
+/// Compute descriptive statistics, optionally skipping the quartiles.
+///
+/// Without quartiles the column is never sorted or copied: count, mean, std, min
+/// and max come from a single `RunningStats` pass and Q1/median/Q3 are `NaN`.
+pub fn describe_with(df: &DataFrame, col_name: &str, quantiles: bool) -> Option<DescriptiveStats> {
+    if !quantiles {
+        let idx = df.col_index(col_name)?;
+        let mut running = RunningStats::new();
+        let mut missing = 0;
+        for row in &df.rows {
+            let val = row[idx].trim();
+            match val.parse::<f64>() {
+                Ok(x) if !is_missing(val) => running.push(x),
+                _ => missing += 1,
+            }
+        }
+        return Some(DescriptiveStats {
+            name: col_name.to_string(),
+            count: running.count(),
+            missing,
+            zeros: running.zeros(),
+            negatives: running.negatives(),
+            mean: running.mean(),
+            std_dev: if running.count() == 0 {
+                f64::NAN
+            } else {
+                running.std_dev()
+            },
+            min: running.min(),
+            q1: f64::NAN,
+            median: f64::NAN,
+            q3: f64::NAN,
+            max: running.max(),
+            iqr: f64::NAN,
+            range: running.max() - running.min(),
+            skewness: f64::NAN,
+            kurtosis: f64::NAN,
+            trimmed_mean: None,
+            median_ci: None,
+            geometric_mean: None,
+            harmonic_mean: None,
+            jb_p: None,
+        });
+    }
+
     let all_values = df.numeric_column(col_name)?;
     let missing = all_values.iter().filter(|v| v.is_none()).count();
     let mut values: Vec<f64> = all_values.into_iter().flatten().collect();
@@ -67,6 +735,8 @@ pub fn describe(df: &DataFrame, col_name: &str) -> Option<DescriptiveStats> {
             name: col_name.to_string(),
             count: 0,
             missing,
+            zeros: 0,
+            negatives: 0,
             mean: f64::NAN,
             std_dev: f64::NAN,
             min: f64::NAN,
@@ -74,22 +744,50 @@ pub fn describe(df: &DataFrame, col_name: &str) -> Option<DescriptiveStats> {
             median: f64::NAN,
             q3: f64::NAN,
             max: f64::NAN,
+            iqr: f64::NAN,
+            range: f64::NAN,
+            skewness: f64::NAN,
+            kurtosis: f64::NAN,
+            trimmed_mean: None,
+            median_ci: None,
+            geometric_mean: None,
+            harmonic_mean: None,
+            jb_p: None,
         });
     }
 
     values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
+    let mut running = RunningStats::new();
+    values.iter().for_each(|&x| running.push(x));
+
+    let min = values[0];
+    let max = values[values.len() - 1];
+    let q1 = percentile(&values, 25.0);
+    let q3 = percentile(&values, 75.0);
+
     Some(DescriptiveStats {
         name: col_name.to_string(),
         count: values.len(),
         missing,
-        mean: mean(&values),
-        std_dev: std_dev(&values),
-        min: values[0],
-        q1: percentile(&values, 25.0),
+        zeros: running.zeros(),
+        negatives: running.negatives(),
+        mean: running.mean(),
+        std_dev: running.std_dev(),
+        min,
+        q1,
         median: percentile(&values, 50.0),
-        q3: percentile(&values, 75.0),
-        max: *values.last().unwrap(),
+        q3,
+        max,
+        iqr: q3 - q1,
+        range: max - min,
+        skewness: skewness(&values),
+        kurtosis: kurtosis(&values),
+        trimmed_mean: None,
+        median_ci: None,
+        geometric_mean: None,
+        harmonic_mean: None,
+        jb_p: None,
     })
 }
 
@@ -110,18 +808,262 @@ pub fn describe_selected(df: &DataFrame, columns: &[&str]) -> Vec<DescriptiveSta
         .collect()
 }
 
+/// Statistics the summary table can be sorted by.
+pub const SORT_KEYS: [&str; 12] = [
+    "name", "count", "missing", "mean", "std", "min", "q1", "median", "q3", "max", "iqr", "range",
+];
+
+/// Value of the statistic named `key` (one of `SORT_KEYS` except "name").
+fn stat_value(s: &DescriptiveStats, key: &str) -> Option<f64> {
+    match key {
+        "count" => Some(s.count as f64),
+        "missing" => Some(s.missing as f64),
+        "mean" => Some(s.mean),
+        "std" => Some(s.std_dev),
+        "min" => Some(s.min),
+        "q1" => Some(s.q1),
+        "median" => Some(s.median),
+        "q3" => Some(s.q3),
+        "max" => Some(s.max),
+        "iqr" => Some(s.iqr),
+        "range" => Some(s.range),
+        _ => None,
+    }
+}
+
+/// Sort summary rows by a statistic (see `SORT_KEYS`).
+///
+/// NaN values always sort last, in either direction, and ties keep their original
+/// order. Returns false if `key` is not a known statistic.
+pub fn sort_stats(stats: &mut [DescriptiveStats], key: &str, descending: bool) -> bool {
+    if !SORT_KEYS.contains(&key) {
+        return false;
+    }
+
+    let direction = |ord: std::cmp::Ordering| if descending { ord.reverse() } else { ord };
+
+    if key == "name" {
+        stats.sort_by(|a, b| direction(a.name.cmp(&b.name)));
+        return true;
+    }
+
+    stats.sort_by(|a, b| {
+        let va = stat_value(a, key).unwrap_or(f64::NAN);
+        let vb = stat_value(b, key).unwrap_or(f64::NAN);
+        match (va.is_nan(), vb.is_nan()) {
+            (true, true) => std::cmp::Ordering::Equal,
+            (true, false) => std::cmp::Ordering::Greater,
+            (false, true) => std::cmp::Ordering::Less,
+            (false, false) => direction(va.partial_cmp(&vb).unwrap()),
+        }
+    });
+    true
+}
+
 /// Categorical summary: value counts for a column.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CategoricalSummary {
     pub name: String,
     pub total: usize,
     pub missing: usize,
     pub unique: usize,
     pub top_values: Vec<(String, usize)>,
+    /// Shannon entropy of the value distribution, in bits.
+    pub entropy: f64,
+    /// Entropy over its maximum `log2(unique)`: 0 for one level, 1 for equally common levels.
+    pub normalized_entropy: f64,
+    /// Chance that two values drawn at random differ (see `gini_impurity`).
+    pub gini: f64,
+    /// Each missing-value representation as written, with its count (see `missing_tokens`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub missing_tokens: Option<Vec<(String, usize)>>,
 }
 
 /// Compute categorical summary for a column.
 pub fn categorical_summary(df: &DataFrame, col_name: &str) -> Option<CategoricalSummary> {
+    value_counts(df, col_name).map(summarize_counts)
+}
+
+/// Summaries of every column whose inferred type is not numeric.
+pub fn categorical_summaries(df: &DataFrame) -> Vec<CategoricalSummary> {
+    types::infer_types(df)
+        .iter()
+        .filter(|t| t.col_type != types::ColumnType::Numeric)
+        .filter_map(|t| categorical_summary(df, &t.name))
+        .collect()
+}
+
+/// Categorical summary whose distinct count is a HyperLogLog estimate.
+///
+/// Values are not tallied, so memory stays fixed on near-unique columns; top
+/// values are left empty and the diversity measures are `NaN`.
+pub fn approx_categorical_summary(df: &DataFrame, col_name: &str) -> Option<CategoricalSummary> {
+    let values = df.column(col_name)?;
+    let mut missing = 0usize;
+    let mut sketch = HyperLogLog::new(sketch::DEFAULT_PRECISION);
+    for val in &values {
+        let v = val.trim();
+        if is_missing(v) {
+            missing += 1;
+        } else {
+            sketch.insert(v);
+        }
+    }
+
+    Some(sketched_summary(col_name, values.len(), missing, &sketch))
+}
+
+/// Categorical summary of a column whose distinct values were only fed to `sketch`.
+///
+/// Without counts there are no top values, and entropy and Gini impurity are `NaN`.
+pub fn sketched_summary(
+    name: &str,
+    total: usize,
+    missing: usize,
+    sketch: &HyperLogLog,
+) -> CategoricalSummary {
+    CategoricalSummary {
+        name: name.to_string(),
+        total,
+        missing,
+        unique: sketch.estimate().round() as usize,
+        top_values: Vec::new(),
+        entropy: f64::NAN,
+        normalized_entropy: f64::NAN,
+        gini: f64::NAN,
+        missing_tokens: None,
+    }
+}
+
+/// Like `categorical_summaries`, with estimated distinct counts (see `approx_categorical_summary`).
+///
+/// Columns are classified with `types::column_type`, which never collects their
+/// distinct values.
+pub fn approx_categorical_summaries(df: &DataFrame) -> Vec<CategoricalSummary> {
+    df.headers
+        .iter()
+        .filter(|h| types::column_type(df, h).is_some_and(|t| t != types::ColumnType::Numeric))
+        .filter_map(|h| approx_categorical_summary(df, h))
+        .collect()
+}
+
+/// Categorical summary of already counted values, keeping the ten most frequent.
+pub fn summarize_counts(vc: ValueCounts) -> CategoricalSummary {
+    let unique = vc.counts.len();
+    let counts: Vec<usize> = vc.counts.iter().map(|(_, c)| *c).collect();
+    let entropy = entropy(&counts);
+    let mut top_values = vc.counts;
+    top_values.truncate(10);
+
+    CategoricalSummary {
+        name: vc.name,
+        total: vc.total,
+        missing: vc.missing,
+        unique,
+        top_values,
+        entropy,
+        normalized_entropy: normalized_entropy(entropy, unique),
+        gini: gini_impurity(&counts),
+        missing_tokens: None,
+    }
+}
+
+/// Shannon entropy, in bits, of the distribution given by per-level `counts`.
+///
+/// Zero for a single level; `NaN` when there are no observations.
+pub fn entropy(counts: &[usize]) -> f64 {
+    let total: usize = counts.iter().sum();
+    if total == 0 {
+        return f64::NAN;
+    }
+    let total = total as f64;
+    counts
+        .iter()
+        .filter(|&&c| c > 0)
+        .map(|&c| {
+            let c = c as f64;
+            c / total * (total / c).log2()
+        })
+        .sum()
+}
+
+/// Gini impurity of per-level `counts`: one minus the sum of squared proportions.
+///
+/// Zero for a single level, approaching 1 as values spread over many levels;
+/// `NaN` when there are no observations.
+pub fn gini_impurity(counts: &[usize]) -> f64 {
+    let total: usize = counts.iter().sum();
+    if total == 0 {
+        return f64::NAN;
+    }
+    let total = total as f64;
+    1.0 - counts
+        .iter()
+        .map(|&c| (c as f64 / total).powi(2))
+        .sum::<f64>()
+}
+
+/// Entropy on a 0–1 scale, relative to `levels` equally common values.
+///
+/// A single level has no diversity to compare, so it scores 0.
+fn normalized_entropy(entropy: f64, levels: usize) -> f64 {
+    if levels <= 1 {
+        return if entropy.is_nan() { f64::NAN } else { 0.0 };
+    }
+    entropy / (levels as f64).log2()
+}
+
+/// Frequency of each distinct non-missing value in a column.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValueCounts {
+    pub name: String,
+    pub total: usize,
+    pub missing: usize,
+    /// (value, count) pairs, most frequent first with ties ordered by value.
+    /// Binned counts are in bin order instead.
+    pub counts: Vec<(String, usize)>,
+    /// Whether the values are equal-width bins of a numeric column.
+    #[serde(skip)]
+    pub binned: bool,
+}
+
+impl ValueCounts {
+    /// Order raw per-value counts, most frequent first.
+    pub fn new(
+        name: &str,
+        total: usize,
+        missing: usize,
+        counts: std::collections::HashMap<String, usize>,
+    ) -> Self {
+        let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ValueCounts {
+            name: name.to_string(),
+            total,
+            missing,
+            counts,
+            binned: false,
+        }
+    }
+
+    /// Reorder the counts by value: numerically if every value is a number,
+    /// otherwise as strings.
+    pub fn sort_by_value(&mut self) {
+        let numbers: Option<Vec<f64>> = self.counts.iter().map(|(v, _)| v.parse().ok()).collect();
+        match numbers {
+            Some(numbers) => {
+                let mut keyed: Vec<(f64, (String, usize))> =
+                    numbers.into_iter().zip(self.counts.drain(..)).collect();
+                keyed.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+                self.counts = keyed.into_iter().map(|(_, c)| c).collect();
+            }
+            None => self.counts.sort_by(|a, b| a.0.cmp(&b.0)),
+        }
+    }
+}
+
+/// Count every distinct non-missing value of a column.
+pub fn value_counts(df: &DataFrame, col_name: &str) -> Option<ValueCounts> {
     let values = df.column(col_name)?;
     let total = values.len();
     let mut missing = 0usize;
@@ -136,17 +1078,85 @@ pub fn categorical_summary(df: &DataFrame, col_name: &str) -> Option<Categorical
         }
     }
 
-    let unique = counts.len();
-    let mut top_values: Vec<(String, usize)> = counts.into_iter().collect();
-    top_values.sort_by(|a, b| b.1.cmp(&a.1));
-    top_values.truncate(10);
+    Some(ValueCounts::new(col_name, total, missing, counts))
+}
 
-    Some(CategoricalSummary {
+/// Count each missing-value representation in a column as it is written.
+///
+/// Tokens are trimmed but otherwise kept apart, so `NA`, `na` and empty cells are
+/// tallied separately. Most frequent first, ties ordered by token.
+pub fn missing_tokens(df: &DataFrame, col_name: &str) -> Option<Vec<(String, usize)>> {
+    let values = df.column(col_name)?;
+    let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    for val in &values {
+        let v = val.trim();
+        if is_missing(v) {
+            *counts.entry(v.to_string()).or_insert(0) += 1;
+        }
+    }
+    Some(ValueCounts::new(col_name, values.len(), 0, counts).counts)
+}
+
+/// Count sorted values into `num_bins` equal-width bins spanning their range.
+///
+/// Returns the bin width and the count per bin; the maximum lands in the last bin.
+pub fn bin_counts(sorted: &[f64], num_bins: usize) -> (f64, Vec<usize>) {
+    let mut bins = vec![0usize; num_bins];
+    let (min_val, max_val) = match (sorted.first(), sorted.last()) {
+        (Some(&lo), Some(&hi)) => (lo, hi),
+        _ => return (1.0, bins),
+    };
+
+    let range = max_val - min_val;
+    let bin_width = if range > 0.0 {
+        range / num_bins as f64
+    } else {
+        1.0
+    };
+
+    for &v in sorted {
+        let idx = ((v - min_val) / bin_width).floor() as usize;
+        bins[idx.min(num_bins - 1)] += 1;
+    }
+
+    (bin_width, bins)
+}
+
+/// Value counts of a numeric column bucketed into `num_bins` equal-width bins.
+///
+/// Bins keep their natural order and are labeled by `label(lower, upper)`;
+/// non-numeric values count as missing.
+pub fn binned_value_counts(
+    df: &DataFrame,
+    col_name: &str,
+    num_bins: usize,
+    label: impl Fn(f64, f64) -> String,
+) -> Option<ValueCounts> {
+    let mut values = df.valid_numeric_column(col_name)?;
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let total = df.nrows();
+    let missing = total - values.len();
+    let counts = match values.first() {
+        Some(&min_val) => {
+            let (bin_width, bins) = bin_counts(&values, num_bins.max(1));
+            bins.into_iter()
+                .enumerate()
+                .map(|(i, count)| {
+                    let lower = min_val + i as f64 * bin_width;
+                    (label(lower, lower + bin_width), count)
+                })
+                .collect()
+        }
+        None => Vec::new(),
+    };
+
+    Some(ValueCounts {
         name: col_name.to_string(),
         total,
         missing,
-        unique,
-        top_values,
+        counts,
+        binned: true,
     })
 }
 
@@ -155,6 +1165,129 @@ mod tests {
     use super::*;
     use crate::reader;
 
+    fn stats_with_std(name: &str, std_dev: f64) -> DescriptiveStats {
+        DescriptiveStats {
+            name: name.to_string(),
+            count: 10,
+            missing: 0,
+            zeros: 0,
+            negatives: 0,
+            mean: 0.0,
+            std_dev,
+            min: 0.0,
+            q1: 0.0,
+            median: 0.0,
+            q3: 0.0,
+            max: 0.0,
+            iqr: 0.0,
+            range: 0.0,
+            skewness: 0.0,
+            kurtosis: 0.0,
+            trimmed_mean: None,
+            median_ci: None,
+            geometric_mean: None,
+            harmonic_mean: None,
+            jb_p: None,
+        }
+    }
+
+    fn names(stats: &[DescriptiveStats]) -> Vec<&str> {
+        stats.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_stats_nan_last_and_stable_ties() {
+        let mut stats = vec![
+            stats_with_std("a", f64::NAN),
+            stats_with_std("b", 2.0),
+            stats_with_std("c", 1.0),
+            stats_with_std("d", 2.0),
+            stats_with_std("e", f64::NAN),
+        ];
+
+        assert!(sort_stats(&mut stats, "std", false));
+        assert_eq!(names(&stats), ["c", "b", "d", "a", "e"]);
+
+        assert!(sort_stats(&mut stats, "std", true));
+        assert_eq!(names(&stats), ["b", "d", "c", "a", "e"]);
+    }
+
+    #[test]
+    fn test_sort_stats_unknown_key() {
+        let mut stats = vec![stats_with_std("b", 1.0), stats_with_std("a", 2.0)];
+        assert!(!sort_stats(&mut stats, "variance", false));
+        assert_eq!(names(&stats), ["b", "a"]);
+        assert!(sort_stats(&mut stats, "name", false));
+        assert_eq!(names(&stats), ["a", "b"]);
+    }
+
+    #[test]
+    fn test_value_counts_sorted_with_missing() {
+        let df = reader::read_file("tests/data/sample.csv").unwrap();
+        let vc = value_counts(&df, "city").unwrap();
+        assert_eq!(vc.total, 30);
+        assert_eq!(vc.missing, 2);
+        assert_eq!(vc.counts.iter().map(|c| c.1).sum::<usize>(), 28);
+        assert!(vc.counts.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert!(value_counts(&df, "nope").is_none());
+    }
+
+    #[test]
+    fn test_missing_tokens_kept_apart() {
+        let df = DataFrame::from_column("v", &["a", "NA", "", " N/A ", "NA", "na", "b"]);
+        let tokens = missing_tokens(&df, "v").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                ("NA".to_string(), 2),
+                ("".to_string(), 1),
+                ("N/A".to_string(), 1),
+                ("na".to_string(), 1),
+            ]
+        );
+        assert!(missing_tokens(&df, "nope").is_none());
+    }
+
+    #[test]
+    fn test_value_counts_sort_by_value() {
+        let df = reader::read_file("tests/data/sample.csv").unwrap();
+        let mut vc = value_counts(&df, "city").unwrap();
+        vc.sort_by_value();
+        let values: Vec<&str> = vc.counts.iter().map(|c| c.0.as_str()).collect();
+        assert_eq!(
+            values,
+            ["Barranquilla", "Bogota", "Cali", "Cartagena", "Medellin"]
+        );
+
+        let mut vc = value_counts(&df, "id").unwrap();
+        vc.sort_by_value();
+        assert_eq!(vc.counts.len(), 30);
+        assert_eq!(vc.counts[1].0, "2");
+        assert_eq!(vc.counts[29].0, "30");
+    }
+
+    #[test]
+    fn test_bin_counts_matches_histogram_math() {
+        let (width, bins) = bin_counts(&[0.0, 1.0, 2.0, 3.0, 4.0], 2);
+        assert!((width - 2.0).abs() < 1e-12);
+        assert_eq!(bins, [2, 3]);
+
+        let (width, bins) = bin_counts(&[5.0, 5.0], 3);
+        assert!((width - 1.0).abs() < 1e-12);
+        assert_eq!(bins, [2, 0, 0]);
+    }
+
+    #[test]
+    fn test_binned_value_counts() {
+        let df = reader::read_file("tests/data/sample.csv").unwrap();
+        let vc = binned_value_counts(&df, "income", 4, |lo, hi| format!("{}-{}", lo, hi)).unwrap();
+        assert_eq!(vc.counts.len(), 4);
+        assert_eq!(vc.missing, 3);
+        assert_eq!(vc.counts.iter().map(|c| c.1).sum::<usize>(), 27);
+        assert_eq!(vc.counts[0].0, "32000-43500");
+        assert!(vc.binned);
+    }
+
     #[test]
     fn test_mean_basic() {
         assert!((mean(&[1.0, 2.0, 3.0, 4.0, 5.0]) - 3.0).abs() < 1e-10);
@@ -206,6 +1339,106 @@ mod tests {
         assert!((sd - 7.07107).abs() < 0.001);
     }
 
+    #[test]
+    fn test_trimmed_mean_drops_outliers() {
+        let data = vec![-100.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 500.0];
+        assert!((mean(&data) - 43.6).abs() < 1e-10);
+        assert!((trimmed_mean(&data, 0.1) - 4.5).abs() < 1e-10);
+        assert!((trimmed_mean(&data, 0.0) - 43.6).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_trimmed_mean_too_much_trimming() {
+        assert!(trimmed_mean(&[1.0, 2.0, 3.0, 4.0], 0.5).is_nan());
+        assert!(trimmed_mean(&[], 0.1).is_nan());
+        assert!((trimmed_mean(&[1.0, 2.0, 3.0], 0.4) - 2.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_trim_count_and_nan_from_infinities() {
+        assert_eq!(trim_count(4, 0.5), 2);
+        assert_eq!(trim_count(3, 0.4), 1);
+        assert_eq!(trim_count(10, 0.05), 0);
+        // Nothing is trimmed here; the NaN comes from inf + -inf
+        let sorted = [f64::NEG_INFINITY, 1.0, f64::INFINITY];
+        assert_eq!(trim_count(sorted.len(), 0.1), 0);
+        assert!(trimmed_mean(&sorted, 0.1).is_nan());
+    }
+
+    #[test]
+    fn test_winsorize_clips_to_percentiles() {
+        let data = vec![500.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, -100.0];
+        let clipped = winsorize(&data, 0.1);
+
+        let mut sorted = data.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let min = clipped.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = clipped.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        assert_eq!(min, percentile(&sorted, 10.0));
+        assert_eq!(max, percentile(&sorted, 90.0));
+        // Values inside the bounds keep their position and value
+        assert_eq!(clipped[1..9], data[1..9]);
+        assert!(winsorize(&[], 0.05).is_empty());
+    }
+
+    #[test]
+    fn test_winsorize_column_keeps_missing_cells() {
+        let mut df = reader::read_file("tests/data/sample.csv").unwrap();
+        let mut sorted = df.valid_numeric_column("income").unwrap();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        assert!(winsorize_column(&mut df, "income", 0.05));
+        let s = describe(&df, "income").unwrap();
+        assert_eq!(s.missing, 3);
+        assert_eq!(s.min, percentile(&sorted, 5.0));
+        assert_eq!(s.max, percentile(&sorted, 95.0));
+        assert!(!winsorize_column(&mut df, "nope", 0.05));
+    }
+
+    #[test]
+    fn test_geometric_and_harmonic_means() {
+        let data = vec![1.0, 2.0, 4.0];
+        assert!((geometric_mean(&data) - 2.0).abs() < 1e-10);
+        assert!((harmonic_mean(&data) - 12.0 / 7.0).abs() < 1e-10);
+        assert!(harmonic_mean(&data) < geometric_mean(&data));
+        assert!(geometric_mean(&data) < mean(&data));
+    }
+
+    #[test]
+    fn test_geometric_and_harmonic_means_reject_zero() {
+        let data = vec![3.0, 0.0, 5.0];
+        assert!(geometric_mean(&data).is_nan());
+        assert!(harmonic_mean(&data).is_nan());
+        assert!(geometric_mean(&[2.0, -1.0]).is_nan());
+        assert!(harmonic_mean(&[]).is_nan());
+    }
+
+    #[test]
+    fn test_skewness_and_kurtosis_known() {
+        let data = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        assert!(skewness(&data).abs() < 1e-10);
+        assert!((kurtosis(&data) + 1.2).abs() < 1e-10);
+
+        let skewed = vec![1.0, 1.0, 2.0, 2.0, 3.0, 10.0];
+        assert!(skewness(&skewed) > 1.0);
+        assert!(skewness(&[1.0, 2.0]).is_nan());
+        assert!(kurtosis(&[4.0, 4.0, 4.0, 4.0]).is_nan());
+    }
+
+    #[test]
+    fn test_bimodality_coefficient() {
+        let two_groups: Vec<f64> = (0..20)
+            .map(|i| if i % 2 == 0 { 10.0 } else { 50.0 })
+            .collect();
+        let bc = bimodality_coefficient(20, skewness(&two_groups), kurtosis(&two_groups));
+        assert!(bc > BIMODALITY_THRESHOLD, "{}", bc);
+
+        let df = reader::read_file("tests/data/sample.csv").unwrap();
+        let age = describe(&df, "age").unwrap();
+        assert!(age.bimodality() < BIMODALITY_THRESHOLD);
+        assert!(bimodality_coefficient(3, 0.0, 0.0).is_nan());
+    }
+
     #[test]
     fn test_percentile_median_odd() {
         let data = vec![1.0, 2.0, 3.0, 4.0, 5.0];
@@ -239,11 +1472,133 @@ mod tests {
         assert!(percentile(&[], 50.0).is_nan());
     }
 
+    #[test]
+    fn test_percentile_rank() {
+        let sorted = [1.0, 2.0, 2.0, 3.0, 5.0];
+        assert!((percentile_rank(&sorted, 2.0) - 60.0).abs() < 1e-10);
+        assert!((percentile_rank(&sorted, 4.0) - 80.0).abs() < 1e-10);
+        assert!(percentile_rank(&[], 1.0).is_nan());
+    }
+
+    #[test]
+    fn test_average_ranks_share_ties() {
+        assert_eq!(average_ranks(&[30.0, 10.0, 20.0, 20.0]), [4.0, 1.0, 2.5, 2.5]);
+        assert_eq!(average_ranks(&[5.0, 5.0, 5.0]), [2.0, 2.0, 2.0]);
+        assert!(average_ranks(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_percentile_rank_clamps_outside_range() {
+        let sorted = [10.0, 20.0, 30.0];
+        assert_eq!(percentile_rank(&sorted, 9.99), 0.0);
+        assert_eq!(percentile_rank(&sorted, -1e9), 0.0);
+        assert_eq!(percentile_rank(&sorted, 30.0), 100.0);
+        assert_eq!(percentile_rank(&sorted, 1e9), 100.0);
+    }
+
+    #[test]
+    fn test_bootstrap_ci_reproducible_with_seed() {
+        let values: Vec<f64> = (1..=50).map(|i| (i * i % 37) as f64).collect();
+        let first = bootstrap_ci(&values, median, 1000, 0.95, Some(42));
+        let second = bootstrap_ci(&values, median, 1000, 0.95, Some(42));
+        assert_eq!(first, second);
+
+        let (low, high) = first;
+        assert!(low <= median(&values) && median(&values) <= high);
+        assert!(low < high);
+        assert_ne!(first, bootstrap_ci(&values, median, 1000, 0.95, Some(7)));
+    }
+
+    #[test]
+    fn test_bootstrap_ci_degenerate_input() {
+        let (low, high) = bootstrap_ci(&[], median, 100, 0.95, Some(1));
+        assert!(low.is_nan() && high.is_nan());
+        let constant = bootstrap_ci(&[3.0; 5], median, 100, 0.95, Some(1));
+        assert_eq!(constant, (3.0, 3.0));
+        assert!(bootstrap_ci(&[1.0, 2.0], mean, 0, 0.95, Some(1)).0.is_nan());
+    }
+
     #[test]
     fn test_percentile_single() {
         assert!((percentile(&[42.0], 50.0) - 42.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_normal_quantile_known_values() {
+        assert!(normal_quantile(0.5).abs() < 1e-9);
+        assert!((normal_quantile(0.975) - 1.959964).abs() < 1e-5);
+        assert!((normal_quantile(0.025) + 1.959964).abs() < 1e-5);
+        assert!((normal_quantile(0.001) + 3.090232).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_t_test_p_value_known_values() {
+        // Two-sided critical values from t tables
+        assert!((t_test_p_value(2.228, 10.0) - 0.05).abs() < 1e-3);
+        assert!((t_test_p_value(-2.228, 10.0) - 0.05).abs() < 1e-3);
+        assert!((t_test_p_value(1.96, 1e6) - 0.05).abs() < 1e-3);
+        assert!((t_test_p_value(0.0, 5.0) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_f_test_p_value_known_values() {
+        // 5% critical values from F tables
+        assert!((f_test_p_value(4.26, 2.0, 9.0) - 0.05).abs() < 1e-3);
+        assert!((f_test_p_value(2.87, 4.0, 20.0) - 0.05).abs() < 1e-3);
+        assert!((f_test_p_value(0.0, 3.0, 10.0) - 1.0).abs() < 1e-12);
+        assert!(f_test_p_value(1.0, 0.0, 10.0).is_nan());
+    }
+
+    #[test]
+    fn test_correlation_p_value() {
+        // r = 0.6 with n = 12: t = 2.372, df = 10
+        assert!((correlation_p_value(0.6, 12) - 0.0393).abs() < 1e-3);
+        assert_eq!(correlation_p_value(1.0, 10), 0.0);
+        assert!(correlation_p_value(0.5, 2).is_nan());
+    }
+
+    #[test]
+    fn test_normal_quantile_out_of_range() {
+        assert!(normal_quantile(0.0).is_nan());
+        assert!(normal_quantile(1.0).is_nan());
+    }
+
+    #[test]
+    fn test_running_stats_matches_two_pass() {
+        let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0, 13.5, -1.25];
+        let mut running = RunningStats::new();
+        data.iter().for_each(|&x| running.push(x));
+        assert_eq!(running.count(), data.len());
+        assert!((running.mean() - mean(&data)).abs() < 1e-12);
+        assert!((running.std_dev() - std_dev(&data)).abs() < 1e-12);
+        assert_eq!(running.min(), -1.25);
+        assert_eq!(running.max(), 13.5);
+    }
+
+    #[test]
+    fn test_running_stats_large_offset() {
+        // Variance of 1e9 + {4, 7, 13, 16} is exactly 30
+        let mut running = RunningStats::new();
+        for x in [4.0, 7.0, 13.0, 16.0] {
+            running.push(1e9 + x);
+        }
+        assert!((running.variance() - 30.0).abs() < 1e-6);
+        assert!(RunningStats::new().mean().is_nan());
+        assert_eq!(RunningStats::new().variance(), 0.0);
+    }
+
+    #[test]
+    fn test_describe_without_quantiles() {
+        let df = reader::read_file("tests/data/sample.csv").unwrap();
+        let full = describe(&df, "income").unwrap();
+        let fast = describe_with(&df, "income", false).unwrap();
+        assert_eq!((fast.count, fast.missing), (full.count, full.missing));
+        assert!((fast.mean - full.mean).abs() < 1e-9);
+        assert!((fast.std_dev - full.std_dev).abs() < 1e-9);
+        assert_eq!((fast.min, fast.max), (full.min, full.max));
+        assert!(fast.median.is_nan());
+    }
+
     #[test]
     fn test_describe_with_missing() {
         let df = reader::read_file("tests/data/sample.csv").unwrap();
@@ -275,6 +1630,17 @@ mod tests {
         assert!(!names.contains(&"city"));
     }
 
+    #[test]
+    fn test_describe_serializes_with_short_keys() {
+        let df = reader::read_file("tests/data/sample.csv").unwrap();
+        let stats = describe(&df, "age").unwrap();
+        let value = serde_json::to_value(&stats).unwrap();
+        for key in ["name", "count", "mean", "std", "min", "q1", "median", "q3", "max"] {
+            assert!(value.get(key).is_some(), "missing key {}", key);
+        }
+        assert_eq!(value["count"], 30);
+    }
+
     #[test]
     fn test_categorical_summary() {
         let df = reader::read_file("tests/data/sample.csv").unwrap();
@@ -284,4 +1650,65 @@ mod tests {
         // city has some missing values (Uma row 21, Ben row 28)
         assert!(summary.missing >= 1);
     }
+
+    #[test]
+    fn test_approx_cardinality_matches_exact_on_few_levels() {
+        let df = reader::read_file("tests/data/sample.csv").unwrap();
+        for col in ["name", "gender", "city"] {
+            let exact = categorical_summary(&df, col).unwrap();
+            let approx = approx_categorical_summary(&df, col).unwrap();
+            assert_eq!(approx.unique, exact.unique, "{}", col);
+            assert_eq!((approx.total, approx.missing), (exact.total, exact.missing));
+            assert!(approx.top_values.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_approx_cardinality_of_near_unique_column() {
+        let n = 50_000;
+        let rows: Vec<[String; 2]> = (0..n)
+            .map(|i| [format!("user-{}", i), i.to_string()])
+            .collect();
+        let df = DataFrame::from_rows(&["id", "x"], &rows);
+        let summaries = approx_categorical_summaries(&df);
+        assert_eq!(summaries.len(), 1, "the numeric column is left out");
+        let relative = (summaries[0].unique as f64 - n as f64).abs() / n as f64;
+        assert!(relative < 3.0 * sketch::relative_error(sketch::DEFAULT_PRECISION));
+    }
+
+    #[test]
+    fn test_entropy_of_level_counts() {
+        assert_eq!(entropy(&[12]), 0.0);
+        assert!((entropy(&[5, 5]) - 1.0).abs() < 1e-12);
+        assert!((entropy(&[1, 1, 1, 1]) - 2.0).abs() < 1e-12);
+        assert!(entropy(&[]).is_nan());
+
+        // A skewed split carries less than one bit
+        let skewed = entropy(&[9, 1]);
+        assert!(skewed > 0.0 && skewed < 1.0);
+        assert!((normalized_entropy(entropy(&[3, 3, 3]), 3) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_single_level_column_has_zero_entropy() {
+        let df = DataFrame::from_column("x", &["a", "a", ""]);
+        let summary = categorical_summary(&df, "x").unwrap();
+        assert_eq!(summary.entropy, 0.0);
+        assert_eq!(summary.normalized_entropy, 0.0);
+        assert_eq!(summary.gini, 0.0);
+    }
+
+    #[test]
+    fn test_gini_impurity_of_level_counts() {
+        assert!((gini_impurity(&[7, 7]) - 0.5).abs() < 1e-12);
+        assert!((gini_impurity(&[1, 1, 1, 1]) - 0.75).abs() < 1e-12);
+        assert!((gini_impurity(&[9, 1]) - 0.18).abs() < 1e-12);
+        assert!(gini_impurity(&[]).is_nan());
+
+        // Shares the counts behind the summary's entropy
+        let df = reader::read_file("tests/data/sample.csv").unwrap();
+        let summary = categorical_summary(&df, "employed").unwrap();
+        let expected = 1.0 - (25.0f64 / 30.0).powi(2) - (5.0f64 / 30.0).powi(2);
+        assert!((summary.gini - expected).abs() < 1e-12);
+    }
 }
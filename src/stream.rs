@@ -0,0 +1,473 @@
+use crate::missing::{self, MissingInfo, MissingPatternReport, PatternLimits};
+use crate::reader::RowVisitor;
+use crate::sketch::{self, HyperLogLog};
+use crate::stats::{
+    self, CategoricalSummary, DescriptiveStats, Reservoir, RunningStats, ValueCounts,
+};
+use crate::types::{self, ColumnType, ColumnTypeInfo, NumericKind};
+use crate::utils::is_missing;
+use std::collections::HashMap;
+
+/// Distinct values counted exactly per column; past this, a sketch estimates them.
+pub const MAX_TRACKED_LEVELS: usize = 1_000;
+
+/// Per-column accumulators filled one row at a time.
+#[derive(Default)]
+struct ColumnAccumulator {
+    missing: usize,
+    running: RunningStats,
+    /// Sample of the numeric values for approximate quartiles, when requested.
+    reservoir: Option<Reservoir>,
+    /// Values that read as booleans (see `types::is_boolean_value`).
+    booleans: usize,
+    /// Values that parse as finite whole numbers.
+    integers: usize,
+    /// Count of each distinct non-missing value, up to `MAX_TRACKED_LEVELS` of them.
+    counts: HashMap<String, usize>,
+    /// Distinct-count estimate that replaces `counts` once a column has too many levels.
+    sketch: Option<HyperLogLog>,
+}
+
+impl ColumnAccumulator {
+    /// Count one non-missing value, switching to the sketch when `counts` outgrows the limit.
+    fn count(&mut self, val: &str) {
+        if let Some(sketch) = self.sketch.as_mut() {
+            sketch.insert(val);
+            return;
+        }
+        match self.counts.get_mut(val) {
+            Some(n) => *n += 1,
+            None => {
+                self.counts.insert(val.to_string(), 1);
+            }
+        }
+        if self.counts.len() > MAX_TRACKED_LEVELS {
+            let mut sketch = HyperLogLog::new(sketch::DEFAULT_PRECISION);
+            for value in self.counts.keys() {
+                sketch.insert(value.as_str());
+            }
+            self.counts = HashMap::new();
+            self.sketch = Some(sketch);
+        }
+    }
+
+    /// Type of the column, from its counts or, once sketched, from its tallies.
+    fn type_info(&self, name: &str, nrows: usize) -> ColumnTypeInfo {
+        let Some(sketch) = &self.sketch else {
+            return types::infer_from_counts(name, &self.counts);
+        };
+        let non_missing = nrows - self.missing;
+        let col_type = if non_missing > 0 && self.booleans == non_missing {
+            ColumnType::Boolean
+        } else if types::mostly_numeric(self.running.count(), non_missing) {
+            ColumnType::Numeric
+        } else {
+            ColumnType::Categorical
+        };
+        let numeric_kind = match col_type {
+            ColumnType::Numeric if self.integers == non_missing => Some(NumericKind::Integer),
+            ColumnType::Numeric => Some(NumericKind::Float),
+            _ => None,
+        };
+        let unique_count = sketch.estimate().round() as usize;
+        let levels = if col_type == ColumnType::Numeric {
+            vec!["-".to_string()]
+        } else {
+            vec![format!("({} unique)", unique_count)]
+        };
+
+        ColumnTypeInfo {
+            name: name.to_string(),
+            col_type,
+            numeric_kind,
+            unique_count,
+            levels,
+            // Value frequencies are no longer known
+            dominant_share: None,
+        }
+    }
+}
+
+/// Column statistics gathered in a single pass without keeping the rows.
+///
+/// Memory is bounded per column: running totals plus the counts of at most
+/// `MAX_TRACKED_LEVELS` distinct values, after which a fixed-size HyperLogLog
+/// sketch estimates the unique count. Otherwise it grows only with the number of
+/// distinct missingness patterns, not with the number of rows. Exact quartiles
+/// need the sorted column and are not computed; `with_reservoir` estimates them
+/// from a fixed-size random sample of each column instead.
+#[derive(Default)]
+pub struct StreamStats {
+    headers: Vec<String>,
+    /// Values sampled per column for approximate quartiles, if any.
+    reservoir_size: Option<usize>,
+    /// Estimate every column's unique count with a sketch from the first row.
+    sketch_all: bool,
+    columns: Vec<ColumnAccumulator>,
+    nrows: usize,
+    rows_with_missing: usize,
+    patterns: HashMap<String, usize>,
+}
+
+impl RowVisitor for StreamStats {
+    fn headers(&mut self, headers: &[String]) {
+        self.headers = headers.to_vec();
+        self.columns = headers
+            .iter()
+            .map(|_| ColumnAccumulator {
+                reservoir: self.reservoir_size.map(Reservoir::new),
+                sketch: self
+                    .sketch_all
+                    .then(|| HyperLogLog::new(sketch::DEFAULT_PRECISION)),
+                ..ColumnAccumulator::default()
+            })
+            .collect();
+    }
+
+    fn row(&mut self, row: &[String]) {
+        self.nrows += 1;
+        let mut pattern = String::with_capacity(row.len());
+        for (acc, val) in self.columns.iter_mut().zip(row) {
+            let val = val.trim();
+            if is_missing(val) {
+                acc.missing += 1;
+                pattern.push('1');
+                continue;
+            }
+            pattern.push('0');
+            if let Ok(x) = val.parse::<f64>() {
+                acc.running.push(x);
+                if let Some(reservoir) = acc.reservoir.as_mut() {
+                    reservoir.push(x);
+                }
+                if x.is_finite() && x.fract() == 0.0 {
+                    acc.integers += 1;
+                }
+            }
+            if types::is_boolean_value(val) {
+                acc.booleans += 1;
+            }
+            acc.count(val);
+        }
+        if pattern.contains('1') {
+            self.rows_with_missing += 1;
+        }
+        *self.patterns.entry(pattern).or_insert(0) += 1;
+    }
+}
+
+impl StreamStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also keep a random sample of up to `size` values per column, so `describe`
+    /// can estimate Q1, median and Q3.
+    pub fn with_reservoir(size: usize) -> Self {
+        StreamStats {
+            reservoir_size: Some(size),
+            ..Self::default()
+        }
+    }
+
+    /// Feed every column to a distinct-count sketch instead of counting its values,
+    /// as if it had passed `MAX_TRACKED_LEVELS` from the start.
+    pub fn with_sketches(self) -> Self {
+        StreamStats {
+            sketch_all: true,
+            ..self
+        }
+    }
+
+    /// Header names seen in the stream.
+    pub fn column_names(&self) -> &[String] {
+        &self.headers
+    }
+
+    fn column(&self, name: &str) -> Option<&ColumnAccumulator> {
+        let idx = self.headers.iter().position(|h| h == name)?;
+        self.columns.get(idx)
+    }
+
+    /// Inferred type of every column, as `types::infer_types` would report it.
+    pub fn type_infos(&self) -> Vec<ColumnTypeInfo> {
+        self.headers
+            .iter()
+            .zip(&self.columns)
+            .map(|(name, acc)| acc.type_info(name, self.nrows))
+            .collect()
+    }
+
+    /// Columns with more than `MAX_TRACKED_LEVELS` distinct values, whose unique
+    /// counts are estimates and whose value frequencies were not kept.
+    pub fn sketched_columns(&self) -> Vec<&str> {
+        self.headers
+            .iter()
+            .zip(&self.columns)
+            .filter(|(_, acc)| acc.sketch.is_some())
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Statistics for one column, like `stats::describe_with` without quartiles.
+    ///
+    /// With a reservoir, quartiles and IQR are estimated from the sampled values.
+    pub fn describe(&self, name: &str) -> Option<DescriptiveStats> {
+        let acc = self.column(name)?;
+        let running = &acc.running;
+        let (q1, median, q3) = match &acc.reservoir {
+            Some(reservoir) => reservoir.quartiles(),
+            None => (f64::NAN, f64::NAN, f64::NAN),
+        };
+        Some(DescriptiveStats {
+            name: name.to_string(),
+            count: running.count(),
+            missing: self.nrows - running.count(),
+            zeros: running.zeros(),
+            negatives: running.negatives(),
+            mean: running.mean(),
+            std_dev: if running.count() == 0 {
+                f64::NAN
+            } else {
+                running.std_dev()
+            },
+            min: running.min(),
+            q1,
+            median,
+            q3,
+            max: running.max(),
+            iqr: q3 - q1,
+            range: running.max() - running.min(),
+            skewness: f64::NAN,
+            kurtosis: f64::NAN,
+            trimmed_mean: None,
+            median_ci: None,
+            geometric_mean: None,
+            harmonic_mean: None,
+            jb_p: None,
+        })
+    }
+
+    /// Columns whose quartiles are estimates because they had more values than
+    /// the reservoir keeps.
+    pub fn sampled_columns(&self) -> Vec<&str> {
+        self.headers
+            .iter()
+            .zip(&self.columns)
+            .filter(|(_, acc)| acc.reservoir.as_ref().is_some_and(|r| !r.is_exact()))
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Statistics for every numeric column.
+    pub fn describe_all(&self) -> Vec<DescriptiveStats> {
+        self.type_infos()
+            .iter()
+            .filter(|t| t.col_type == ColumnType::Numeric)
+            .filter_map(|t| self.describe(&t.name))
+            .collect()
+    }
+
+    /// Statistics for the named columns; unknown names are skipped.
+    pub fn describe_selected(&self, columns: &[&str]) -> Vec<DescriptiveStats> {
+        columns
+            .iter()
+            .filter_map(|col| self.describe(col))
+            .collect()
+    }
+
+    /// Summaries of the non-numeric columns, like `stats::categorical_summary`.
+    ///
+    /// Columns in `sketched_columns` get `stats::sketched_summary` instead.
+    pub fn categorical_summaries(&self) -> Vec<CategoricalSummary> {
+        self.type_infos()
+            .iter()
+            .filter(|t| t.col_type != ColumnType::Numeric)
+            .filter_map(|t| {
+                let acc = self.column(&t.name)?;
+                if let Some(sketch) = &acc.sketch {
+                    return Some(stats::sketched_summary(
+                        &t.name,
+                        self.nrows,
+                        acc.missing,
+                        sketch,
+                    ));
+                }
+                let vc = ValueCounts::new(&t.name, self.nrows, acc.missing, acc.counts.clone());
+                Some(stats::summarize_counts(vc))
+            })
+            .collect()
+    }
+
+    /// Missing counts per column, like `missing::analyze`.
+    pub fn missing_infos(&self) -> Vec<MissingInfo> {
+        self.headers
+            .iter()
+            .zip(&self.columns)
+            .map(|(name, acc)| MissingInfo {
+                name: name.clone(),
+                missing: acc.missing,
+                total: self.nrows,
+                pct: if self.nrows > 0 {
+                    (acc.missing as f64 / self.nrows as f64) * 100.0
+                } else {
+                    0.0
+                },
+            })
+            .collect()
+    }
+
+    /// Missingness patterns, like `missing::missing_patterns`.
+    pub fn missing_patterns(&self, limits: &PatternLimits) -> MissingPatternReport {
+        missing::pattern_report(
+            &self.headers,
+            self.nrows,
+            self.rows_with_missing,
+            self.patterns.clone(),
+            limits,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::{read_file, stream_file_with, ReadOptions};
+
+    fn streamed(path: &str, opts: &ReadOptions) -> StreamStats {
+        let mut stats = StreamStats::new();
+        stream_file_with(path, opts, &mut stats).unwrap();
+        stats
+    }
+
+    #[test]
+    fn test_stream_matches_in_memory() {
+        let df = read_file("tests/data/sample.csv").unwrap();
+        let s = streamed("tests/data/sample.csv", &ReadOptions::default());
+        let limits = PatternLimits::default();
+        assert_eq!(s.missing_patterns(&limits).total_rows, df.nrows());
+
+        let expected = stats::describe_all(&df);
+        let actual = s.describe_all();
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(&expected) {
+            assert_eq!(
+                (a.name.as_str(), a.count, a.missing),
+                (e.name.as_str(), e.count, e.missing)
+            );
+            assert!((a.mean - e.mean).abs() < 1e-9);
+            assert!((a.std_dev - e.std_dev).abs() < 1e-9);
+            assert_eq!((a.min, a.max), (e.min, e.max));
+            assert!(a.median.is_nan());
+        }
+
+        let missing: Vec<usize> = s.missing_infos().iter().map(|m| m.missing).collect();
+        let expected: Vec<usize> = missing::analyze(&df).iter().map(|m| m.missing).collect();
+        assert_eq!(missing, expected);
+        assert_eq!(
+            s.missing_patterns(&limits).rows_with_missing,
+            missing::missing_patterns(&df, &limits).rows_with_missing
+        );
+
+        for (a, e) in s.type_infos().iter().zip(types::infer_types(&df)) {
+            assert_eq!(a.type_label(), e.type_label(), "{}", e.name);
+            assert_eq!((a.unique_count, &a.levels), (e.unique_count, &e.levels));
+            assert_eq!(a.dominant_share, e.dominant_share);
+        }
+    }
+
+    #[test]
+    fn test_reservoir_quartiles_match_exact_on_small_stream() {
+        let df = read_file("tests/data/sample.csv").unwrap();
+        let mut s = StreamStats::with_reservoir(100);
+        stream_file_with("tests/data/sample.csv", &ReadOptions::default(), &mut s).unwrap();
+        assert!(s.sampled_columns().is_empty());
+
+        for (a, e) in s.describe_all().iter().zip(stats::describe_all(&df)) {
+            assert_eq!((a.q1, a.median, a.q3), (e.q1, e.median, e.q3), "{}", e.name);
+            assert_eq!(a.iqr, e.iqr);
+        }
+    }
+
+    #[test]
+    fn test_reservoir_estimates_quartiles_of_long_stream() {
+        let mut s = StreamStats::with_reservoir(500);
+        s.headers(&["x".to_string()]);
+        for i in 0..10_000 {
+            s.row(&[i.to_string()]);
+        }
+        assert_eq!(s.sampled_columns(), ["x"]);
+        // Only the reservoir and a sketch are kept, not every distinct value
+        assert_eq!(s.sketched_columns(), ["x"]);
+        assert!(s.columns[0].counts.is_empty());
+        let d = s.describe("x").unwrap();
+        assert_eq!(d.count, 10_000);
+        assert!((d.median - 5000.0).abs() < 500.0, "median {}", d.median);
+        assert!((d.q1 - 2500.0).abs() < 500.0, "q1 {}", d.q1);
+    }
+
+    #[test]
+    fn test_high_cardinality_columns_switch_to_sketch() {
+        let mut s = StreamStats::new();
+        s.headers(&["id".to_string(), "x".to_string(), "group".to_string()]);
+        let n = 5_000;
+        for i in 0..n {
+            s.row(&[format!("u{}", i), format!("{}.5", i), (i % 3).to_string()]);
+        }
+        assert_eq!(s.sketched_columns(), ["id", "x"]);
+        assert!(s.columns[..2].iter().all(|acc| acc.counts.is_empty()));
+
+        let infos = s.type_infos();
+        assert_eq!(infos[0].col_type, ColumnType::Categorical);
+        assert_eq!(infos[1].type_label(), "Float");
+        assert_eq!(infos[2].type_label(), "Integer");
+        assert_eq!(infos[2].unique_count, 3);
+        let error = sketch::relative_error(sketch::DEFAULT_PRECISION);
+        for info in &infos[..2] {
+            let relative = (info.unique_count as f64 - n as f64).abs() / n as f64;
+            assert!(
+                relative < 3.0 * error,
+                "{}: {}",
+                info.name,
+                info.unique_count
+            );
+        }
+
+        let summaries = s.categorical_summaries();
+        assert_eq!(summaries[0].unique, infos[0].unique_count);
+        assert!(summaries[0].top_values.is_empty());
+    }
+
+    #[test]
+    fn test_with_sketches_matches_exact_unique_counts() {
+        let df = read_file("tests/data/sample.csv").unwrap();
+        let mut s = StreamStats::new().with_sketches();
+        stream_file_with("tests/data/sample.csv", &ReadOptions::default(), &mut s).unwrap();
+        assert_eq!(s.sketched_columns(), df.headers);
+        assert!(s.columns.iter().all(|acc| acc.counts.is_empty()));
+
+        for (a, e) in s.type_infos().iter().zip(types::infer_types(&df)) {
+            assert_eq!(a.type_label(), e.type_label(), "{}", e.name);
+            assert_eq!(a.unique_count, e.unique_count, "{}", e.name);
+        }
+        let exact = stats::categorical_summaries(&df);
+        for (a, e) in s.categorical_summaries().iter().zip(&exact) {
+            assert_eq!(
+                (&a.name, a.unique, a.missing),
+                (&e.name, e.unique, e.missing)
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_reader_options() {
+        let opts = ReadOptions {
+            no_header: true,
+            ..ReadOptions::default()
+        };
+        let s = streamed("tests/data/headerless.csv", &opts);
+        let limits = PatternLimits::default();
+        assert_eq!(s.missing_patterns(&limits).total_rows, 3);
+        assert_eq!(s.type_infos()[0].name, "col1");
+    }
+}
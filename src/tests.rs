@@ -0,0 +1,324 @@
+use crate::reader::DataFrame;
+use crate::stats;
+use crate::utils::is_missing;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Size and mean of one group in a grouped comparison.
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupMean {
+    pub group: String,
+    pub n: usize,
+    pub mean: f64,
+}
+
+/// One-way analysis of variance of a numeric column across the levels of another.
+#[derive(Debug, Clone, Serialize)]
+pub struct Anova {
+    pub var: String,
+    pub by: String,
+    /// Groups in label order.
+    pub groups: Vec<GroupMean>,
+    pub ss_between: f64,
+    pub ss_within: f64,
+    pub df_between: usize,
+    pub df_within: usize,
+    /// `NaN` with fewer than two groups or no within-group degrees of freedom.
+    pub f: f64,
+    pub p: f64,
+    /// Share of the total sum of squares explained by the groups.
+    pub eta_squared: f64,
+}
+
+/// One-way ANOVA of `var` grouped by the values of `by`.
+///
+/// Rows missing either value are skipped. Groups with a single observation add
+/// nothing to the within-group sum of squares; if every group has one, F and p
+/// are `NaN`. Returns `None` if either column does not exist.
+pub fn one_way_anova(df: &DataFrame, var: &str, by: &str) -> Option<Anova> {
+    let values = df.numeric_column(var)?;
+    let labels = df.column(by)?;
+
+    let mut partition: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+    for (value, label) in values.iter().zip(&labels) {
+        let label = label.trim();
+        if let Some(v) = value {
+            if !is_missing(label) {
+                partition.entry(label).or_default().push(*v);
+            }
+        }
+    }
+
+    let all: Vec<f64> = partition.values().flatten().copied().collect();
+    let grand_mean = stats::mean(&all);
+    let mut groups = Vec::new();
+    let mut ss_between = 0.0;
+    let mut ss_within = 0.0;
+    for (label, group) in &partition {
+        let mean = stats::mean(group);
+        ss_between += group.len() as f64 * (mean - grand_mean).powi(2);
+        ss_within += group.iter().map(|v| (v - mean).powi(2)).sum::<f64>();
+        groups.push(GroupMean {
+            group: label.to_string(),
+            n: group.len(),
+            mean,
+        });
+    }
+
+    let df_between = groups.len().saturating_sub(1);
+    let df_within = all.len().saturating_sub(groups.len());
+    let (f, p) = if df_between == 0 || df_within == 0 {
+        (f64::NAN, f64::NAN)
+    } else {
+        let f = (ss_between / df_between as f64) / (ss_within / df_within as f64);
+        (
+            f,
+            stats::f_test_p_value(f, df_between as f64, df_within as f64),
+        )
+    };
+    let ss_total = ss_between + ss_within;
+    let eta_squared = if ss_total > 0.0 {
+        ss_between / ss_total
+    } else {
+        f64::NAN
+    };
+
+    Some(Anova {
+        var: var.to_string(),
+        by: by.to_string(),
+        groups,
+        ss_between,
+        ss_within,
+        df_between,
+        df_within,
+        f,
+        p,
+        eta_squared,
+    })
+}
+
+/// Two-sample Kolmogorov–Smirnov test of one column across two datasets.
+#[derive(Debug, Clone, Serialize)]
+pub struct KsTest {
+    pub var: String,
+    pub n1: usize,
+    pub n2: usize,
+    /// Largest vertical gap between the two empirical distribution functions.
+    pub d: f64,
+    /// Asymptotic p-value; `NaN` if either sample is empty.
+    pub p: f64,
+}
+
+/// Finite values of `values`, sorted ascending, as `ks_two_sample` expects.
+///
+/// `NaN` and infinite cells are dropped: they have no place in an empirical CDF
+/// and a `NaN` would stop the sample walk from advancing.
+pub fn ks_sample(mut values: Vec<f64>) -> Vec<f64> {
+    values.retain(|v| v.is_finite());
+    values.sort_by(|a, b| a.total_cmp(b));
+    values
+}
+
+/// Kolmogorov–Smirnov test between two samples of finite values, each sorted ascending.
+///
+/// The p-value uses the asymptotic Kolmogorov distribution with Stephens'
+/// small-sample correction, so it is approximate for very small samples.
+pub fn ks_two_sample(var: &str, sorted1: &[f64], sorted2: &[f64]) -> KsTest {
+    let (n1, n2) = (sorted1.len(), sorted2.len());
+    if n1 == 0 || n2 == 0 {
+        return KsTest {
+            var: var.to_string(),
+            n1,
+            n2,
+            d: f64::NAN,
+            p: f64::NAN,
+        };
+    }
+
+    // Walk both samples in step, advancing past ties together so the gap is
+    // only measured between distinct values
+    let (mut i, mut j) = (0, 0);
+    let mut d: f64 = 0.0;
+    while i < n1 && j < n2 {
+        let x = sorted1[i].min(sorted2[j]);
+        while i < n1 && sorted1[i] <= x {
+            i += 1;
+        }
+        while j < n2 && sorted2[j] <= x {
+            j += 1;
+        }
+        d = d.max((i as f64 / n1 as f64 - j as f64 / n2 as f64).abs());
+    }
+
+    let en = ((n1 * n2) as f64 / (n1 + n2) as f64).sqrt();
+    KsTest {
+        var: var.to_string(),
+        n1,
+        n2,
+        d,
+        p: kolmogorov_p_value((en + 0.12 + 0.11 / en) * d),
+    }
+}
+
+/// Upper tail of the Kolmogorov distribution, `2 * sum((-1)^(j-1) * exp(-2 j^2 x^2))`.
+fn kolmogorov_p_value(lambda: f64) -> f64 {
+    let mut sum = 0.0;
+    let mut sign = 1.0;
+    for j in 1..=100 {
+        let term = sign * 2.0 * (-2.0 * (j * j) as f64 * lambda * lambda).exp();
+        sum += term;
+        if term.abs() <= 1e-10 * sum.abs() {
+            return sum.clamp(0.0, 1.0);
+        }
+        sign = -sign;
+    }
+    // The series only fails to converge when lambda is close to zero
+    1.0
+}
+
+/// Fewest values for which [`jarque_bera`] reports a result.
+pub const JARQUE_BERA_MIN_N: usize = 8;
+
+/// Jarque–Bera normality test: the statistic and its chi-square(2) p-value.
+///
+/// `JB = n/6 * (S^2 + K^2/4)` from the moment skewness `S` and excess kurtosis
+/// `K`. The chi-square approximation is poor for tiny samples, so both are `NaN`
+/// below [`JARQUE_BERA_MIN_N`] values, as they are for a constant column.
+pub fn jarque_bera(values: &[f64]) -> (f64, f64) {
+    if values.len() < JARQUE_BERA_MIN_N {
+        return (f64::NAN, f64::NAN);
+    }
+    let (m2, m3, m4) = stats::central_moments(values);
+    if m2 == 0.0 {
+        return (f64::NAN, f64::NAN);
+    }
+    let skewness = m3 / m2.powf(1.5);
+    let kurtosis = m4 / (m2 * m2) - 3.0;
+    let jb = values.len() as f64 / 6.0 * (skewness * skewness + kurtosis * kurtosis / 4.0);
+    // The chi-square survival function with 2 degrees of freedom is exp(-x/2)
+    (jb, (-jb / 2.0).exp())
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::reader::read_file;
+
+    #[test]
+    fn test_anova_known_values() {
+        // Group means 2, 5 and 8 around a grand mean of 5, each with variance 1
+        let df = DataFrame::from_rows(
+            &["y", "g"],
+            &[
+                ["1", "a"],
+                ["2", "a"],
+                ["3", "a"],
+                ["4", "b"],
+                ["5", "b"],
+                ["6", "b"],
+                ["7", "c"],
+                ["8", "c"],
+                ["9", "c"],
+                ["NA", "c"],
+                ["10", ""],
+            ],
+        );
+        let anova = one_way_anova(&df, "y", "g").unwrap();
+        let means: Vec<f64> = anova.groups.iter().map(|g| g.mean).collect();
+        assert_eq!(means, vec![2.0, 5.0, 8.0]);
+        assert!((anova.ss_between - 54.0).abs() < 1e-10);
+        assert!((anova.ss_within - 6.0).abs() < 1e-10);
+        assert_eq!((anova.df_between, anova.df_within), (2, 6));
+        assert!((anova.f - 27.0).abs() < 1e-10);
+        assert!((anova.p - 0.001).abs() < 1e-4);
+        assert!((anova.eta_squared - 0.9).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_anova_single_observation_groups() {
+        let df = DataFrame::from_rows(&["y", "g"], &[["1", "a"], ["4", "b"]]);
+        let anova = one_way_anova(&df, "y", "g").unwrap();
+        assert_eq!(anova.df_within, 0);
+        assert!(anova.f.is_nan() && anova.p.is_nan());
+        assert!((anova.eta_squared - 1.0).abs() < 1e-10);
+
+        // A singleton group alongside a larger one still yields a test
+        let df = DataFrame::from_rows(
+            &["y", "g"],
+            &[["1", "a"], ["2", "a"], ["3", "a"], ["9", "b"]],
+        );
+        let anova = one_way_anova(&df, "y", "g").unwrap();
+        assert_eq!((anova.df_between, anova.df_within), (1, 2));
+        assert!(anova.f.is_finite());
+    }
+
+    #[test]
+    fn test_anova_sample_file() {
+        let df = read_file("tests/data/sample.csv").unwrap();
+        let anova = one_way_anova(&df, "income", "gender").unwrap();
+        let n: usize = anova.groups.iter().map(|g| g.n).sum();
+        assert_eq!(n, 27);
+        assert!((0.0..=1.0).contains(&anova.p));
+        assert!(one_way_anova(&df, "income", "nope").is_none());
+    }
+
+    #[test]
+    fn test_ks_separated_distributions() {
+        let low: Vec<f64> = (0..50).map(f64::from).collect();
+        let high: Vec<f64> = (100..150).map(f64::from).collect();
+        let ks = ks_two_sample("x", &low, &high);
+        assert_eq!((ks.n1, ks.n2), (50, 50));
+        assert!((ks.d - 1.0).abs() < 1e-10);
+        assert!(ks.p < 1e-10);
+
+        let same = ks_two_sample("x", &low, &low);
+        assert_eq!(same.d, 0.0);
+        assert!((same.p - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_ks_ties_and_empty_samples() {
+        // The empirical CDFs differ by a third at 1 and at 3
+        let ks = ks_two_sample("x", &[1.0, 2.0, 3.0], &[2.0, 3.0, 4.0]);
+        assert!((ks.d - 1.0 / 3.0).abs() < 1e-10);
+        assert!(ks.p > 0.5);
+
+        let empty = ks_two_sample("x", &[], &[1.0]);
+        assert!(empty.d.is_nan() && empty.p.is_nan());
+    }
+
+    #[test]
+    fn test_ks_sample_drops_nan_in_both_samples() {
+        let a = ks_sample(vec![3.0, f64::NAN, 1.0, f64::INFINITY]);
+        let b = ks_sample(vec![f64::NAN, 1.0, 3.0]);
+        assert_eq!(a, [1.0, 3.0]);
+        let ks = ks_two_sample("x", &a, &b);
+        assert_eq!((ks.n1, ks.n2), (2, 2));
+        assert_eq!(ks.d, 0.0);
+    }
+
+    #[test]
+    fn test_jarque_bera_rejects_skewed_data() {
+        // Quantiles of an exponential distribution: skewness 2, excess kurtosis 6
+        let n = 200;
+        let exponential: Vec<f64> = (0..n)
+            .map(|i| -(1.0 - (i as f64 + 0.5) / n as f64).ln())
+            .collect();
+        let (jb, p) = jarque_bera(&exponential);
+        assert!(jb > 100.0);
+        assert!(p < 1e-10);
+
+        // Evenly spaced values are light-tailed but symmetric, well short of that
+        let even: Vec<f64> = (0..50).map(f64::from).collect();
+        let (_, p) = jarque_bera(&even);
+        assert!(p > 0.05);
+    }
+
+    #[test]
+    fn test_jarque_bera_small_samples_are_nan() {
+        let (jb, p) = jarque_bera(&[1.0, 2.0, 3.0, 10.0, 20.0, 40.0, 90.0]);
+        assert!(jb.is_nan() && p.is_nan());
+        let (jb, p) = jarque_bera(&[5.0; 10]);
+        assert!(jb.is_nan() && p.is_nan());
+    }
+}
@@ -0,0 +1,247 @@
+use crate::reader::DataFrame;
+use crate::stats;
+use crate::types::{infer_types, ColumnType};
+use anyhow::{bail, Result};
+
+/// Column-wise transformation applied by `transform`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transform {
+    /// Trailing moving average over the last `window` rows.
+    RollingMean { window: usize },
+    /// Running total from the first row.
+    CumSum { na: MissingPolicy },
+    /// Rank among the column's values, ties sharing their average rank.
+    Rank,
+}
+
+impl Transform {
+    /// Suffix of the output column, e.g. `sales_rolling_mean`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Transform::RollingMean { .. } => "rolling_mean",
+            Transform::CumSum { .. } => "cumsum",
+            Transform::Rank => "rank",
+        }
+    }
+}
+
+/// How a running total treats missing cells.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MissingPolicy {
+    /// Leave the row empty and carry the total past it.
+    Skip,
+    /// Count the cell as 0, so the row shows the total so far.
+    Zero,
+}
+
+impl MissingPolicy {
+    /// Parse "skip" or "zero".
+    pub fn parse(value: &str) -> Option<MissingPolicy> {
+        match value {
+            "skip" => Some(MissingPolicy::Skip),
+            "zero" => Some(MissingPolicy::Zero),
+            _ => None,
+        }
+    }
+}
+
+/// Trailing moving average: position `i` is the mean of `values[i + 1 - window..=i]`.
+///
+/// The first `window - 1` positions have no full window and are `NaN`, as is any
+/// window containing a `NaN` (a missing cell).
+pub fn rolling_mean(values: &[f64], window: usize) -> Vec<f64> {
+    (0..values.len())
+        .map(|i| {
+            if window == 0 || i + 1 < window {
+                f64::NAN
+            } else {
+                stats::mean(&values[i + 1 - window..=i])
+            }
+        })
+        .collect()
+}
+
+/// Running total of `values`.
+///
+/// A `NaN` (missing cell) leaves the total unchanged and is `NaN` itself; replace
+/// missing values with 0 first to show the carried total on those rows instead.
+pub fn cumulative_sum(values: &[f64]) -> Vec<f64> {
+    let mut total = 0.0;
+    values
+        .iter()
+        .map(|&v| {
+            if v.is_nan() {
+                f64::NAN
+            } else {
+                total += v;
+                total
+            }
+        })
+        .collect()
+}
+
+/// 1-based rank of each value in its original position, ties sharing their average rank.
+///
+/// Missing values (`NaN`) are left out of the ranking and stay `NaN`.
+pub fn rank(values: &[f64]) -> Vec<f64> {
+    let present: Vec<f64> = values.iter().copied().filter(|v| !v.is_nan()).collect();
+    let mut ranks = stats::average_ranks(&present).into_iter();
+    values
+        .iter()
+        .map(|v| {
+            if v.is_nan() {
+                f64::NAN
+            } else {
+                ranks.next().unwrap_or(f64::NAN)
+            }
+        })
+        .collect()
+}
+
+/// Apply `transform` to the numeric column `col`, one output value per row.
+///
+/// Missing and unparseable cells enter as `NaN`. Errors if the column does not
+/// exist, is not numeric, or the window does not fit the number of rows.
+pub fn transform_column(df: &DataFrame, col: &str, transform: Transform) -> Result<Vec<f64>> {
+    let Some(values) = df.numeric_column(col) else {
+        bail!("Column '{}' not found", col);
+    };
+    let numeric = infer_types(df)
+        .iter()
+        .any(|t| t.name == col && t.col_type == ColumnType::Numeric);
+    if !numeric {
+        bail!("Column '{}' is not numeric", col);
+    }
+    let values: Vec<f64> = values.into_iter().map(|v| v.unwrap_or(f64::NAN)).collect();
+
+    match transform {
+        Transform::RollingMean { window } => {
+            if window == 0 || window > values.len() {
+                bail!(
+                    "--window must be between 1 and the number of rows ({}), got {}",
+                    values.len(),
+                    window
+                );
+            }
+            Ok(rolling_mean(&values, window))
+        }
+        Transform::CumSum {
+            na: MissingPolicy::Skip,
+        } => Ok(cumulative_sum(&values)),
+        Transform::CumSum {
+            na: MissingPolicy::Zero,
+        } => {
+            let zeroed: Vec<f64> = values
+                .iter()
+                .map(|&v| if v.is_nan() { 0.0 } else { v })
+                .collect();
+            Ok(cumulative_sum(&zeroed))
+        }
+        Transform::Rank => Ok(rank(&values)),
+    }
+}
+
+/// Two-column frame of 1-based row numbers and transformed values, ready to write as CSV.
+///
+/// `NaN` results are written as empty cells.
+pub fn to_frame(col: &str, transform: Transform, values: &[f64]) -> DataFrame {
+    let rows = values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let cell = if v.is_nan() {
+                String::new()
+            } else {
+                v.to_string()
+            };
+            vec![(i + 1).to_string(), cell]
+        })
+        .collect();
+    DataFrame {
+        headers: vec!["row".to_string(), format!("{}_{}", col, transform.name())],
+        rows,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::read_file;
+
+    #[test]
+    fn test_rolling_mean_is_trailing() {
+        let out = rolling_mean(&[1.0, 2.0, 3.0, 4.0, 5.0], 3);
+        assert!(out[0].is_nan() && out[1].is_nan());
+        assert_eq!(out[2..], [2.0, 3.0, 4.0]);
+
+        // A missing value blanks every window that covers it
+        let gap = rolling_mean(&[1.0, f64::NAN, 3.0, 5.0], 2);
+        assert!(gap[1].is_nan() && gap[2].is_nan());
+        assert_eq!(gap[3], 4.0);
+    }
+
+    #[test]
+    fn test_cumulative_sum_missing_policies() {
+        let df = DataFrame::from_column("amount", &["10", "", "5", "NA", "1"]);
+
+        // skip: missing rows stay empty, the total carries past them
+        let na = MissingPolicy::Skip;
+        let skip = transform_column(&df, "amount", Transform::CumSum { na }).unwrap();
+        assert_eq!(skip[0], 10.0);
+        assert!(skip[1].is_nan());
+        assert_eq!(skip[2], 15.0);
+        assert!(skip[3].is_nan());
+        assert_eq!(skip[4], 16.0);
+
+        // zero: missing rows add nothing but show the total so far
+        let na = MissingPolicy::Zero;
+        let zero = transform_column(&df, "amount", Transform::CumSum { na }).unwrap();
+        assert_eq!(zero, [10.0, 10.0, 15.0, 15.0, 16.0]);
+    }
+
+    #[test]
+    fn test_rank_averages_ties_in_row_order() {
+        let out = rank(&[3.0, 1.0, f64::NAN, 3.0, 2.0, 3.0]);
+        // The three 3s span ranks 3, 4 and 5
+        assert_eq!(out[0], 4.0);
+        assert_eq!(out[1], 1.0);
+        assert!(out[2].is_nan());
+        assert_eq!(out[3..], [4.0, 2.0, 4.0]);
+    }
+
+    #[test]
+    fn test_rolling_mean_window_edges() {
+        let df = read_file("tests/data/sample.csv").unwrap();
+        let age = df.valid_numeric_column("age").unwrap();
+
+        // A window of one row is the column itself
+        let same = transform_column(&df, "age", Transform::RollingMean { window: 1 }).unwrap();
+        assert_eq!(same, age);
+
+        // A window as long as the file leaves only the last row, the overall mean
+        let full = transform_column(&df, "age", Transform::RollingMean { window: 30 }).unwrap();
+        assert!(full[..29].iter().all(|v| v.is_nan()));
+        assert!((full[29] - stats::mean(&age)).abs() < 1e-9);
+
+        for window in [0, 31] {
+            let err = transform_column(&df, "age", Transform::RollingMean { window }).unwrap_err();
+            assert!(err.to_string().contains("--window must be between 1 and"));
+        }
+    }
+
+    #[test]
+    fn test_transform_rejects_unknown_and_text_columns() {
+        let df = read_file("tests/data/sample.csv").unwrap();
+        let rolling = Transform::RollingMean { window: 2 };
+        assert!(transform_column(&df, "nope", rolling).is_err());
+        assert!(transform_column(&df, "city", rolling).is_err());
+    }
+
+    #[test]
+    fn test_to_frame_blanks_nan() {
+        let rolling = Transform::RollingMean { window: 2 };
+        let df = to_frame("sales", rolling, &[f64::NAN, 1.5]);
+        assert_eq!(df.headers, ["row", "sales_rolling_mean"]);
+        assert_eq!(df.rows, [["1", ""], ["2", "1.5"]]);
+    }
+}
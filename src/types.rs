@@ -1,5 +1,6 @@
 use crate::reader::DataFrame;
 use crate::utils::is_missing;
+use std::collections::HashMap;
 
 /// Inferred type for a column.
 #[derive(Debug, Clone, PartialEq)]
@@ -19,13 +20,72 @@ impl std::fmt::Display for ColumnType {
     }
 }
 
+/// Storage kind of a numeric column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumericKind {
+    Integer,
+    Float,
+}
+
+impl std::fmt::Display for NumericKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NumericKind::Integer => write!(f, "Integer"),
+            NumericKind::Float => write!(f, "Float"),
+        }
+    }
+}
+
 /// Information about a column's type.
 #[derive(Debug, Clone)]
 pub struct ColumnTypeInfo {
     pub name: String,
     pub col_type: ColumnType,
+    /// Integer vs float, for numeric columns only.
+    pub numeric_kind: Option<NumericKind>,
     pub unique_count: usize,
     pub levels: Vec<String>,
+    /// Share of non-missing values taken by the most common value (`None` if all missing).
+    pub dominant_share: Option<f64>,
+}
+
+impl ColumnTypeInfo {
+    /// Returns true if all non-missing values are identical.
+    pub fn is_constant(&self) -> bool {
+        self.dominant_share == Some(1.0)
+    }
+
+    /// Quality note: "constant", or "near-constant" when one value takes more than
+    /// the `near_constant` fraction of non-missing values.
+    pub fn quality_note(&self, near_constant: Option<f64>) -> Option<String> {
+        let share = self.dominant_share?;
+        if self.is_constant() {
+            Some("constant".to_string())
+        } else if near_constant.is_some_and(|t| share > t) {
+            Some(format!("near-constant ({:.1}% one value)", share * 100.0))
+        } else {
+            None
+        }
+    }
+
+    /// Type shown to users: "Integer"/"Float" for numeric columns, else the column type.
+    pub fn type_label(&self) -> String {
+        match self.numeric_kind {
+            Some(kind) => kind.to_string(),
+            None => self.col_type.to_string(),
+        }
+    }
+}
+
+/// Returns true if a value reads as a boolean: true/false, yes/no or 1/0, in any case.
+pub fn is_boolean_value(v: &str) -> bool {
+    let lower = v.to_lowercase();
+    lower == "true"
+        || lower == "false"
+        || lower == "yes"
+        || lower == "no"
+        || lower == "1"
+        || lower == "0"
 }
 
 /// Returns true if all non-missing values look boolean.
@@ -34,26 +94,78 @@ fn is_boolean(values: &[&str]) -> bool {
     if non_missing.is_empty() {
         return false;
     }
-    non_missing.iter().all(|v| {
-        let lower = v.to_lowercase();
-        lower == "true"
-            || lower == "false"
-            || lower == "yes"
-            || lower == "no"
-            || lower == "1"
-            || lower == "0"
-    })
+    non_missing.iter().all(|v| is_boolean_value(v))
 }
 
-/// Returns true if most non-missing values can be parsed as numbers.
-fn is_numeric(values: &[&str]) -> bool {
-    let non_missing: Vec<&str> = values.iter().copied().filter(|v| !is_missing(v)).collect();
-    if non_missing.is_empty() {
-        return false;
-    }
-    let parseable = non_missing.iter().filter(|v| v.parse::<f64>().is_ok()).count();
+/// Returns true if enough of `non_missing` values parse as numbers for a numeric column.
+pub fn mostly_numeric(parseable: usize, non_missing: usize) -> bool {
     // Consider numeric if >= 80% of non-missing values parse as numbers
-    (parseable as f64 / non_missing.len() as f64) >= 0.8
+    non_missing > 0 && parseable as f64 / non_missing as f64 >= 0.8
+}
+
+/// Returns true if most non-missing values can be parsed as numbers, given
+/// (value, count) pairs.
+fn is_numeric(counts: &[(&str, usize)]) -> bool {
+    let (mut non_missing, mut parseable) = (0, 0);
+    for &(v, n) in counts.iter().filter(|(v, _)| !is_missing(v)) {
+        non_missing += n;
+        if v.parse::<f64>().is_ok() {
+            parseable += n;
+        }
+    }
+    mostly_numeric(parseable, non_missing)
+}
+
+/// Returns true if every non-missing value parses as a number with no fractional part.
+fn is_integer(values: &[&str]) -> bool {
+    let mut non_missing = values.iter().filter(|v| !is_missing(v)).peekable();
+    non_missing.peek().is_some()
+        && non_missing.all(|v| {
+            v.trim()
+                .parse::<f64>()
+                .is_ok_and(|x| x.is_finite() && x.fract() == 0.0)
+        })
+}
+
+/// Share of the most common value among (non-missing value, count) pairs; numbers
+/// compare by value, so "1" and "1.0" match.
+fn dominant_share(counts: &[(&str, usize)]) -> Option<f64> {
+    let mut merged: HashMap<String, usize> = HashMap::new();
+    let mut total = 0;
+    for &(v, n) in counts {
+        let key = match v.parse::<f64>() {
+            Ok(x) => x.to_string(),
+            Err(_) => v.to_string(),
+        };
+        *merged.entry(key).or_insert(0) += n;
+        total += n;
+    }
+    merged.values().max().map(|&top| top as f64 / total as f64)
+}
+
+/// Boolean if every non-missing value looks boolean, numeric if most parse as numbers.
+///
+/// Takes (value, count) pairs, so raw cells (each counted once) and tallies of
+/// distinct values are classified alike.
+fn classify(counts: &[(&str, usize)]) -> ColumnType {
+    let values: Vec<&str> = counts.iter().map(|&(v, _)| v).collect();
+    if is_boolean(&values) {
+        ColumnType::Boolean
+    } else if is_numeric(counts) {
+        ColumnType::Numeric
+    } else {
+        ColumnType::Categorical
+    }
+}
+
+/// Each cell of a column as a (value, 1) pair for `classify` and `dominant_share`.
+fn counted_once<'a>(values: &[&'a str]) -> Vec<(&'a str, usize)> {
+    values.iter().map(|&v| (v, 1)).collect()
+}
+
+/// Type of one column by the rules of `infer_types`, without collecting its distinct values.
+pub fn column_type(df: &DataFrame, col: &str) -> Option<ColumnType> {
+    df.column(col).map(|values| classify(&counted_once(&values)))
 }
 
 /// Infer the type of each column in the DataFrame.
@@ -70,12 +182,14 @@ pub fn infer_types(df: &DataFrame) -> Vec<ColumnTypeInfo> {
             unique_set.dedup();
             let unique_count = unique_set.len();
 
-            let col_type = if is_boolean(&values) {
-                ColumnType::Boolean
-            } else if is_numeric(&values) {
-                ColumnType::Numeric
+            let col_type = classify(&counted_once(&values));
+
+            let numeric_kind = if col_type != ColumnType::Numeric {
+                None
+            } else if is_integer(&values) {
+                Some(NumericKind::Integer)
             } else {
-                ColumnType::Categorical
+                Some(NumericKind::Float)
             };
 
             let levels = if col_type == ColumnType::Categorical || col_type == ColumnType::Boolean {
@@ -91,8 +205,10 @@ pub fn infer_types(df: &DataFrame) -> Vec<ColumnTypeInfo> {
             results.push(ColumnTypeInfo {
                 name: header.clone(),
                 col_type,
+                numeric_kind,
                 unique_count,
                 levels,
+                dominant_share: dominant_share(&counted_once(&non_missing)),
             });
         }
     }
@@ -100,6 +216,44 @@ pub fn infer_types(df: &DataFrame) -> Vec<ColumnTypeInfo> {
     results
 }
 
+/// Infer a column's type from the counts of its distinct non-missing values.
+///
+/// Applies the same rules as `infer_types`, for callers that never hold the
+/// whole column, such as the streaming reader.
+pub fn infer_from_counts(name: &str, counts: &HashMap<String, usize>) -> ColumnTypeInfo {
+    let mut counted: Vec<(&str, usize)> = counts.iter().map(|(v, &n)| (v.as_str(), n)).collect();
+    counted.sort();
+    let unique_set: Vec<&str> = counted.iter().map(|&(v, _)| v).collect();
+    let unique_count = unique_set.len();
+
+    let col_type = classify(&counted);
+
+    let numeric_kind = if col_type != ColumnType::Numeric {
+        None
+    } else if is_integer(&unique_set) {
+        Some(NumericKind::Integer)
+    } else {
+        Some(NumericKind::Float)
+    };
+
+    let levels = if col_type == ColumnType::Numeric {
+        vec!["-".to_string()]
+    } else if unique_count <= 20 {
+        unique_set.iter().map(|v| v.to_string()).collect()
+    } else {
+        vec![format!("({} unique)", unique_count)]
+    };
+
+    ColumnTypeInfo {
+        name: name.to_string(),
+        col_type,
+        numeric_kind,
+        unique_count,
+        levels,
+        dominant_share: dominant_share(&counted),
+    }
+}
+
 /// Returns the names of columns that are numeric.
 pub fn numeric_columns(df: &DataFrame) -> Vec<String> {
     infer_types(df)
@@ -151,33 +305,98 @@ mod tests {
 
     #[test]
     fn test_is_numeric_integers() {
-        assert!(is_numeric(&["1", "2", "3", "100"]));
+        assert!(is_numeric(&counted_once(&["1", "2", "3", "100"])));
     }
 
     #[test]
     fn test_is_numeric_floats() {
-        assert!(is_numeric(&["1.5", "2.7", "3.14"]));
+        assert!(is_numeric(&counted_once(&["1.5", "2.7", "3.14"])));
     }
 
     #[test]
     fn test_is_numeric_with_missing() {
-        assert!(is_numeric(&["1", "NA", "3", ""]));
+        assert!(is_numeric(&counted_once(&["1", "NA", "3", ""])));
     }
 
     #[test]
     fn test_is_numeric_mostly_numeric() {
         // 80% threshold: 4 out of 5 non-missing are numeric
-        assert!(is_numeric(&["1", "2", "3", "4", "hello"]));
+        assert!(is_numeric(&counted_once(&["1", "2", "3", "4", "hello"])));
     }
 
     #[test]
     fn test_is_numeric_text() {
-        assert!(!is_numeric(&["hello", "world", "foo"]));
+        assert!(!is_numeric(&counted_once(&["hello", "world", "foo"])));
     }
 
     #[test]
     fn test_is_numeric_all_missing() {
-        assert!(!is_numeric(&["NA", "", "null"]));
+        assert!(!is_numeric(&counted_once(&["NA", "", "null"])));
+    }
+
+    #[test]
+    fn test_infer_from_counts_matches_infer_types() {
+        let mut df = reader::read_file("tests/data/sample.csv").unwrap();
+        df.rows[0][0] = "1.0".to_string();
+        for (i, info) in infer_types(&df).iter().enumerate() {
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for row in df.rows.iter().filter(|r| !is_missing(&r[i])) {
+                *counts.entry(row[i].clone()).or_insert(0) += 1;
+            }
+            let tallied = infer_from_counts(&info.name, &counts);
+            assert_eq!(tallied.col_type, info.col_type, "{}", info.name);
+            assert_eq!(tallied.numeric_kind, info.numeric_kind, "{}", info.name);
+            assert_eq!(tallied.levels, info.levels, "{}", info.name);
+            assert_eq!(tallied.dominant_share, info.dominant_share, "{}", info.name);
+        }
+    }
+
+    #[test]
+    fn test_is_integer() {
+        assert!(is_integer(&["1", "-20", "NA", "300"]));
+        assert!(is_integer(&["1.0", "2"]));
+        assert!(!is_integer(&["1", "2.5"]));
+        assert!(!is_integer(&["1", "2", "abc"]));
+        assert!(!is_integer(&["NA", ""]));
+    }
+
+    #[test]
+    fn test_infer_numeric_kind() {
+        let df = reader::read_file("tests/data/sample.csv").unwrap();
+        let types = infer_types(&df);
+        let find = |name: &str| types.iter().find(|t| t.name == name).unwrap();
+
+        assert_eq!(find("id").numeric_kind, Some(NumericKind::Integer));
+        assert_eq!(find("id").type_label(), "Integer");
+        assert_eq!(find("city").numeric_kind, None);
+        assert_eq!(find("city").type_label(), "Categorical");
+    }
+
+    #[test]
+    fn test_constant_and_near_constant() {
+        let mut rows: Vec<Vec<String>> = (0..100)
+            .map(|i| {
+                vec![
+                    if i % 2 == 0 { "1" } else { "1.0" }.to_string(),
+                    if i == 0 { "b" } else { "a" }.to_string(),
+                    i.to_string(),
+                ]
+            })
+            .collect();
+        rows[5][0] = "NA".to_string();
+        let df = DataFrame::from_rows(&["flat", "mostly", "id"], &rows);
+        let types = infer_types(&df);
+
+        assert!(types[0].is_constant());
+        assert_eq!(types[0].quality_note(None).as_deref(), Some("constant"));
+        assert!(!types[1].is_constant());
+        assert_eq!(types[1].quality_note(None), None);
+        assert_eq!(
+            types[1].quality_note(Some(0.95)).as_deref(),
+            Some("near-constant (99.0% one value)")
+        );
+        assert_eq!(types[1].quality_note(Some(0.995)), None);
+        assert_eq!(types[2].quality_note(Some(0.5)), None);
     }
 
     #[test]
@@ -1,9 +1,41 @@
+use anyhow::{bail, Result};
+use regex::Regex;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::sync::RwLock;
+
+/// Extra tokens treated as missing for this run, set from `--na-values`.
+static EXTRA_MISSING: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Treat `tokens` as missing in addition to the built-in representations.
+///
+/// Applies process-wide, replacing any tokens set before. Tokens are trimmed and
+/// matched exactly (case-sensitive) against trimmed values.
+pub fn set_missing_values(tokens: &[String]) {
+    let tokens = tokens
+        .iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+    if let Ok(mut extra) = EXTRA_MISSING.write() {
+        *extra = tokens;
+    }
+}
+
 /// Check if a value represents a missing value.
 ///
 /// Recognizes common missing/null representations found in CSV/TSV data files,
-/// including NA, null, NaN, empty strings, dots, dashes, and None.
+/// including NA, null, NaN, empty strings, dots, dashes, and None, plus any
+/// tokens registered with `set_missing_values`.
 pub fn is_missing(val: &str) -> bool {
     let v = val.trim();
+    is_builtin_missing(v)
+        || EXTRA_MISSING
+            .read()
+            .is_ok_and(|extra| extra.iter().any(|t| t == v))
+}
+
+/// The fixed set of missing representations, for an already trimmed value.
+fn is_builtin_missing(v: &str) -> bool {
     v.is_empty()
         || v == "NA"
         || v == "na"
@@ -19,10 +51,199 @@ pub fn is_missing(val: &str) -> bool {
         || v == "none"
 }
 
+/// Width of the terminal in columns, from `COLUMNS` or the attached TTY.
+///
+/// Returns `None` when neither is available (e.g. output is piped).
+pub fn terminal_width() -> Option<usize> {
+    parse_columns(std::env::var("COLUMNS").ok().as_deref())
+        .or_else(|| terminal_size::terminal_size().map(|(w, _)| w.0 as usize))
+}
+
+/// Parse a `COLUMNS`-style value, ignoring empty, zero or malformed input.
+fn parse_columns(value: Option<&str>) -> Option<usize> {
+    value?.trim().parse().ok().filter(|&w: &usize| w > 0)
+}
+
+/// Plot width for a terminal of `term_width` columns, leaving room for axis labels.
+///
+/// Falls back to `fallback` when the terminal width is unknown.
+pub fn plot_width(term_width: Option<usize>, fallback: usize) -> usize {
+    match term_width {
+        Some(w) => (w * 3 / 4).max(20),
+        None => fallback,
+    }
+}
+
+/// True when both stdin and stdout are attached to a terminal, so a prompt can be answered.
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+/// Option picked by `answer`: a 1-based number from the list, or an exact name.
+fn parse_choice(answer: &str, options: &[String]) -> Option<String> {
+    let answer = answer.trim();
+    if let Some(name) = options.iter().find(|o| *o == answer) {
+        return Some(name.clone());
+    }
+    let index: usize = answer.parse().ok()?;
+    options.get(index.checked_sub(1)?).cloned()
+}
+
+/// List `options` as a numbered menu on stderr and read the user's pick from stdin.
+///
+/// Asks again after an invalid answer; an empty answer or end of input is an error.
+pub fn prompt_choice(prompt: &str, options: &[String]) -> Result<String> {
+    for (i, option) in options.iter().enumerate() {
+        eprintln!("  {:>2}) {}", i + 1, option);
+    }
+    let mut stdin = io::stdin().lock();
+    loop {
+        eprint!("{} [1-{}]: ", prompt, options.len());
+        io::stderr().flush()?;
+        let mut answer = String::new();
+        if stdin.read_line(&mut answer)? == 0 || answer.trim().is_empty() {
+            bail!("Nothing was selected");
+        }
+        match parse_choice(&answer, options) {
+            Some(choice) => return Ok(choice),
+            None => eprintln!("'{}' is not one of the listed options", answer.trim()),
+        }
+    }
+}
+
+/// Parse an index range such as `5-8`.
+fn parse_range(spec: &str) -> Option<(usize, usize)> {
+    let (start, end) = spec.split_once('-')?;
+    Some((start.trim().parse().ok()?, end.trim().parse().ok()?))
+}
+
+/// Resolve column specs against `headers`: names, 1-based indices (`3`) and
+/// inclusive index ranges (`5-8`).
+///
+/// A spec matching a header literally is always taken as a name, so a column
+/// called `1` wins over the first column. Unknown names are passed through for
+/// the caller to report; indices outside the header row are an error.
+pub fn resolve_columns(specs: &[String], headers: &[String]) -> Result<Vec<String>> {
+    let column = |spec: &str, index: usize| -> Result<String> {
+        match index.checked_sub(1).and_then(|i| headers.get(i)) {
+            Some(name) => Ok(name.clone()),
+            None => bail!(
+                "Column index {} in '{}' is out of range (1-{})",
+                index,
+                spec,
+                headers.len()
+            ),
+        }
+    };
+
+    let mut resolved = Vec::new();
+    for spec in specs {
+        if headers.contains(spec) {
+            resolved.push(spec.clone());
+        } else if let Ok(index) = spec.parse::<usize>() {
+            resolved.push(column(spec, index)?);
+        } else if let Some((start, end)) = parse_range(spec) {
+            if start > end {
+                bail!("Invalid column range '{}': start is after end", spec);
+            }
+            for index in start..=end {
+                resolved.push(column(spec, index)?);
+            }
+        } else {
+            resolved.push(spec.clone());
+        }
+    }
+    Ok(resolved)
+}
+
+/// Headers whose name matches the regular expression `pattern`, in header order.
+///
+/// An invalid pattern, or one that matches no header, is an error naming the pattern.
+pub fn match_columns(pattern: &str, headers: &[String]) -> Result<Vec<String>> {
+    let re = match Regex::new(pattern) {
+        Ok(re) => re,
+        Err(e) => bail!("Invalid column pattern '{}': {}", pattern, e),
+    };
+    let matched: Vec<String> = headers.iter().filter(|h| re.is_match(h)).cloned().collect();
+    if matched.is_empty() {
+        bail!("No columns match the pattern '{}'", pattern);
+    }
+    Ok(matched)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn names(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_resolve_columns_mixed() {
+        let headers = names(&["id", "name", "age", "income", "score", "city"]);
+        let resolved = resolve_columns(&names(&["city", "1", "3-5"]), &headers).unwrap();
+        assert_eq!(resolved, ["city", "id", "age", "income", "score"]);
+        // Unknown names are left for the caller
+        assert_eq!(resolve_columns(&names(&["nope"]), &headers).unwrap(), ["nope"]);
+    }
+
+    #[test]
+    fn test_resolve_columns_prefers_names() {
+        let headers = names(&["2", "x", "1-2"]);
+        let resolved = resolve_columns(&names(&["2", "1-2", "1"]), &headers).unwrap();
+        assert_eq!(resolved, ["2", "1-2", "2"]);
+    }
+
+    #[test]
+    fn test_parse_choice_by_number_or_name() {
+        let options = names(&["age", "income", "2"]);
+        assert_eq!(parse_choice("1\n", &options).as_deref(), Some("age"));
+        assert_eq!(parse_choice(" income ", &options).as_deref(), Some("income"));
+        // A column named like a number is taken by name
+        assert_eq!(parse_choice("2", &options).as_deref(), Some("2"));
+        assert_eq!(parse_choice("3", &options).as_deref(), Some("2"));
+        assert_eq!(parse_choice("0", &options), None);
+        assert_eq!(parse_choice("4", &options), None);
+        assert_eq!(parse_choice("city", &options), None);
+    }
+
+    #[test]
+    fn test_resolve_columns_out_of_range() {
+        let headers = names(&["a", "b"]);
+        assert!(resolve_columns(&names(&["3"]), &headers).is_err());
+        assert!(resolve_columns(&names(&["0"]), &headers).is_err());
+        assert!(resolve_columns(&names(&["1-4"]), &headers).is_err());
+        assert!(resolve_columns(&names(&["2-1"]), &headers).is_err());
+    }
+
+    #[test]
+    fn test_match_columns_regex() {
+        let headers = names(&["id", "score_math", "score_art", "my_score", "q1_score"]);
+        let matched = match_columns("^score_", &headers).unwrap();
+        assert_eq!(matched, ["score_math", "score_art"]);
+
+        let err = match_columns("^nothing", &headers).unwrap_err();
+        assert!(err.to_string().contains("^nothing"));
+        assert!(match_columns("score_(", &headers).is_err());
+    }
+
+    #[test]
+    fn test_parse_columns() {
+        assert_eq!(parse_columns(Some("120")), Some(120));
+        assert_eq!(parse_columns(Some(" 90 ")), Some(90));
+        assert_eq!(parse_columns(Some("0")), None);
+        assert_eq!(parse_columns(Some("wide")), None);
+        assert_eq!(parse_columns(None), None);
+    }
+
+    #[test]
+    fn test_plot_width_fallback_and_fraction() {
+        assert_eq!(plot_width(None, 50), 50);
+        assert_eq!(plot_width(Some(160), 50), 120);
+        assert_eq!(plot_width(Some(10), 50), 20);
+    }
+
     #[test]
     fn test_missing_values() {
         assert!(is_missing(""));
@@ -56,4 +277,22 @@ mod tests {
         assert!(!is_missing("true"));
         assert!(!is_missing("N/A value"));
     }
+
+    #[test]
+    fn test_configured_missing_values() {
+        let df = crate::reader::DataFrame::from_column("x", &["1", "-999", "3"]);
+        assert!(!is_missing("-999"));
+        assert_eq!(df.numeric_column("x").unwrap()[1], Some(-999.0));
+
+        set_missing_values(&names(&["-999", " Unknown "]));
+        assert!(is_missing(" -999 "));
+        assert!(is_missing("Unknown"));
+        assert!(!is_missing("unknown"));
+        assert_eq!(
+            df.numeric_column("x").unwrap(),
+            vec![Some(1.0), None, Some(3.0)]
+        );
+        set_missing_values(&[]);
+        assert!(!is_missing("-999"));
+    }
 }
@@ -0,0 +1,77 @@
+use crate::reader::DataFrame;
+use serde::Serialize;
+
+/// A distinct value that failed to parse as a number, with where it occurs.
+#[derive(Debug, Clone, Serialize)]
+pub struct ParseFailure {
+    pub value: String,
+    /// 1-based data row numbers (the header row is not counted).
+    pub rows: Vec<usize>,
+}
+
+/// Parse check of one column that is expected to be numeric.
+#[derive(Debug, Clone, Serialize)]
+pub struct ColumnValidation {
+    pub name: String,
+    /// Non-missing cells that parsed as numbers.
+    pub parsed: usize,
+    /// Unparseable values in order of first appearance.
+    pub failures: Vec<ParseFailure>,
+}
+
+impl ColumnValidation {
+    /// Number of cells that failed to parse.
+    pub fn failed_cells(&self) -> usize {
+        self.failures.iter().map(|f| f.rows.len()).sum()
+    }
+}
+
+/// List the non-missing values of `column` that do not parse as numbers.
+///
+/// Returns `None` if the column does not exist.
+pub fn validate_column(df: &DataFrame, column: &str) -> Option<ColumnValidation> {
+    let (values, failed) = df.numeric_column_checked(column)?;
+
+    let mut failures: Vec<ParseFailure> = Vec::new();
+    for (row, value) in failed {
+        match failures.iter_mut().find(|f| f.value == value) {
+            Some(failure) => failure.rows.push(row + 1),
+            None => failures.push(ParseFailure {
+                value,
+                rows: vec![row + 1],
+            }),
+        }
+    }
+
+    Some(ColumnValidation {
+        name: column.to_string(),
+        parsed: values.iter().flatten().count(),
+        failures,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_groups_failures_by_value() {
+        let df = DataFrame::from_column("age", &["31", "3O", "", "42", "3O", "abc", "NA"]);
+        let result = validate_column(&df, "age").unwrap();
+        assert_eq!(result.parsed, 2);
+        assert_eq!(result.failed_cells(), 3);
+        assert_eq!(result.failures[0].value, "3O");
+        assert_eq!(result.failures[0].rows, vec![2, 5]);
+        assert_eq!(result.failures[1].value, "abc");
+        assert_eq!(result.failures[1].rows, vec![6]);
+    }
+
+    #[test]
+    fn test_validate_clean_and_missing_columns() {
+        let df = crate::reader::read_file("tests/data/sample.csv").unwrap();
+        let result = validate_column(&df, "income").unwrap();
+        assert!(result.failures.is_empty());
+        assert_eq!(result.parsed, 27);
+        assert!(validate_column(&df, "nope").is_none());
+    }
+}